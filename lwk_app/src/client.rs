@@ -1,4 +1,5 @@
 use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 
 use lwk_jade::TIMEOUT;
 use lwk_wollet::UnvalidatedRecipient;
@@ -8,6 +9,7 @@ use serde_json::value::to_raw_value;
 use serde_json::value::RawValue;
 use serde_json::Value;
 
+use crate::consts;
 use crate::error::Error;
 use crate::method::Method;
 use crate::reqwest_transport::ReqwestHttpTransport;
@@ -17,10 +19,40 @@ pub struct Client {
     client: jsonrpc::Client,
 }
 
+/// Call `poll` every `interval` until the transaction has `confirmations` confirmations,
+/// returning its confirmation height, or None if they are not reached within `timeout`
+///
+/// Errors while polling don't abort the wait, since the transaction has already been broadcast.
+pub(crate) fn wait_confirmations_with(
+    mut poll: impl FnMut() -> Result<response::WalletConfirmations, Error>,
+    confirmations: u32,
+    timeout: Duration,
+    interval: Duration,
+) -> Option<u32> {
+    let start = Instant::now();
+    loop {
+        match poll() {
+            Ok(r) if r.confirmations.unwrap_or(0) >= confirmations => return r.height,
+            Ok(_) => (),
+            Err(e) => tracing::info!("waiting confirmations: {e}"),
+        }
+        if start.elapsed() >= timeout {
+            return None;
+        }
+        std::thread::sleep(interval);
+    }
+}
+
 impl Client {
     pub fn new(addr: SocketAddr) -> Result<Self, Error> {
+        Self::with_auth_token(addr, None)
+    }
+
+    /// Create a client sending the given token in every request, needed if the server has been
+    /// configured with [`crate::Config::auth_tokens`]
+    pub fn with_auth_token(addr: SocketAddr, auth_token: Option<String>) -> Result<Self, Error> {
         let url = format!("http://{addr}");
-        let transport = ReqwestHttpTransport::new(url, TIMEOUT);
+        let transport = ReqwestHttpTransport::new(url, TIMEOUT, auth_token);
         let client = jsonrpc::Client::with_transport(transport);
         Ok(Self { client })
     }
@@ -35,15 +67,20 @@ impl Client {
         Res: DeserializeOwned,
     {
         let params = req.map(|req| to_raw_value(&req)).transpose()?;
+        let secret_params = method.has_secret_params();
         let method = method.to_string();
         let request = self.client.build_request(&method, params.as_deref());
-        tracing::trace!("---> {}", serde_json::to_string(&request)?);
+        if secret_params {
+            tracing::trace!("---> method: {method} params: <redacted>");
+        } else {
+            tracing::trace!("---> {}", serde_json::to_string(&request)?);
+        }
         let response = self.client.send_request(request)?;
         tracing::trace!("<--- {}", serde_json::to_string(&response)?);
         match response.result.as_ref() {
             Some(result) => Ok(serde_json::from_str(result.get())?),
             None => match response.error {
-                Some(rpc_err) => Err(Error::RpcError(rpc_err)),
+                Some(rpc_err) => Err(Error::from_rpc_error(rpc_err)),
                 None => Err(Error::NeitherResultNorErrorSet),
             },
         }
@@ -57,16 +94,26 @@ impl Client {
         self.make_request(Method::SignerGenerate, None::<Box<RawValue>>)
     }
 
+    pub fn signer_validate_mnemonic(
+        &self,
+        mnemonic: String,
+    ) -> Result<response::SignerValidateMnemonic, Error> {
+        let req = request::SignerValidateMnemonic { mnemonic };
+        self.make_request(Method::SignerValidateMnemonic, Some(req))
+    }
+
     pub fn signer_load_software(
         &self,
         name: String,
         mnemonic: String,
         persist: bool,
+        force: bool,
     ) -> Result<response::Signer, Error> {
         let req = request::SignerLoadSoftware {
             name,
             mnemonic,
             persist,
+            force,
         };
         self.make_request(Method::SignerLoadSoftware, Some(req))
     }
@@ -76,17 +123,37 @@ impl Client {
         name: String,
         id: String,
         emulator: Option<SocketAddr>,
+        force: bool,
     ) -> Result<response::Signer, Error> {
-        let req = request::SignerLoadJade { name, id, emulator };
+        let req = request::SignerLoadJade {
+            name,
+            id,
+            emulator,
+            force,
+        };
         self.make_request(Method::SignerLoadJade, Some(req))
     }
 
+    pub fn signer_load_many(
+        &self,
+        signers: Vec<request::SignerSpec>,
+        atomic: bool,
+    ) -> Result<response::SignerLoadMany, Error> {
+        let req = request::SignerLoadMany { signers, atomic };
+        self.make_request(Method::SignerLoadMany, Some(req))
+    }
+
     pub fn signer_load_external(
         &self,
         name: String,
         fingerprint: String,
+        force: bool,
     ) -> Result<response::Signer, Error> {
-        let req = request::SignerLoadExternal { name, fingerprint };
+        let req = request::SignerLoadExternal {
+            name,
+            fingerprint,
+            force,
+        };
         self.make_request(Method::SignerLoadExternal, Some(req))
     }
 
@@ -95,7 +162,20 @@ impl Client {
     }
 
     pub fn wallet_load(&self, descriptor: String, name: String) -> Result<response::Wallet, Error> {
-        let req = request::WalletLoad { descriptor, name };
+        self.wallet_load_with_fee_rate(descriptor, name, None)
+    }
+
+    pub fn wallet_load_with_fee_rate(
+        &self,
+        descriptor: String,
+        name: String,
+        default_fee_rate: Option<f32>,
+    ) -> Result<response::Wallet, Error> {
+        let req = request::WalletLoad {
+            descriptor,
+            name,
+            default_fee_rate,
+        };
         self.make_request(Method::WalletLoad, Some(req))
     }
 
@@ -109,6 +189,23 @@ impl Client {
         self.make_request(Method::SignerUnload, Some(req))
     }
 
+    pub fn signer_replace(&self, name: String) -> Result<response::SignerReplace, Error> {
+        let req = request::SignerReplace { name };
+        self.make_request(Method::SignerReplace, Some(req))
+    }
+
+    pub fn signer_matches_wallet(
+        &self,
+        signer_name: String,
+        wallet_name: String,
+    ) -> Result<response::SignerMatchesWallet, Error> {
+        let req = request::SignerMatchesWallet {
+            signer_name,
+            wallet_name,
+        };
+        self.make_request(Method::SignerMatchesWallet, Some(req))
+    }
+
     pub fn signer_list(&self) -> Result<response::SignerList, Error> {
         self.make_request(Method::SignerList, None::<Box<RawValue>>)
     }
@@ -117,11 +214,39 @@ impl Client {
         &self,
         name: String,
         with_tickers: bool,
+        with_reissuance_tokens: bool,
+        with_pending: bool,
     ) -> Result<response::WalletBalance, Error> {
-        let req = request::WalletBalance { name, with_tickers };
+        let req = request::WalletBalance {
+            name,
+            with_tickers,
+            with_reissuance_tokens,
+            with_pending,
+        };
         self.make_request(Method::WalletBalance, Some(req))
     }
 
+    pub fn wallet_confirmed_balance(
+        &self,
+        name: String,
+        min_confirmations: Option<u32>,
+    ) -> Result<response::WalletConfirmedBalance, Error> {
+        let req = request::WalletConfirmedBalance {
+            name,
+            min_confirmations,
+        };
+        self.make_request(Method::WalletConfirmedBalance, Some(req))
+    }
+
+    pub fn wallet_balance_all(
+        &self,
+        with_tickers: bool,
+        sync: bool,
+    ) -> Result<response::WalletBalanceAll, Error> {
+        let req = request::WalletBalanceAll { with_tickers, sync };
+        self.make_request(Method::WalletBalanceAll, Some(req))
+    }
+
     pub fn wallet_address(
         &self,
         name: String,
@@ -129,6 +254,7 @@ impl Client {
         signer: Option<String>,
         with_text_qr: bool,
         with_uri_qr: Option<u8>,
+        with_payment_request: bool,
     ) -> Result<response::WalletAddress, Error> {
         let req = request::WalletAddress {
             name,
@@ -136,24 +262,199 @@ impl Client {
             signer,
             with_text_qr,
             with_uri_qr,
+            with_payment_request,
         };
         self.make_request(Method::WalletAddress, Some(req))
     }
 
+    pub fn wallet_confirm_address(
+        &self,
+        name: String,
+        index: u32,
+        signer: String,
+    ) -> Result<response::WalletConfirmAddress, Error> {
+        let req = request::WalletConfirmAddress {
+            name,
+            index,
+            signer,
+        };
+        self.make_request(Method::WalletConfirmAddress, Some(req))
+    }
+
+    pub fn verify_device_address(
+        &self,
+        wallet_name: String,
+        signer_name: String,
+        index: u32,
+    ) -> Result<response::VerifyDeviceAddress, Error> {
+        let req = request::VerifyDeviceAddress {
+            wallet_name,
+            signer_name,
+            index,
+        };
+        self.make_request(Method::VerifyDeviceAddress, Some(req))
+    }
+
     pub fn wallet_send_many(
         &self,
         name: String,
         addressees: Vec<UnvalidatedRecipient>,
         fee_rate: Option<f32>,
-    ) -> Result<response::Pset, Error> {
+    ) -> Result<response::WalletSendMany, Error> {
         let req = request::WalletSendMany {
             addressees: addressees.into_iter().map(unvalidate_addressee).collect(),
             fee_rate,
+            fee: None,
             name,
+            spend_path: None,
+            privacy: false,
         };
+        self.wallet_send_many_with_request(req)
+    }
+
+    /// Like [`Client::wallet_send_many()`] with all the options of the request, like an exact
+    /// fee, a spend path or the privacy option
+    pub fn wallet_send_many_with_request(
+        &self,
+        req: request::WalletSendMany,
+    ) -> Result<response::WalletSendMany, Error> {
         self.make_request(Method::WalletSendMany, Some(req))
     }
 
+    pub fn wallet_local_multisig_send(
+        &self,
+        name: String,
+        addressees: Vec<UnvalidatedRecipient>,
+        fee_rate: Option<f32>,
+        fee: Option<u64>,
+        signers: Vec<String>,
+    ) -> Result<response::WalletLocalMultisigSend, Error> {
+        let req = request::WalletLocalMultisigSend {
+            send: request::WalletSendMany {
+                addressees: addressees.into_iter().map(unvalidate_addressee).collect(),
+                fee_rate,
+                fee,
+                name,
+                spend_path: None,
+                privacy: false,
+            },
+            signers,
+        };
+        self.make_request(Method::WalletLocalMultisigSend, Some(req))
+    }
+
+    pub fn wallet_consolidate(
+        &self,
+        name: String,
+        fee_rate: Option<f32>,
+        max_inputs: Option<u32>,
+        target_address: Option<String>,
+    ) -> Result<response::WalletConsolidate, Error> {
+        let req = request::WalletConsolidate {
+            name,
+            fee_rate,
+            max_inputs,
+            target_address,
+        };
+        self.make_request(Method::WalletConsolidate, Some(req))
+    }
+
+    pub fn wallet_max_spendable(
+        &self,
+        name: String,
+        asset: Option<String>,
+        fee_rate: Option<f32>,
+        address: Option<String>,
+    ) -> Result<response::WalletMaxSpendable, Error> {
+        let req = request::WalletMaxSpendable {
+            name,
+            asset,
+            fee_rate,
+            address,
+        };
+        self.make_request(Method::WalletMaxSpendable, Some(req))
+    }
+
+    pub fn sweep_key(
+        &self,
+        name: String,
+        wif: String,
+        blinding_key: String,
+        script_type: Option<String>,
+        fee_rate: Option<f32>,
+    ) -> Result<response::SweepKey, Error> {
+        let req = request::SweepKey {
+            name,
+            wif,
+            blinding_key,
+            script_type,
+            fee_rate,
+        };
+        self.make_request(Method::SweepKey, Some(req))
+    }
+
+    pub fn wallet_validate_addressees(
+        &self,
+        addressees: Vec<UnvalidatedRecipient>,
+    ) -> Result<response::WalletValidateAddressees, Error> {
+        let req = request::WalletValidateAddressees {
+            addressees: addressees.into_iter().map(unvalidate_addressee).collect(),
+        };
+        self.make_request(Method::WalletValidateAddressees, Some(req))
+    }
+
+    pub fn wallet_add_to_pset(
+        &self,
+        name: String,
+        pset: String,
+        add_inputs: Vec<String>,
+        add_outputs: Vec<UnvalidatedRecipient>,
+        fee: u64,
+        blind: bool,
+    ) -> Result<response::Pset, Error> {
+        let req = request::WalletAddToPset {
+            name,
+            pset,
+            add_inputs,
+            add_outputs: add_outputs.into_iter().map(unvalidate_addressee).collect(),
+            fee,
+            blind,
+        };
+        self.make_request(Method::WalletAddToPset, Some(req))
+    }
+
+    pub fn wallet_swap_propose(
+        &self,
+        name: String,
+        send_asset: String,
+        send_amount: u64,
+        receive_asset: String,
+        receive_amount: u64,
+    ) -> Result<response::Pset, Error> {
+        let req = request::WalletSwapPropose {
+            name,
+            send_asset,
+            send_amount,
+            receive_asset,
+            receive_amount,
+        };
+        self.make_request(Method::WalletSwapPropose, Some(req))
+    }
+
+    pub fn wallet_swap_accept(
+        &self,
+        name: String,
+        pset: String,
+        fee_rate: Option<f32>,
+    ) -> Result<response::Pset, Error> {
+        let req = request::WalletSwapAccept {
+            name,
+            pset,
+            fee_rate,
+        };
+        self.make_request(Method::WalletSwapAccept, Some(req))
+    }
+
     pub fn signer_singlesig_descriptor(
         &self,
         name: String,
@@ -168,22 +469,113 @@ impl Client {
         self.make_request(Method::SignerSinglesigDescriptor, Some(req))
     }
 
+    pub fn signer_singlesig_descriptor_set(
+        &self,
+        name: String,
+    ) -> Result<response::SignerSinglesigDescriptorSet, Error> {
+        let req = request::SignerSinglesigDescriptorSet { name };
+        self.make_request(Method::SignerSinglesigDescriptorSet, Some(req))
+    }
+
     pub fn wallet_multisig_descriptor(
         &self,
         descriptor_blinding_key: String,
         multisig_kind: String,
         threshold: u32,
         keyorigin_xpubs: Vec<String>,
+        with_jade_name: bool,
     ) -> Result<response::WalletMultisigDescriptor, Error> {
         let req = request::WalletMultisigDescriptor {
             descriptor_blinding_key,
             multisig_kind,
             threshold,
             keyorigin_xpubs,
+            with_jade_name,
         };
         self.make_request(Method::WalletMultisigDescriptor, Some(req))
     }
 
+    pub fn wallet_compile_policy(
+        &self,
+        policy: String,
+        descriptor_blinding_key: String,
+    ) -> Result<response::WalletCompilePolicy, Error> {
+        let req = request::WalletCompilePolicy {
+            policy,
+            descriptor_blinding_key,
+        };
+        self.make_request(Method::WalletCompilePolicy, Some(req))
+    }
+
+    pub fn wallet_descriptor_checksum(
+        &self,
+        descriptor: String,
+    ) -> Result<response::WalletDescriptorChecksum, Error> {
+        let req = request::WalletDescriptorChecksum { descriptor };
+        self.make_request(Method::WalletDescriptorChecksum, Some(req))
+    }
+
+    pub fn wallet_check_network(
+        &self,
+        descriptor: String,
+    ) -> Result<response::WalletCheckNetwork, Error> {
+        let req = request::WalletCheckNetwork { descriptor };
+        self.make_request(Method::WalletCheckNetwork, Some(req))
+    }
+
+    pub fn wallet_convert_blinding_key(
+        &self,
+        input: String,
+        to: String,
+        descriptor: Option<String>,
+    ) -> Result<response::WalletConvertBlindingKey, Error> {
+        let req = request::WalletConvertBlindingKey {
+            input,
+            to,
+            descriptor,
+        };
+        self.make_request(Method::WalletConvertBlindingKey, Some(req))
+    }
+
+    pub fn wallet_rebroadcast_pending(
+        &self,
+        name: String,
+    ) -> Result<response::WalletRebroadcastPending, Error> {
+        let req = request::WalletRebroadcastPending { name };
+        self.make_request(Method::WalletRebroadcastPending, Some(req))
+    }
+
+    pub fn wallet_pset_fee(&self, pset: String) -> Result<response::WalletPsetFee, Error> {
+        let req = request::WalletPsetFee { pset };
+        self.make_request(Method::WalletPsetFee, Some(req))
+    }
+
+    pub fn wallet_check_spend_path(
+        &self,
+        name: String,
+        pset: String,
+    ) -> Result<response::WalletCheckSpendPath, Error> {
+        let req = request::WalletCheckSpendPath { name, pset };
+        self.make_request(Method::WalletCheckSpendPath, Some(req))
+    }
+
+    pub fn wallet_convert_pset_version(
+        &self,
+        pset: String,
+        version: u32,
+    ) -> Result<response::WalletConvertPsetVersion, Error> {
+        let req = request::WalletConvertPsetVersion { pset, version };
+        self.make_request(Method::WalletConvertPsetVersion, Some(req))
+    }
+
+    pub fn wallet_strip_signatures(
+        &self,
+        pset: String,
+    ) -> Result<response::WalletStripSignatures, Error> {
+        let req = request::WalletStripSignatures { pset };
+        self.make_request(Method::WalletStripSignatures, Some(req))
+    }
+
     pub fn signer_xpub(
         &self,
         name: String,
@@ -202,23 +594,77 @@ impl Client {
         self.make_request(Method::SignerRegisterMultisig, Some(req))
     }
 
-    pub fn signer_sign(&self, name: String, pset: String) -> Result<response::Pset, Error> {
-        let req = request::SignerSign { name, pset };
+    pub fn signer_sign(
+        &self,
+        name: String,
+        pset: String,
+        inputs: Option<Vec<u32>>,
+    ) -> Result<response::Pset, Error> {
+        let req = request::SignerSign { name, pset, inputs };
         self.make_request(Method::SignerSign, Some(req))
     }
 
+    pub fn signer_can_sign(
+        &self,
+        name: String,
+        wallet: String,
+        pset: String,
+    ) -> Result<response::SignerCanSign, Error> {
+        let req = request::SignerCanSign { name, wallet, pset };
+        self.make_request(Method::SignerCanSign, Some(req))
+    }
+
+    pub fn signer_capabilities(&self, name: String) -> Result<response::SignerCapabilities, Error> {
+        let req = request::SignerCapabilities { name };
+        self.make_request(Method::SignerCapabilities, Some(req))
+    }
+
+    pub fn wallet_broadcast_batch(
+        &self,
+        name: String,
+        psets: Vec<String>,
+    ) -> Result<response::WalletBroadcastBatch, Error> {
+        let req = request::WalletBroadcastBatch { name, psets };
+        self.make_request(Method::WalletBroadcastBatch, Some(req))
+    }
+
     pub fn wallet_broadcast(
         &self,
         name: String,
         dry_run: bool,
         pset: String,
+    ) -> Result<response::WalletBroadcast, Error> {
+        self.wallet_broadcast_with_wait(name, dry_run, pset, None, None)
+    }
+
+    pub fn wallet_broadcast_with_wait(
+        &self,
+        name: String,
+        dry_run: bool,
+        pset: String,
+        wait_confirmations: Option<u32>,
+        wait_timeout_secs: Option<u64>,
     ) -> Result<response::WalletBroadcast, Error> {
         let req = request::WalletBroadcast {
-            name,
+            name: name.clone(),
             dry_run,
             pset,
         };
-        self.make_request(Method::WalletBroadcast, Some(req))
+        let mut r: response::WalletBroadcast =
+            self.make_request(Method::WalletBroadcast, Some(req))?;
+        let Some(confirmations) = wait_confirmations.filter(|c| *c > 0 && !dry_run) else {
+            return Ok(r);
+        };
+        // The server replies immediately, the wait is done here so that it doesn't block the
+        // other requests to the server
+        let timeout = wait_timeout_secs
+            .map_or(consts::BROADCAST_WAIT_TIMEOUT, Duration::from_secs)
+            .min(consts::BROADCAST_WAIT_MAX_TIMEOUT);
+        let poll = || self.wallet_confirmations(name.clone(), r.txid.clone());
+        r.confirmation_height =
+            wait_confirmations_with(poll, confirmations, timeout, consts::BROADCAST_WAIT_POLL);
+        r.wait_timed_out = r.confirmation_height.is_none();
+        Ok(r)
     }
 
     pub fn wallet_details(&self, name: String) -> Result<response::WalletDetails, Error> {
@@ -226,6 +672,47 @@ impl Client {
         self.make_request(Method::WalletDetails, Some(req))
     }
 
+    pub fn wallet_descriptor(
+        &self,
+        name: String,
+        include_internal: bool,
+    ) -> Result<response::WalletDescriptor, Error> {
+        let req = request::WalletDescriptor {
+            name,
+            include_internal,
+        };
+        self.make_request(Method::WalletDescriptor, Some(req))
+    }
+
+    pub fn wallet_sync_progress(
+        &self,
+        name: String,
+    ) -> Result<response::WalletSyncProgress, Error> {
+        let req = request::WalletSyncProgress { name };
+        self.make_request(Method::WalletSyncProgress, Some(req))
+    }
+
+    pub fn wallet_cancel_sync(&self, name: String) -> Result<response::WalletCancelSync, Error> {
+        let req = request::WalletCancelSync { name };
+        self.make_request(Method::WalletCancelSync, Some(req))
+    }
+
+    pub fn wallet_set_backend(
+        &self,
+        name: String,
+        electrum_url: String,
+        tls: bool,
+        cert_fingerprint: Option<String>,
+    ) -> Result<response::WalletSetBackend, Error> {
+        let req = request::WalletSetBackend {
+            name,
+            electrum_url,
+            tls,
+            cert_fingerprint,
+        };
+        self.make_request(Method::WalletSetBackend, Some(req))
+    }
+
     pub fn signer_details(&self, name: String) -> Result<response::SignerDetails, Error> {
         let req = request::SignerDetails { name };
         self.make_request(Method::SignerDetails, Some(req))
@@ -235,11 +722,33 @@ impl Client {
         &self,
         name: String,
         pset: Vec<String>,
+        pset_file: Vec<String>,
     ) -> Result<response::WalletCombine, Error> {
-        let req = request::WalletCombine { name, pset };
+        let req = request::WalletCombine {
+            name,
+            pset,
+            pset_file,
+        };
         self.make_request(Method::WalletCombine, Some(req))
     }
 
+    pub fn wallet_merge_signatures(
+        &self,
+        psets: Vec<String>,
+    ) -> Result<response::WalletCombine, Error> {
+        let req = request::WalletMergeSignatures { psets };
+        self.make_request(Method::WalletMergeSignatures, Some(req))
+    }
+
+    pub fn wallet_signing_request(
+        &self,
+        name: String,
+        pset: String,
+    ) -> Result<response::WalletSigningRequest, Error> {
+        let req = request::WalletSigningRequest { name, pset };
+        self.make_request(Method::WalletSigningRequest, Some(req))
+    }
+
     pub fn wallet_pset_details(
         &self,
         name: String,
@@ -254,11 +763,123 @@ impl Client {
         self.make_request(Method::WalletPsetDetails, Some(req))
     }
 
-    pub fn wallet_utxos(&self, name: String) -> Result<response::WalletUtxos, Error> {
-        let req = request::WalletUtxos { name };
+    pub fn wallet_check_pset_spendable(
+        &self,
+        name: String,
+        pset: String,
+    ) -> Result<response::WalletCheckPsetSpendable, Error> {
+        let req = request::WalletCheckPsetSpendable { name, pset };
+        self.make_request(Method::WalletCheckPsetSpendable, Some(req))
+    }
+
+    pub fn wallet_privacy_analysis(
+        &self,
+        name: String,
+        pset: String,
+    ) -> Result<response::WalletPrivacyAnalysis, Error> {
+        let req = request::WalletPrivacyAnalysis { name, pset };
+        self.make_request(Method::WalletPrivacyAnalysis, Some(req))
+    }
+
+    pub fn wallet_session_create(
+        &self,
+        name: String,
+        pset: String,
+    ) -> Result<response::WalletSession, Error> {
+        let req = request::WalletSessionCreate { name, pset };
+        self.make_request(Method::WalletSessionCreate, Some(req))
+    }
+
+    pub fn wallet_session_add(
+        &self,
+        session_id: String,
+        pset: String,
+    ) -> Result<response::WalletSession, Error> {
+        let req = request::WalletSessionAdd { session_id, pset };
+        self.make_request(Method::WalletSessionAdd, Some(req))
+    }
+
+    pub fn wallet_session_get(&self, session_id: String) -> Result<response::WalletSession, Error> {
+        let req = request::WalletSessionGet { session_id };
+        self.make_request(Method::WalletSessionGet, Some(req))
+    }
+
+    pub fn wallet_utxos(
+        &self,
+        name: String,
+        format: Option<String>,
+    ) -> Result<response::WalletUtxos, Error> {
+        let req = request::WalletUtxos { name, format };
         self.make_request(Method::WalletUtxos, Some(req))
     }
 
+    pub fn wallet_used_addresses(
+        &self,
+        name: String,
+    ) -> Result<response::WalletUsedAddresses, Error> {
+        let req = request::WalletUsedAddresses { name };
+        self.make_request(Method::WalletUsedAddresses, Some(req))
+    }
+
+    pub fn wallet_multisig_fee_estimate(
+        &self,
+        name: String,
+        pset: String,
+    ) -> Result<response::WalletMultisigFeeEstimate, Error> {
+        let req = request::WalletMultisigFeeEstimate { name, pset };
+        self.make_request(Method::WalletMultisigFeeEstimate, Some(req))
+    }
+
+    pub fn wallet_dust_report(
+        &self,
+        name: String,
+        fee_rate: Option<f32>,
+    ) -> Result<response::WalletDustReport, Error> {
+        let req = request::WalletDustReport { name, fee_rate };
+        self.make_request(Method::WalletDustReport, Some(req))
+    }
+
+    pub fn wallet_discover_funds(
+        &self,
+        name: String,
+        max_gap: Option<u32>,
+    ) -> Result<response::WalletDiscoverFunds, Error> {
+        let req = request::WalletDiscoverFunds { name, max_gap };
+        self.make_request(Method::WalletDiscoverFunds, Some(req))
+    }
+
+    pub fn wallet_lock_utxo(
+        &self,
+        name: String,
+        outpoint: String,
+        expires_at: Option<u64>,
+    ) -> Result<response::WalletLockUtxo, Error> {
+        let req = request::WalletLockUtxo {
+            name,
+            outpoint,
+            expires_at,
+        };
+        self.make_request(Method::WalletLockUtxo, Some(req))
+    }
+
+    pub fn wallet_unlock_utxo(
+        &self,
+        name: String,
+        outpoint: String,
+    ) -> Result<response::WalletUnlockUtxo, Error> {
+        let req = request::WalletUnlockUtxo { name, outpoint };
+        self.make_request(Method::WalletUnlockUtxo, Some(req))
+    }
+
+    pub fn wallet_confirmations(
+        &self,
+        name: String,
+        txid: String,
+    ) -> Result<response::WalletConfirmations, Error> {
+        let req = request::WalletConfirmations { name, txid };
+        self.make_request(Method::WalletConfirmations, Some(req))
+    }
+
     pub fn wallet_txs(
         &self,
         name: String,
@@ -268,6 +889,15 @@ impl Client {
         self.make_request(Method::WalletTxs, Some(req))
     }
 
+    pub fn wallet_issuance_tx(
+        &self,
+        name: String,
+        asset_id: String,
+    ) -> Result<response::WalletIssuanceTx, Error> {
+        let req = request::WalletIssuanceTx { name, asset_id };
+        self.make_request(Method::WalletIssuanceTx, Some(req))
+    }
+
     pub fn wallet_tx(
         &self,
         name: String,
@@ -282,6 +912,41 @@ impl Client {
         self.make_request(Method::WalletTx, Some(req))
     }
 
+    pub fn wallet_decode_data_outputs(
+        &self,
+        name: String,
+        txid: String,
+    ) -> Result<response::WalletDecodeDataOutputs, Error> {
+        let req = request::WalletDecodeDataOutputs { name, txid };
+        self.make_request(Method::WalletDecodeDataOutputs, Some(req))
+    }
+
+    pub fn wallet_export_watch_only(
+        &self,
+        name: String,
+    ) -> Result<response::WalletExportWatchOnly, Error> {
+        let req = request::WalletExportWatchOnly { name };
+        self.make_request(Method::WalletExportWatchOnly, Some(req))
+    }
+
+    pub fn wallet_export_to(
+        &self,
+        name: String,
+        target: String,
+    ) -> Result<response::WalletExportTo, Error> {
+        let req = request::WalletExportTo { name, target };
+        self.make_request(Method::WalletExportTo, Some(req))
+    }
+
+    pub fn wallet_blinding_factors(
+        &self,
+        name: String,
+        txid: String,
+    ) -> Result<response::WalletBlindingFactors, Error> {
+        let req = request::WalletBlindingFactors { name, txid };
+        self.make_request(Method::WalletBlindingFactors, Some(req))
+    }
+
     pub fn wallet_set_tx_memo(
         &self,
         name: String,
@@ -316,7 +981,8 @@ impl Client {
         address_token: Option<String>,
         contract: Option<String>,
         fee_rate: Option<f32>,
-    ) -> Result<response::Pset, Error> {
+        blinded_issuance: bool,
+    ) -> Result<response::WalletIssue, Error> {
         let req = request::WalletIssue {
             name,
             satoshi_asset,
@@ -325,6 +991,7 @@ impl Client {
             address_token,
             contract,
             fee_rate,
+            blinded_issuance,
         };
         self.make_request(Method::WalletIssue, Some(req))
     }
@@ -388,6 +1055,11 @@ impl Client {
         self.make_request(Method::AssetDetails, Some(req))
     }
 
+    pub fn asset_metadata(&self, asset_id: String) -> Result<response::AssetMetadata, Error> {
+        let req = request::AssetMetadata { asset_id };
+        self.make_request(Method::AssetMetadata, Some(req))
+    }
+
     pub fn asset_list(&self) -> Result<response::AssetList, Error> {
         self.make_request(Method::AssetList, None::<Box<RawValue>>)
     }
@@ -434,6 +1106,43 @@ impl Client {
         self.make_request(Method::SignerJadeId, Some(req))
     }
 
+    pub fn health(&self) -> Result<response::Health, Error> {
+        self.make_request(Method::Health, None::<Box<RawValue>>)
+    }
+
+    pub fn ping_backend(&self) -> Result<response::PingBackend, Error> {
+        self.make_request(Method::PingBackend, None::<Box<RawValue>>)
+    }
+
+    pub fn set_defaults(
+        &self,
+        fee_rate: Option<f32>,
+        confirmation_target: Option<u32>,
+    ) -> Result<response::Empty, Error> {
+        let req = request::SetDefaults {
+            fee_rate,
+            confirmation_target,
+        };
+        self.make_request(Method::SetDefaults, Some(req))
+    }
+
+    pub fn get_defaults(&self) -> Result<response::Defaults, Error> {
+        self.make_request(Method::GetDefaults, None::<Box<RawValue>>)
+    }
+
+    pub fn list_variants(&self) -> Result<response::ListVariants, Error> {
+        self.make_request(Method::ListVariants, None::<Box<RawValue>>)
+    }
+
+    pub fn get_config(&self) -> Result<response::GetConfig, Error> {
+        self.make_request(Method::GetConfig, None::<Box<RawValue>>)
+    }
+
+    pub fn unload_all(&self, wallets: bool, signers: bool) -> Result<response::UnloadAll, Error> {
+        let req = request::UnloadAll { wallets, signers };
+        self.make_request(Method::UnloadAll, Some(req))
+    }
+
     pub fn scan(&self) -> Result<Value, Error> {
         self.make_request(Method::Scan, None::<Box<RawValue>>)
     }
@@ -445,10 +1154,12 @@ impl Client {
     }
 }
 
-fn unvalidate_addressee(a: lwk_wollet::UnvalidatedRecipient) -> request::UnvalidatedAddressee {
+/// The addressee of the requests, like [`request::WalletSendMany`], for the given recipient
+pub fn unvalidate_addressee(a: lwk_wollet::UnvalidatedRecipient) -> request::UnvalidatedAddressee {
     request::UnvalidatedAddressee {
         satoshi: a.satoshi,
         address: a.address,
         asset: a.asset,
+        uri: None,
     }
 }
@@ -1,13 +1,18 @@
 use lwk_jade::Network as JadeNetwork;
 use lwk_jade::TIMEOUT;
+use lwk_wollet::elements::encode::deserialize;
+use lwk_wollet::elements::hex::FromHex;
+use lwk_wollet::elements::pset::PartiallySignedTransaction;
 use lwk_wollet::elements::AssetId;
 use lwk_wollet::ElementsNetwork;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
+use crate::method::{Method, WalletAccess};
 use crate::{consts, Error};
 
 #[derive(Clone, Debug)]
@@ -16,18 +21,135 @@ pub struct Config {
     pub addr: SocketAddr,
     pub datadir: PathBuf,
     pub electrum_url: String,
+
+    /// Electrum servers tried in order when connecting to `electrum_url` fails
+    pub electrum_fallback_urls: Vec<String>,
+
     pub network: ElementsNetwork,
     pub tls: bool,
     pub validate_domain: bool,
 
+    /// SHA-256 fingerprint of the certificate the Electrum server at `electrum_url` must present,
+    /// in hex with optional `:` separators. Used only with `tls`, it replaces the domain
+    /// validation. The fallback servers are not pinned.
+    pub electrum_cert_fingerprint: Option<String>,
+
     pub explorer_url: String,
 
     // Unfortunately we cannot always derive the "api" url from "explorer_url", thus we need two separate values
     pub esplora_api_url: String,
 
     pub registry_url: String,
+
+    /// How long contracts fetched from the registry are served from cache before being refreshed
+    pub registry_cache_ttl: Duration,
+
     pub timeout: Duration,
     pub scanning_interval: Duration,
+
+    /// How long PSET signing sessions are kept
+    pub session_ttl: Duration,
+
+    /// If true, broadcasting a transaction already in mempool or in chain is not an error
+    ///
+    /// False by default, so that the broadcast fails as it did before this was configurable
+    pub already_known_is_success: bool,
+
+    /// Maximum number of wallets that can be loaded at the same time
+    pub max_wallets: usize,
+
+    /// Maximum number of signers that can be loaded at the same time
+    pub max_signers: usize,
+
+    /// Maximum number of connections open at the same time to the Electrum servers, by scans,
+    /// broadcasts and fee estimations of every wallet
+    pub max_backend_connections: usize,
+
+    /// PSET details warn if the fee is higher than this fraction of the policy asset sent
+    pub fee_warning_fraction: f64,
+
+    /// Allow methods returning secret data, like blinding factors.
+    ///
+    /// Descriptors are not gated: the confidential descriptor is needed to use the wallet and
+    /// it's already returned by `wallet_details` and `wallet_descriptor`.
+    pub allow_secret_export: bool,
+
+    /// Maximum number of requests per second of the given methods, for each auth token.
+    /// Methods not present are not limited.
+    pub rate_limits: HashMap<Method, u32>,
+
+    /// If the datadir is not writable, keep the state and the wallets data in memory instead of
+    /// failing. Nothing is persisted and everything is lost when the server stops.
+    pub in_memory_fallback: bool,
+
+    /// Directory from which methods can read PSET files, if not set PSET file paths are refused
+    pub pset_dir: Option<PathBuf>,
+
+    /// Tokens accepted by the server with the methods they are allowed to call.
+    /// If empty, every request is accepted.
+    pub auth_tokens: HashMap<String, Permissions>,
+
+    /// Tokens allowed to call wallet methods only on the given wallets.
+    /// Tokens not present can access every wallet.
+    pub auth_token_wallets: HashMap<String, HashSet<String>>,
+
+    /// INSECURE, ONLY FOR TESTS: seed of the random generator used to generate signers, so that
+    /// the generated mnemonics are always the same. Anyone knowing the seed knows the keys of
+    /// the generated signers, the server refuses to start with it on mainnet.
+    pub insecure_signer_seed: Option<u64>,
+}
+
+/// The methods a client authenticated with a given token is allowed to call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Permissions {
+    /// Only methods which don't change the server state and can't be used to move funds
+    Readonly,
+
+    /// Any method
+    Full,
+
+    /// An explicit set of methods
+    Methods(HashSet<Method>),
+}
+
+impl Permissions {
+    pub fn allows(&self, method: &Method) -> bool {
+        match self {
+            Permissions::Readonly => method.is_readonly(),
+            Permissions::Full => true,
+            Permissions::Methods(methods) => methods.contains(method),
+        }
+    }
+}
+
+impl FromStr for Permissions {
+    type Err = Error;
+
+    /// Parse one of the builtin roles `readonly` or `full`,
+    /// or a comma separated list of method names, e.g. `wallet_balance,wallet_txs`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "readonly" => Permissions::Readonly,
+            "full" => Permissions::Full,
+            _ => Permissions::Methods(
+                s.split(',')
+                    .map(|m| m.trim().parse())
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+/// Expensive methods hitting the backend are limited, cheap ones are not
+fn default_rate_limits() -> HashMap<Method, u32> {
+    [
+        (Method::Scan, consts::SCAN_RATE_LIMIT),
+        (Method::WalletDiscoverFunds, consts::SCAN_RATE_LIMIT),
+        (Method::WalletBroadcast, consts::BROADCAST_RATE_LIMIT),
+        (Method::WalletBroadcastBatch, consts::BROADCAST_RATE_LIMIT),
+    ]
+    .into_iter()
+    .collect()
 }
 
 impl Config {
@@ -36,14 +158,30 @@ impl Config {
             addr: consts::DEFAULT_ADDR.into(),
             datadir,
             electrum_url: "blockstream.info:465".into(),
+            electrum_fallback_urls: vec![],
             network: ElementsNetwork::LiquidTestnet,
             tls: true,
             validate_domain: true,
+            electrum_cert_fingerprint: None,
             explorer_url: "https://blockstream.info/liquidtestnet/".into(),
             esplora_api_url: "https://blockstream.info/liquidtestnet/api/".into(),
             registry_url: "https://assets-testnet.blockstream.info/".into(),
+            registry_cache_ttl: consts::REGISTRY_CACHE_TTL,
             timeout: TIMEOUT,
             scanning_interval: consts::SCANNING_INTERVAL,
+            session_ttl: consts::SESSION_TTL,
+            already_known_is_success: false,
+            max_wallets: consts::MAX_WALLETS,
+            max_signers: consts::MAX_SIGNERS,
+            max_backend_connections: consts::MAX_BACKEND_CONNECTIONS,
+            fee_warning_fraction: consts::FEE_WARNING_FRACTION,
+            allow_secret_export: false,
+            insecure_signer_seed: None,
+            in_memory_fallback: false,
+            pset_dir: None,
+            rate_limits: default_rate_limits(),
+            auth_tokens: HashMap::new(),
+            auth_token_wallets: HashMap::new(),
         }
     }
 
@@ -52,14 +190,30 @@ impl Config {
             addr: consts::DEFAULT_ADDR.into(),
             datadir,
             electrum_url: "blockstream.info:995".into(),
+            electrum_fallback_urls: vec![],
             network: ElementsNetwork::Liquid,
             tls: true,
             validate_domain: true,
+            electrum_cert_fingerprint: None,
             explorer_url: "https://blockstream.info/liquid/".into(),
             esplora_api_url: "https://blockstream.info/liquid/api/".into(),
             registry_url: "https://assets.blockstream.info/".into(),
+            registry_cache_ttl: consts::REGISTRY_CACHE_TTL,
             timeout: TIMEOUT,
             scanning_interval: consts::SCANNING_INTERVAL,
+            session_ttl: consts::SESSION_TTL,
+            already_known_is_success: false,
+            max_wallets: consts::MAX_WALLETS,
+            max_signers: consts::MAX_SIGNERS,
+            max_backend_connections: consts::MAX_BACKEND_CONNECTIONS,
+            fee_warning_fraction: consts::FEE_WARNING_FRACTION,
+            allow_secret_export: false,
+            insecure_signer_seed: None,
+            in_memory_fallback: false,
+            pset_dir: None,
+            rate_limits: default_rate_limits(),
+            auth_tokens: HashMap::new(),
+            auth_token_wallets: HashMap::new(),
         }
     }
 
@@ -72,15 +226,31 @@ impl Config {
             addr: consts::DEFAULT_ADDR.into(),
             datadir,
             electrum_url: "".into(),
+            electrum_fallback_urls: vec![],
             network: ElementsNetwork::ElementsRegtest { policy_asset },
             tls: false,
             validate_domain: false,
+            electrum_cert_fingerprint: None,
             explorer_url: "".into(),
             esplora_api_url: "".into(),
             registry_url: "".into(),
+            registry_cache_ttl: consts::REGISTRY_CACHE_TTL,
             timeout: TIMEOUT,
             // Scan more frequently while testing
             scanning_interval: Duration::from_secs(1),
+            session_ttl: consts::SESSION_TTL,
+            already_known_is_success: false,
+            max_wallets: consts::MAX_WALLETS,
+            max_signers: consts::MAX_SIGNERS,
+            max_backend_connections: consts::MAX_BACKEND_CONNECTIONS,
+            fee_warning_fraction: consts::FEE_WARNING_FRACTION,
+            allow_secret_export: false,
+            insecure_signer_seed: None,
+            in_memory_fallback: false,
+            pset_dir: None,
+            rate_limits: default_rate_limits(),
+            auth_tokens: HashMap::new(),
+            auth_token_wallets: HashMap::new(),
         }
     }
 
@@ -107,6 +277,48 @@ impl Config {
         Ok(path)
     }
 
+    /// Check the datadir of the network can be created, read and written
+    pub fn check_datadir(&self) -> Result<(), Error> {
+        let path = self.datadir.join(self.network.as_str());
+        let not_writable = |e: std::io::Error| {
+            Error::DatadirNotWritable(path.display().to_string(), e.to_string())
+        };
+        fs::create_dir_all(&path).map_err(not_writable)?;
+        fs::read_dir(&path).map_err(not_writable)?;
+        let probe = path.join(".write_check");
+        fs::write(&probe, []).map_err(not_writable)?;
+        fs::remove_file(&probe).map_err(not_writable)?;
+        Ok(())
+    }
+
+    /// Read a PSET, in base64 or binary, from a file in `pset_dir`
+    ///
+    /// Relative paths are resolved from `pset_dir`, paths resolving outside of it are refused.
+    pub fn read_pset_file(&self, path: &str) -> Result<PartiallySignedTransaction, Error> {
+        let not_allowed = |msg: String| Error::PsetFileNotAllowed(path.to_string(), msg);
+        let dir = self
+            .pset_dir
+            .as_ref()
+            .ok_or_else(|| not_allowed("the server has no pset directory".into()))?
+            .canonicalize()
+            .map_err(|e| not_allowed(e.to_string()))?;
+        let file = dir
+            .join(path)
+            .canonicalize()
+            .map_err(|e| not_allowed(e.to_string()))?;
+        if !file.starts_with(&dir) {
+            return Err(not_allowed("the file is outside the pset directory".into()));
+        }
+        let bytes = fs::read(&file).map_err(|e| not_allowed(e.to_string()))?;
+        let base64 = std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|s| PartiallySignedTransaction::from_str(s.trim()).ok());
+        match base64 {
+            Some(pset) => Ok(pset),
+            None => deserialize(&bytes).map_err(|e| not_allowed(format!("invalid pset: {e}"))),
+        }
+    }
+
     /// Returns the path of the state file under datadir
     pub fn state_path(&self) -> Result<PathBuf, Error> {
         let mut path = self.datadir()?;
@@ -119,12 +331,119 @@ impl Config {
         matches!(self.network, ElementsNetwork::Liquid)
     }
 
-    fn electrum_url(&self) -> lwk_wollet::ElectrumUrl {
-        lwk_wollet::ElectrumUrl::new(&self.electrum_url, self.tls, self.validate_domain)
+    /// Check the given token allows calling `method` with `params`, always succeeds if no token
+    /// is configured
+    ///
+    /// Tokens restricted to some wallets can call methods accessing wallets only if every wallet
+    /// named in the params is one of them, see [`Method::wallet_access`]. Methods accessing
+    /// wallets not named in the params, like `unload_all` or `wallet_list`, are refused.
+    pub fn check_permission(
+        &self,
+        auth_token: Option<&str>,
+        method: &Method,
+        params: Option<&serde_json::Value>,
+    ) -> Result<(), Error> {
+        if self.auth_tokens.is_empty() {
+            return Ok(());
+        }
+        let (token, permissions) = auth_token
+            .and_then(|t| self.auth_tokens.get_key_value(t))
+            .ok_or(Error::Unauthorized)?;
+        if !permissions.allows(method) {
+            return Err(Error::MethodNotPermitted(method.to_string()));
+        }
+        let Some(wallets) = self.auth_token_wallets.get(token) else {
+            return Ok(());
+        };
+        let pointers = match method.wallet_access() {
+            WalletAccess::None => return Ok(()),
+            WalletAccess::Unnamed => return Err(Error::MethodNotPermitted(method.to_string())),
+            WalletAccess::Params(pointers) => pointers,
+        };
+        for pointer in pointers {
+            let name = params
+                .and_then(|p| p.pointer(pointer))
+                .and_then(|n| n.as_str());
+            match name {
+                Some(name) if wallets.contains(name) => (),
+                Some(name) => {
+                    return Err(Error::WalletNotPermitted {
+                        method: method.to_string(),
+                        wallet: name.to_string(),
+                    })
+                }
+                None => return Err(Error::MethodNotPermitted(method.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    /// The Electrum servers to connect to, in order of preference
+    pub fn electrum_urls(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.electrum_url).chain(self.electrum_fallback_urls.iter())
+    }
+
+    /// The pinned certificate of `electrum_url`, if any
+    pub fn electrum_cert_sha256(&self) -> Result<Option<[u8; 32]>, Error> {
+        self.electrum_cert_fingerprint
+            .as_deref()
+            .map(cert_sha256)
+            .transpose()
+    }
+
+    /// The url to connect to the given Electrum server, accepting only the certificate with
+    /// `cert_fingerprint` if given, otherwise with the configured domain validation
+    pub fn electrum_url_for(
+        &self,
+        url: &str,
+        tls: bool,
+        cert_fingerprint: Option<&str>,
+    ) -> Result<lwk_wollet::ElectrumUrl, Error> {
+        Ok(match cert_fingerprint.map(cert_sha256).transpose()? {
+            Some(cert) if tls => lwk_wollet::ElectrumUrl::new_pinned(url, cert),
+            _ => lwk_wollet::ElectrumUrl::new(url, tls, self.validate_domain),
+        })
     }
 
     pub fn electrum_client(&self) -> Result<lwk_wollet::ElectrumClient, Error> {
+        Ok(self.electrum_client_with_url()?.0)
+    }
+
+    /// Connect to the first reachable Electrum server, returning also its url
+    pub fn electrum_client_with_url(&self) -> Result<(lwk_wollet::ElectrumClient, String), Error> {
         // TODO cache it instead of recreating every time
-        Ok(lwk_wollet::ElectrumClient::new(&self.electrum_url())?)
+        let mut last_err = None;
+        for (i, url) in self.electrum_urls().enumerate() {
+            // The pinned certificate is the one of `electrum_url`, not of the fallbacks
+            let cert_fingerprint = match i {
+                0 => self.electrum_cert_fingerprint.as_deref(),
+                _ => None,
+            };
+            let electrum_url = self.electrum_url_for(url, self.tls, cert_fingerprint)?;
+            match lwk_wollet::ElectrumClient::new(&electrum_url) {
+                Ok(client) => {
+                    tracing::debug!("using electrum server {url}");
+                    return Ok((client, url.clone()));
+                }
+                Err(e) => {
+                    tracing::debug!("cannot connect to electrum server {url}: {e}");
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.expect("electrum_urls is never empty").into())
     }
 }
+
+/// The SHA-256 of a certificate fingerprint in hex with optional `:` separators
+fn cert_sha256(fingerprint: &str) -> Result<[u8; 32], Error> {
+    let hex: String = fingerprint.chars().filter(|c| *c != ':').collect();
+    Vec::<u8>::from_hex(&hex)
+        .ok()
+        .and_then(|v| <[u8; 32]>::try_from(v).ok())
+        .ok_or_else(|| {
+            Error::Generic(format!(
+                "invalid electrum certificate fingerprint '{fingerprint}', expected a SHA-256 in hex"
+            ))
+        })
+}
@@ -6,3 +6,67 @@ pub const DEFAULT_ADDR: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0
 pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const SCANNING_INTERVAL: Duration = Duration::from_secs(10);
+
+pub const REGISTRY_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+pub const TIP_CACHE_TTL: Duration = Duration::from_secs(5);
+
+pub const UTXO_LOCK_TTL: Duration = Duration::from_secs(60 * 60);
+
+pub const SESSION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub const MAX_WALLETS: usize = 1_000;
+
+pub const MAX_SIGNERS: usize = 1_000;
+
+pub const DUST_THRESHOLD: u64 = 546;
+
+pub const CONSOLIDATE_MAX_INPUTS: u32 = 100;
+
+pub const MAX_BACKEND_CONNECTIONS: usize = 4;
+
+pub const BACKEND_PERMIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Parts of the broadcast errors, lowercase, meaning the transaction is already in mempool or in
+/// chain
+///
+/// Electrum servers relay the node rejection reason, each message comes from:
+/// - `txn-already-in-mempool`: the node has the transaction in its mempool
+/// - `txn-already-known`: the node has the transaction in its mempool with a different witness
+/// - `transaction already in block chain`: the node has it confirmed (elementsd based on Bitcoin
+///   Core before 25.0)
+/// - `transaction outputs already in utxo set`: the node has it confirmed (later versions)
+/// - `already in blockchain`: the wording some Electrum servers use for a confirmed transaction
+pub const ALREADY_KNOWN_MESSAGES: &[&str] = &[
+    "txn-already-in-mempool",
+    "txn-already-known",
+    "transaction already in block chain",
+    "transaction outputs already in utxo set",
+    "already in blockchain",
+];
+
+pub const BROADCAST_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub const BROADCAST_WAIT_POLL: Duration = Duration::from_secs(1);
+
+pub const BROADCAST_WAIT_MAX_TIMEOUT: Duration = Duration::from_secs(600);
+
+pub const DISCOVER_DEFAULT_GAP: u32 = 100;
+
+pub const DISCOVER_MAX_GAP: u32 = 1_000;
+
+pub const DISCOVER_MAX_INDEX: u32 = 10_000;
+
+pub const DEFAULT_FEE_RATE: f32 = 100.0;
+
+pub const FEE_WARNING_FRACTION: f64 = 0.1;
+
+pub const SCAN_RATE_LIMIT: u32 = 10;
+
+pub const BROADCAST_RATE_LIMIT: u32 = 20;
+
+pub const MAX_CONFIRMATION_TARGET: u32 = 1_008;
+
+pub const SIGNING_REQUEST_VERSION: u32 = 1;
+
+pub const EXPORT_GAP_LIMIT: u32 = 20;
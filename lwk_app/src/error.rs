@@ -1,7 +1,8 @@
 use std::sync::{MutexGuard, PoisonError};
 
+use jsonrpc::error::RpcError;
 use lwk_tiny_jrpc::error::ImplementationDefinedCode;
-use serde_json::json;
+use serde_json::{json, Value};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -51,7 +52,7 @@ pub enum Error {
     NeitherResultNorErrorSet,
 
     #[error("Rpc returned an error {0:?}")]
-    RpcError(jsonrpc::error::RpcError),
+    RpcError(RpcError),
 
     #[error("Signer New Error: {0}")]
     SignerNew(#[from] lwk_signer::NewError),
@@ -71,15 +72,44 @@ pub enum Error {
     #[error("Tx '{0}' was not found in wallet '{1}'")]
     WalletTxNotFound(String, String),
 
+    #[error("Asset '{0}' was not issued by wallet '{1}'")]
+    WalletIssuanceNotFound(String, String),
+
     #[error("Signer '{0}' does not exist")]
     SignerNotExist(String),
 
     #[error("Signer '{0}' is already loaded")]
     SignerAlreadyLoaded(String),
 
+    #[error(
+        "Signer '{name}' is already loaded with fingerprint {fingerprint}, use force to replace it"
+    )]
+    SignerNameInUse { name: String, fingerprint: String },
+
+    #[error("Cannot load more wallets, {0} are loaded which is the configured maximum")]
+    TooManyWallets(usize),
+
+    #[error("Cannot load more signers, {0} are loaded which is the configured maximum")]
+    TooManySigners(usize),
+
+    #[error("Backend {url} is not on the wallet network, its block at height {height} differs")]
+    WrongBackendNetwork { url: String, height: u32 },
+
+    #[error("Pset value balance does not hold: {0}")]
+    PsetValueBalance(String),
+
+    #[error("Invalid addressee at index {index}: {message}")]
+    InvalidAddressee { index: usize, message: String },
+
+    #[error("Signing session '{0}' does not exist or is expired")]
+    SessionNotExist(String),
+
     #[error("Asset '{0}' does not exist")]
     AssetNotExist(String),
 
+    #[error("No contract is known for asset '{0}'")]
+    AssetContractNotFound(String),
+
     #[error("Given transaction does not contain issuance of asset '{0}'")]
     InvalidIssuanceTxtForAsset(String),
 
@@ -92,6 +122,42 @@ pub enum Error {
     #[error(transparent)]
     MethodNotExist(#[from] crate::method::MethodNotExist),
 
+    #[error("Missing or unknown auth token")]
+    Unauthorized,
+
+    #[error("The rpc method '{0}' is not permitted for the given auth token")]
+    MethodNotPermitted(String),
+
+    #[error(
+        "The rpc method '{method}' is not permitted on wallet '{wallet}' for the given auth token"
+    )]
+    WalletNotPermitted { method: String, wallet: String },
+
+    #[error("The rpc method '{0}' returns secret data, start the server allowing secret export")]
+    SecretExportNotAllowed(String),
+
+    #[error("Datadir '{0}' is not writable: {1}")]
+    DatadirNotWritable(String, String),
+
+    #[error("The rpc method '{0}' is rate limited to {1} requests per second")]
+    RateLimited(String, u32),
+
+    #[error("Cannot read PSET file '{0}': {1}")]
+    PsetFileNotAllowed(String, String),
+
+    #[error("Key {key} is for {found}, but the server network needs {expected} keys")]
+    WrongKeyNetwork {
+        key: String,
+        found: String,
+        expected: String,
+    },
+
+    #[error("Too many connections to the backends, none was released in {0} seconds")]
+    BackendBusy(u64),
+
+    #[error("Invalid default {name}: {message}")]
+    InvalidDefault { name: String, message: String },
+
     #[error("Poison error: {0}")]
     PoisonError(String),
 
@@ -117,6 +183,12 @@ impl Error {
     pub fn as_impl_defined_code(&self) -> ImplementationDefinedCode {
         match self {
             Error::Jade(_) => ImplementationDefinedCode::new(-32_013).expect("static"),
+            Error::WrongKeyNetwork { .. } => {
+                ImplementationDefinedCode::new(-32_025).expect("static")
+            }
+            Error::Wollet(lwk_wollet::Error::ElectrumCertificateMismatch { .. }) => {
+                ImplementationDefinedCode::new(-32_024).expect("static")
+            }
             Error::Wollet(_) => ImplementationDefinedCode::new(-32_005).expect("static"),
             Error::SignerNew(_) => ImplementationDefinedCode::new(-32_006).expect("static"),
             Error::Signer(_) => ImplementationDefinedCode::new(-32_007).expect("static"),
@@ -125,9 +197,32 @@ impl Error {
                 ImplementationDefinedCode::new(-32_009).expect("static")
             }
             Error::SignerNotExist(_) => ImplementationDefinedCode::new(-32_010).expect("static"),
-            Error::SignerAlreadyLoaded(_) => {
+            Error::SignerAlreadyLoaded(_) | Error::SignerNameInUse { .. } => {
                 ImplementationDefinedCode::new(-32_011).expect("static")
             }
+            Error::Unauthorized => ImplementationDefinedCode::new(-32_014).expect("static"),
+            Error::MethodNotPermitted(_) => {
+                ImplementationDefinedCode::new(-32_015).expect("static")
+            }
+            Error::TooManyWallets(_) => ImplementationDefinedCode::new(-32_016).expect("static"),
+            Error::TooManySigners(_) => ImplementationDefinedCode::new(-32_017).expect("static"),
+            Error::InvalidAddressee { .. } => {
+                ImplementationDefinedCode::new(-32_018).expect("static")
+            }
+            Error::WalletNotPermitted { .. } => {
+                ImplementationDefinedCode::new(-32_019).expect("static")
+            }
+            Error::SecretExportNotAllowed(_) => {
+                ImplementationDefinedCode::new(-32_020).expect("static")
+            }
+            Error::DatadirNotWritable(..) => {
+                ImplementationDefinedCode::new(-32_021).expect("static")
+            }
+            Error::RateLimited(..) => ImplementationDefinedCode::new(-32_022).expect("static"),
+            Error::PsetFileNotAllowed(..) => {
+                ImplementationDefinedCode::new(-32_023).expect("static")
+            }
+            Error::BackendBusy(_) => ImplementationDefinedCode::new(-32_026).expect("static"),
 
             _ => lwk_tiny_jrpc::error::GENERIC,
         }
@@ -137,10 +232,122 @@ impl Error {
     pub fn as_error_value(&self) -> Option<serde_json::Value> {
         match self {
             Error::WalletNotExist(n) => Some(json!({"name": n.to_string()})),
+            Error::WalletAlreadyLoaded(n) => Some(json!({"name": n.to_string()})),
             Error::SignerNotExist(n) => Some(json!({"name": n.to_string()})),
+            Error::SignerAlreadyLoaded(n) => Some(json!({"name": n.to_string()})),
+            Error::TooManyWallets(max) | Error::TooManySigners(max) => Some(json!({"max": max})),
+            Error::SignerNameInUse { name, fingerprint } => {
+                Some(json!({"name": name, "fingerprint": fingerprint}))
+            }
+            Error::MethodNotPermitted(m) => Some(json!({"method": m.to_string()})),
+            Error::WalletNotPermitted { method, wallet } => {
+                Some(json!({"method": method, "wallet": wallet}))
+            }
+            Error::InvalidAddressee { index, .. } => Some(json!({"index": index})),
+            Error::SecretExportNotAllowed(m) => Some(json!({"method": m.to_string()})),
+            Error::DatadirNotWritable(path, _) => Some(json!({"path": path})),
+            Error::RateLimited(method, limit) => Some(json!({"method": method, "limit": limit})),
+            Error::PsetFileNotAllowed(path, _) => Some(json!({"path": path})),
+            Error::WrongKeyNetwork {
+                key,
+                found,
+                expected,
+            } => Some(json!({"key": key, "found": found, "expected": expected})),
+            Error::BackendBusy(seconds) => Some(json!({"seconds": seconds})),
+            Error::Wollet(lwk_wollet::Error::ElectrumCertificateMismatch { expected, found }) => {
+                Some(json!({"expected": expected, "found": found}))
+            }
+            _ => None,
+        }
+    }
+
+    /// Rebuild the error returned by the server from its code and data, so that clients can match
+    /// on the variants. Errors that can't be rebuilt are returned as [`Error::RpcError`]
+    pub fn from_rpc_error(e: RpcError) -> Self {
+        Self::decode_rpc_error(&e).unwrap_or(Error::RpcError(e))
+    }
+
+    fn decode_rpc_error(e: &RpcError) -> Option<Self> {
+        let data: Value = match &e.data {
+            Some(data) => serde_json::from_str(data.get()).ok()?,
+            None => Value::Null,
+        };
+        let str_field = |key: &str| data.get(key)?.as_str().map(String::from);
+        let u64_field = |key: &str| data.get(key)?.as_u64();
+        // The part of the message following the given prefix
+        let message_after = |prefix: String| e.message.strip_prefix(&prefix).map(String::from);
+
+        let error = match e.code {
+            -32_008 => Error::WalletNotExist(str_field("name")?),
+            -32_009 => Error::WalletAlreadyLoaded(str_field("name")?),
+            -32_010 => Error::SignerNotExist(str_field("name")?),
+            -32_011 => match str_field("fingerprint") {
+                Some(fingerprint) => Error::SignerNameInUse {
+                    name: str_field("name")?,
+                    fingerprint,
+                },
+                None => Error::SignerAlreadyLoaded(str_field("name")?),
+            },
+            -32_014 => Error::Unauthorized,
+            -32_015 => Error::MethodNotPermitted(str_field("method")?),
+            -32_016 => Error::TooManyWallets(u64_field("max")? as usize),
+            -32_017 => Error::TooManySigners(u64_field("max")? as usize),
+            -32_018 => {
+                let index = u64_field("index")? as usize;
+                Error::InvalidAddressee {
+                    index,
+                    message: message_after(format!("Invalid addressee at index {index}: "))?,
+                }
+            }
+            -32_019 => Error::WalletNotPermitted {
+                method: str_field("method")?,
+                wallet: str_field("wallet")?,
+            },
+            -32_020 => Error::SecretExportNotAllowed(str_field("method")?),
+            -32_021 => {
+                let path = str_field("path")?;
+                let message = message_after(format!("Datadir '{path}' is not writable: "))?;
+                Error::DatadirNotWritable(path, message)
+            }
+            -32_022 => Error::RateLimited(str_field("method")?, u64_field("limit")? as u32),
+            -32_023 => {
+                let path = str_field("path")?;
+                let message = message_after(format!("Cannot read PSET file '{path}': "))?;
+                Error::PsetFileNotAllowed(path, message)
+            }
+            -32_024 => Error::Wollet(lwk_wollet::Error::ElectrumCertificateMismatch {
+                expected: str_field("expected")?,
+                found: str_field("found")?,
+            }),
+            -32_025 => Error::WrongKeyNetwork {
+                key: str_field("key")?,
+                found: str_field("found")?,
+                expected: str_field("expected")?,
+            },
+            -32_026 => Error::BackendBusy(u64_field("seconds")?),
+            _ => return None,
+        };
+        Some(error)
+    }
+
+    /// The JSON-RPC error returned by the server, for the errors received by the client
+    pub fn as_rpc_error(&self) -> Option<RpcError> {
+        match self {
+            Error::RpcError(e) => Some(e.clone()),
+            e if Self::decode_rpc_error(&e.to_rpc_error()).is_some() => Some(e.to_rpc_error()),
             _ => None,
         }
     }
+
+    pub(crate) fn to_rpc_error(&self) -> RpcError {
+        RpcError {
+            code: i64::from(self.as_impl_defined_code()) as i32,
+            message: self.to_string(),
+            data: self
+                .as_error_value()
+                .and_then(|v| serde_json::value::to_raw_value(&v).ok()),
+        }
+    }
 }
 
 impl From<String> for Error {
@@ -19,7 +19,7 @@ pub struct RegistryData {
 pub fn get_registry_data(registry_url: &str, asset: &AssetId) -> Result<RegistryData, Error> {
     let url = format!("{registry_url}{asset}");
     tracing::debug!("getting registry data {url}");
-    let data: RegistryData = reqwest::blocking::get(url)?.json()?;
+    let data: RegistryData = reqwest::blocking::get(url)?.error_for_status()?.json()?;
     Ok(data)
 }
 
@@ -13,49 +13,109 @@ pub struct MethodNotExist {
     name: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(test, derive(enum_iterator::Sequence))]
 pub enum Method {
     Schema,
     Version,
+    Health,
+    PingBackend,
+    SetDefaults,
+    GetDefaults,
+    GetConfig,
+    ListVariants,
     Scan,
     Stop,
+    UnloadAll,
     WalletLoad,
     WalletUnload,
     WalletList,
     WalletDetails,
+    WalletDescriptor,
+    WalletSyncProgress,
+    WalletCancelSync,
+    WalletSetBackend,
     WalletAddress,
+    WalletConfirmAddress,
+    VerifyDeviceAddress,
     WalletBalance,
+    WalletBalanceAll,
+    WalletConfirmedBalance,
     WalletUtxos,
+    WalletUsedAddresses,
+    WalletDustReport,
+    WalletDiscoverFunds,
+    WalletConfirmations,
+    WalletLockUtxo,
+    WalletUnlockUtxo,
     WalletTxs,
+    WalletIssuanceTx,
     WalletTx,
+    WalletDecodeDataOutputs,
+    WalletBlindingFactors,
+    WalletExportWatchOnly,
+    WalletExportTo,
     WalletSendMany,
+    WalletLocalMultisigSend,
+    WalletValidateAddressees,
+    WalletConsolidate,
+    WalletMaxSpendable,
+    SweepKey,
+    WalletAddToPset,
+    WalletSwapPropose,
+    WalletSwapAccept,
     WalletIssue,
     WalletReissue,
     WalletBurn,
     WalletCombine,
+    WalletMergeSignatures,
     WalletBroadcast,
+    WalletBroadcastBatch,
+    WalletRebroadcastPending,
     WalletPsetDetails,
+    WalletCheckPsetSpendable,
+    WalletMultisigFeeEstimate,
+    WalletSigningRequest,
+    WalletPrivacyAnalysis,
+    WalletSessionCreate,
+    WalletSessionAdd,
+    WalletSessionGet,
     WalletMultisigDescriptor,
+    WalletCompilePolicy,
+    WalletDescriptorChecksum,
+    WalletCheckNetwork,
+    WalletConvertBlindingKey,
+    WalletConvertPsetVersion,
+    WalletStripSignatures,
+    WalletPsetFee,
+    WalletCheckSpendPath,
     WalletSetTxMemo,
     WalletSetAddrMemo,
     SignerGenerate,
+    SignerValidateMnemonic,
     SignerJadeId,
     SignerLoadSoftware,
     SignerLoadJade,
     SignerLoadExternal,
+    SignerLoadMany,
     SignerUnload,
+    SignerReplace,
+    SignerMatchesWallet,
     SignerList,
     SignerDetails,
     SignerXpub,
     SignerSign,
+    SignerCanSign,
+    SignerCapabilities,
     SignerSinglesigDescriptor,
+    SignerSinglesigDescriptorSet,
     SignerRegisterMultisig,
     AssetContract,
     AssetInsert,
     AssetRemove,
     AssetList,
     AssetDetails,
+    AssetMetadata,
     AssetFromExplorer,
     AssetPublish,
 }
@@ -65,92 +125,220 @@ impl Method {
             Direction::Request => match self {
                 Method::Schema => schema_for!(request::Schema),
                 Method::Version => schema_for!(request::Empty),
+                Method::Health => schema_for!(request::Empty),
+                Method::PingBackend => schema_for!(request::Empty),
+                Method::SetDefaults => schema_for!(request::SetDefaults),
+                Method::GetDefaults => schema_for!(request::Empty),
+                Method::GetConfig => schema_for!(request::Empty),
+                Method::ListVariants => schema_for!(request::Empty),
                 Method::Scan => schema_for!(request::Empty),
                 Method::Stop => schema_for!(request::Empty),
+                Method::UnloadAll => schema_for!(request::UnloadAll),
                 Method::WalletLoad => schema_for!(request::WalletLoad),
                 Method::WalletUnload => schema_for!(request::WalletUnload),
                 Method::WalletList => schema_for!(request::Empty),
                 Method::WalletDetails => schema_for!(request::WalletDetails),
+                Method::WalletDescriptor => schema_for!(request::WalletDescriptor),
+                Method::WalletSyncProgress => schema_for!(request::WalletSyncProgress),
+                Method::WalletCancelSync => schema_for!(request::WalletCancelSync),
+                Method::WalletSetBackend => schema_for!(request::WalletSetBackend),
                 Method::WalletAddress => schema_for!(request::WalletAddress),
+                Method::WalletConfirmAddress => schema_for!(request::WalletConfirmAddress),
+                Method::VerifyDeviceAddress => schema_for!(request::VerifyDeviceAddress),
                 Method::WalletBalance => schema_for!(request::WalletBalance),
+                Method::WalletBalanceAll => schema_for!(request::WalletBalanceAll),
+                Method::WalletConfirmedBalance => schema_for!(request::WalletConfirmedBalance),
                 Method::WalletUtxos => schema_for!(request::WalletUtxos),
+                Method::WalletUsedAddresses => schema_for!(request::WalletUsedAddresses),
+                Method::WalletDustReport => schema_for!(request::WalletDustReport),
+                Method::WalletDiscoverFunds => schema_for!(request::WalletDiscoverFunds),
+                Method::WalletConfirmations => schema_for!(request::WalletConfirmations),
+                Method::WalletLockUtxo => schema_for!(request::WalletLockUtxo),
+                Method::WalletUnlockUtxo => schema_for!(request::WalletUnlockUtxo),
                 Method::WalletTxs => schema_for!(request::WalletTxs),
+                Method::WalletIssuanceTx => schema_for!(request::WalletIssuanceTx),
                 Method::WalletTx => schema_for!(request::WalletTx),
+                Method::WalletDecodeDataOutputs => schema_for!(request::WalletDecodeDataOutputs),
+                Method::WalletBlindingFactors => schema_for!(request::WalletBlindingFactors),
+                Method::WalletExportWatchOnly => schema_for!(request::WalletExportWatchOnly),
+                Method::WalletExportTo => schema_for!(request::WalletExportTo),
                 Method::WalletSendMany => schema_for!(request::WalletSendMany),
+                Method::WalletLocalMultisigSend => schema_for!(request::WalletLocalMultisigSend),
+                Method::WalletValidateAddressees => schema_for!(request::WalletValidateAddressees),
+                Method::WalletConsolidate => schema_for!(request::WalletConsolidate),
+                Method::WalletMaxSpendable => schema_for!(request::WalletMaxSpendable),
+                Method::SweepKey => schema_for!(request::SweepKey),
+                Method::WalletAddToPset => schema_for!(request::WalletAddToPset),
+                Method::WalletSwapPropose => schema_for!(request::WalletSwapPropose),
+                Method::WalletSwapAccept => schema_for!(request::WalletSwapAccept),
                 Method::WalletIssue => schema_for!(request::WalletIssue),
                 Method::WalletReissue => schema_for!(request::WalletReissue),
                 Method::WalletBurn => schema_for!(request::WalletBurn),
                 Method::WalletCombine => schema_for!(request::WalletCombine),
+                Method::WalletMergeSignatures => schema_for!(request::WalletMergeSignatures),
                 Method::WalletBroadcast => schema_for!(request::WalletBroadcast),
+                Method::WalletBroadcastBatch => schema_for!(request::WalletBroadcastBatch),
+                Method::WalletRebroadcastPending => schema_for!(request::WalletRebroadcastPending),
                 Method::WalletPsetDetails => schema_for!(request::WalletPsetDetails),
+                Method::WalletCheckPsetSpendable => schema_for!(request::WalletCheckPsetSpendable),
+                Method::WalletMultisigFeeEstimate => {
+                    schema_for!(request::WalletMultisigFeeEstimate)
+                }
+                Method::WalletSigningRequest => schema_for!(request::WalletSigningRequest),
+                Method::WalletPrivacyAnalysis => schema_for!(request::WalletPrivacyAnalysis),
+                Method::WalletSessionCreate => schema_for!(request::WalletSessionCreate),
+                Method::WalletSessionAdd => schema_for!(request::WalletSessionAdd),
+                Method::WalletSessionGet => schema_for!(request::WalletSessionGet),
                 Method::WalletMultisigDescriptor => schema_for!(request::WalletMultisigDescriptor),
+                Method::WalletCompilePolicy => schema_for!(request::WalletCompilePolicy),
+                Method::WalletDescriptorChecksum => schema_for!(request::WalletDescriptorChecksum),
+                Method::WalletCheckNetwork => schema_for!(request::WalletCheckNetwork),
+                Method::WalletConvertBlindingKey => schema_for!(request::WalletConvertBlindingKey),
+                Method::WalletConvertPsetVersion => schema_for!(request::WalletConvertPsetVersion),
+                Method::WalletStripSignatures => schema_for!(request::WalletStripSignatures),
+                Method::WalletPsetFee => schema_for!(request::WalletPsetFee),
+                Method::WalletCheckSpendPath => schema_for!(request::WalletCheckSpendPath),
                 Method::WalletSetTxMemo => schema_for!(request::WalletSetTxMemo),
                 Method::WalletSetAddrMemo => schema_for!(request::WalletSetAddrMemo),
                 Method::SignerGenerate => schema_for!(request::Empty),
+                Method::SignerValidateMnemonic => schema_for!(request::SignerValidateMnemonic),
                 Method::SignerJadeId => schema_for!(request::Empty),
                 Method::SignerLoadSoftware => schema_for!(request::SignerLoadSoftware),
                 Method::SignerLoadJade => schema_for!(request::SignerLoadJade),
                 Method::SignerLoadExternal => schema_for!(request::SignerLoadExternal),
+                Method::SignerLoadMany => schema_for!(request::SignerLoadMany),
                 Method::SignerUnload => schema_for!(request::SignerUnload),
+                Method::SignerReplace => schema_for!(request::SignerReplace),
+                Method::SignerMatchesWallet => schema_for!(request::SignerMatchesWallet),
                 Method::SignerList => schema_for!(request::Empty),
                 Method::SignerDetails => schema_for!(request::SignerDetails),
                 Method::SignerXpub => schema_for!(request::SignerXpub),
                 Method::SignerSign => schema_for!(request::SignerSign),
+                Method::SignerCanSign => schema_for!(request::SignerCanSign),
+                Method::SignerCapabilities => schema_for!(request::SignerCapabilities),
                 Method::SignerSinglesigDescriptor => {
                     schema_for!(request::SignerSinglesigDescriptor)
                 }
+                Method::SignerSinglesigDescriptorSet => {
+                    schema_for!(request::SignerSinglesigDescriptorSet)
+                }
                 Method::SignerRegisterMultisig => schema_for!(request::SignerRegisterMultisig),
                 Method::AssetContract => schema_for!(request::AssetContract),
                 Method::AssetInsert => schema_for!(request::AssetInsert),
                 Method::AssetRemove => schema_for!(request::AssetRemove),
                 Method::AssetList => schema_for!(request::Empty),
                 Method::AssetDetails => schema_for!(request::AssetDetails),
+                Method::AssetMetadata => schema_for!(request::AssetMetadata),
                 Method::AssetFromExplorer => schema_for!(request::AssetFromExplorer),
                 Method::AssetPublish => schema_for!(request::AssetPublish),
             },
             Direction::Response => match self {
                 Method::Schema => return serde_json::from_str(include_str!("../schema.json")),
                 Method::Version => schema_for!(response::Version),
+                Method::Health => schema_for!(response::Health),
+                Method::PingBackend => schema_for!(response::PingBackend),
+                Method::SetDefaults => schema_for!(response::Empty),
+                Method::GetDefaults => schema_for!(response::Defaults),
+                Method::GetConfig => schema_for!(response::GetConfig),
+                Method::ListVariants => schema_for!(response::ListVariants),
                 Method::Scan => schema_for!(response::Empty),
                 Method::Stop => schema_for!(request::Empty),
+                Method::UnloadAll => schema_for!(response::UnloadAll),
                 Method::WalletLoad => schema_for!(response::Wallet),
                 Method::WalletUnload => schema_for!(response::WalletUnload),
                 Method::WalletList => schema_for!(response::WalletList),
                 Method::WalletDetails => schema_for!(response::WalletDetails),
+                Method::WalletDescriptor => schema_for!(response::WalletDescriptor),
+                Method::WalletSyncProgress => schema_for!(response::WalletSyncProgress),
+                Method::WalletCancelSync => schema_for!(response::WalletCancelSync),
+                Method::WalletSetBackend => schema_for!(response::WalletSetBackend),
                 Method::WalletAddress => schema_for!(response::WalletAddress),
+                Method::WalletConfirmAddress => schema_for!(response::WalletConfirmAddress),
+                Method::VerifyDeviceAddress => schema_for!(response::VerifyDeviceAddress),
                 Method::WalletBalance => schema_for!(response::WalletBalance),
+                Method::WalletBalanceAll => schema_for!(response::WalletBalanceAll),
+                Method::WalletConfirmedBalance => schema_for!(response::WalletConfirmedBalance),
                 Method::WalletUtxos => schema_for!(response::WalletUtxos),
+                Method::WalletUsedAddresses => schema_for!(response::WalletUsedAddresses),
+                Method::WalletDustReport => schema_for!(response::WalletDustReport),
+                Method::WalletDiscoverFunds => schema_for!(response::WalletDiscoverFunds),
+                Method::WalletConfirmations => schema_for!(response::WalletConfirmations),
+                Method::WalletLockUtxo => schema_for!(response::WalletLockUtxo),
+                Method::WalletUnlockUtxo => schema_for!(response::WalletUnlockUtxo),
                 Method::WalletTxs => schema_for!(response::WalletTxs),
+                Method::WalletIssuanceTx => schema_for!(response::WalletIssuanceTx),
                 Method::WalletTx => schema_for!(response::WalletTx),
-                Method::WalletSendMany => schema_for!(response::Pset),
-                Method::WalletIssue => schema_for!(response::Pset),
+                Method::WalletDecodeDataOutputs => schema_for!(response::WalletDecodeDataOutputs),
+                Method::WalletBlindingFactors => schema_for!(response::WalletBlindingFactors),
+                Method::WalletExportWatchOnly => schema_for!(response::WalletExportWatchOnly),
+                Method::WalletExportTo => schema_for!(response::WalletExportTo),
+                Method::WalletSendMany => schema_for!(response::WalletSendMany),
+                Method::WalletLocalMultisigSend => schema_for!(response::WalletLocalMultisigSend),
+                Method::WalletValidateAddressees => schema_for!(response::WalletValidateAddressees),
+                Method::WalletConsolidate => schema_for!(response::WalletConsolidate),
+                Method::WalletMaxSpendable => schema_for!(response::WalletMaxSpendable),
+                Method::SweepKey => schema_for!(response::SweepKey),
+                Method::WalletAddToPset => schema_for!(response::Pset),
+                Method::WalletSwapPropose => schema_for!(response::Pset),
+                Method::WalletSwapAccept => schema_for!(response::Pset),
+                Method::WalletIssue => schema_for!(response::WalletIssue),
                 Method::WalletReissue => schema_for!(response::Pset),
                 Method::WalletBurn => schema_for!(response::Pset),
                 Method::WalletCombine => schema_for!(response::WalletCombine),
+                Method::WalletMergeSignatures => schema_for!(response::WalletCombine),
                 Method::WalletBroadcast => schema_for!(response::WalletBroadcast),
+                Method::WalletBroadcastBatch => schema_for!(response::WalletBroadcastBatch),
+                Method::WalletRebroadcastPending => schema_for!(response::WalletRebroadcastPending),
                 Method::WalletPsetDetails => schema_for!(response::WalletPsetDetails),
+                Method::WalletCheckPsetSpendable => schema_for!(response::WalletCheckPsetSpendable),
+                Method::WalletMultisigFeeEstimate => {
+                    schema_for!(response::WalletMultisigFeeEstimate)
+                }
+                Method::WalletSigningRequest => schema_for!(response::WalletSigningRequest),
+                Method::WalletPrivacyAnalysis => schema_for!(response::WalletPrivacyAnalysis),
+                Method::WalletSessionCreate => schema_for!(response::WalletSession),
+                Method::WalletSessionAdd => schema_for!(response::WalletSession),
+                Method::WalletSessionGet => schema_for!(response::WalletSession),
                 Method::WalletMultisigDescriptor => schema_for!(response::WalletMultisigDescriptor),
+                Method::WalletCompilePolicy => schema_for!(response::WalletCompilePolicy),
+                Method::WalletDescriptorChecksum => schema_for!(response::WalletDescriptorChecksum),
+                Method::WalletCheckNetwork => schema_for!(response::WalletCheckNetwork),
+                Method::WalletConvertBlindingKey => schema_for!(response::WalletConvertBlindingKey),
+                Method::WalletConvertPsetVersion => schema_for!(response::WalletConvertPsetVersion),
+                Method::WalletStripSignatures => schema_for!(response::WalletStripSignatures),
+                Method::WalletPsetFee => schema_for!(response::WalletPsetFee),
+                Method::WalletCheckSpendPath => schema_for!(response::WalletCheckSpendPath),
                 Method::WalletSetTxMemo => schema_for!(response::Empty),
                 Method::WalletSetAddrMemo => schema_for!(response::Empty),
                 Method::SignerGenerate => schema_for!(response::SignerGenerate),
+                Method::SignerValidateMnemonic => schema_for!(response::SignerValidateMnemonic),
                 Method::SignerJadeId => schema_for!(response::JadeId),
                 Method::SignerLoadSoftware => schema_for!(response::Signer),
                 Method::SignerLoadJade => schema_for!(response::Signer),
                 Method::SignerLoadExternal => schema_for!(response::Signer),
+                Method::SignerLoadMany => schema_for!(response::SignerLoadMany),
                 Method::SignerUnload => schema_for!(response::SignerUnload),
+                Method::SignerReplace => schema_for!(response::SignerReplace),
+                Method::SignerMatchesWallet => schema_for!(response::SignerMatchesWallet),
                 Method::SignerList => schema_for!(response::SignerList),
                 Method::SignerDetails => schema_for!(response::SignerDetails),
                 Method::SignerXpub => schema_for!(response::SignerXpub),
                 Method::SignerSign => schema_for!(response::Pset),
+                Method::SignerCanSign => schema_for!(response::SignerCanSign),
+                Method::SignerCapabilities => schema_for!(response::SignerCapabilities),
                 Method::SignerSinglesigDescriptor => {
                     schema_for!(response::SignerSinglesigDescriptor)
                 }
+                Method::SignerSinglesigDescriptorSet => {
+                    schema_for!(response::SignerSinglesigDescriptorSet)
+                }
                 Method::SignerRegisterMultisig => schema_for!(response::Empty),
                 Method::AssetContract => schema_for!(response::AssetContract),
                 Method::AssetInsert => schema_for!(response::Empty),
                 Method::AssetRemove => schema_for!(request::Empty),
                 Method::AssetList => schema_for!(response::AssetList),
                 Method::AssetDetails => schema_for!(response::AssetDetails),
+                Method::AssetMetadata => schema_for!(response::AssetMetadata),
                 Method::AssetFromExplorer => schema_for!(request::Empty),
                 Method::AssetPublish => schema_for!(response::AssetPublish),
             },
@@ -158,6 +346,255 @@ impl Method {
     }
 }
 
+impl Method {
+    /// True if the method params contain secrets, like mnemonics or private keys, which must
+    /// not be logged
+    pub fn has_secret_params(&self) -> bool {
+        matches!(
+            self,
+            Method::SignerValidateMnemonic
+                | Method::SignerLoadSoftware
+                | Method::SignerLoadMany
+                | Method::SweepKey
+        )
+    }
+
+    /// True if the method doesn't change the server state and can't be used to move funds
+    pub fn is_readonly(&self) -> bool {
+        match self {
+            Method::Schema
+            | Method::Version
+            | Method::Health
+            | Method::SignerValidateMnemonic
+            | Method::PingBackend
+            | Method::GetDefaults
+            | Method::GetConfig
+            | Method::ListVariants
+            | Method::Scan
+            | Method::WalletList
+            | Method::WalletDetails
+            | Method::WalletDescriptor
+            | Method::WalletSyncProgress
+            | Method::WalletAddress
+            | Method::WalletBalance
+            | Method::WalletBalanceAll
+            | Method::WalletConfirmedBalance
+            | Method::WalletUtxos
+            | Method::WalletUsedAddresses
+            | Method::WalletDustReport
+            | Method::WalletDiscoverFunds
+            | Method::WalletConfirmations
+            | Method::WalletTxs
+            | Method::WalletDecodeDataOutputs
+            | Method::WalletIssuanceTx
+            | Method::WalletTx
+            | Method::WalletPsetDetails
+            | Method::WalletCheckPsetSpendable
+            | Method::WalletMultisigFeeEstimate
+            | Method::WalletSigningRequest
+            | Method::WalletPrivacyAnalysis
+            | Method::WalletSessionGet
+            | Method::WalletMultisigDescriptor
+            | Method::WalletCompilePolicy
+            | Method::WalletDescriptorChecksum
+            | Method::WalletCheckNetwork
+            | Method::WalletConvertBlindingKey
+            | Method::WalletConvertPsetVersion
+            | Method::WalletStripSignatures
+            | Method::WalletPsetFee
+            | Method::WalletCheckSpendPath
+            | Method::WalletValidateAddressees
+            | Method::SignerList
+            | Method::SignerDetails
+            | Method::SignerMatchesWallet
+            | Method::SignerXpub
+            | Method::SignerSinglesigDescriptor
+            | Method::SignerSinglesigDescriptorSet
+            | Method::SignerCanSign
+            | Method::SignerCapabilities
+            | Method::AssetContract
+            | Method::AssetList
+            | Method::AssetDetails
+            | Method::AssetMetadata => true,
+
+            Method::Stop
+            | Method::UnloadAll
+            | Method::SetDefaults
+            | Method::WalletLoad
+            | Method::WalletUnload
+            | Method::WalletCancelSync
+            | Method::WalletSetBackend
+            | Method::WalletSendMany
+            | Method::WalletLocalMultisigSend
+            | Method::WalletConsolidate
+            | Method::WalletMaxSpendable
+            | Method::SweepKey
+            | Method::WalletAddToPset
+            | Method::WalletSwapPropose
+            | Method::WalletSwapAccept
+            | Method::WalletIssue
+            | Method::WalletReissue
+            | Method::WalletBurn
+            | Method::WalletCombine
+            | Method::WalletMergeSignatures
+            | Method::WalletBroadcast
+            | Method::WalletBroadcastBatch
+            | Method::WalletRebroadcastPending
+            | Method::WalletSessionCreate
+            | Method::WalletSessionAdd
+            | Method::WalletSetTxMemo
+            | Method::WalletSetAddrMemo
+            | Method::WalletLockUtxo
+            | Method::WalletUnlockUtxo
+            | Method::WalletBlindingFactors
+            | Method::WalletExportWatchOnly
+            | Method::WalletExportTo
+            | Method::WalletConfirmAddress
+            | Method::VerifyDeviceAddress
+            | Method::SignerGenerate
+            | Method::SignerJadeId
+            | Method::SignerLoadSoftware
+            | Method::SignerLoadJade
+            | Method::SignerLoadExternal
+            | Method::SignerLoadMany
+            | Method::SignerUnload
+            | Method::SignerReplace
+            | Method::SignerSign
+            | Method::SignerRegisterMultisig
+            | Method::AssetInsert
+            | Method::AssetRemove
+            | Method::AssetFromExplorer
+            | Method::AssetPublish => false,
+        }
+    }
+
+    /// The wallets accessed by the method, used to restrict the tokens scoped to some wallets
+    pub fn wallet_access(&self) -> WalletAccess {
+        match self {
+            Method::Schema
+            | Method::Version
+            | Method::Health
+            | Method::PingBackend
+            | Method::SetDefaults
+            | Method::GetDefaults
+            | Method::GetConfig
+            | Method::ListVariants
+            | Method::Scan
+            | Method::Stop
+            | Method::WalletValidateAddressees
+            | Method::WalletMergeSignatures
+            | Method::WalletMultisigDescriptor
+            | Method::WalletCompilePolicy
+            | Method::WalletDescriptorChecksum
+            | Method::WalletCheckNetwork
+            | Method::WalletConvertBlindingKey
+            | Method::WalletConvertPsetVersion
+            | Method::WalletStripSignatures
+            | Method::WalletPsetFee
+            | Method::SignerGenerate
+            | Method::SignerValidateMnemonic
+            | Method::SignerJadeId
+            | Method::SignerLoadSoftware
+            | Method::SignerLoadJade
+            | Method::SignerLoadExternal
+            | Method::SignerLoadMany
+            | Method::SignerUnload
+            | Method::SignerReplace
+            | Method::SignerList
+            | Method::SignerDetails
+            | Method::SignerXpub
+            | Method::SignerSign
+            | Method::SignerCapabilities
+            | Method::SignerSinglesigDescriptor
+            | Method::SignerSinglesigDescriptorSet
+            | Method::AssetContract
+            | Method::AssetInsert
+            | Method::AssetRemove
+            | Method::AssetList
+            | Method::AssetDetails
+            | Method::AssetMetadata
+            | Method::AssetFromExplorer
+            | Method::AssetPublish => WalletAccess::None,
+
+            Method::UnloadAll
+            | Method::WalletList
+            | Method::WalletBalanceAll
+            | Method::WalletSessionAdd
+            | Method::WalletSessionGet => WalletAccess::Unnamed,
+
+            Method::WalletLoad
+            | Method::WalletUnload
+            | Method::WalletDetails
+            | Method::WalletDescriptor
+            | Method::WalletSyncProgress
+            | Method::WalletCancelSync
+            | Method::WalletSetBackend
+            | Method::WalletAddress
+            | Method::WalletConfirmAddress
+            | Method::WalletBalance
+            | Method::WalletConfirmedBalance
+            | Method::WalletUtxos
+            | Method::WalletUsedAddresses
+            | Method::WalletDustReport
+            | Method::WalletDiscoverFunds
+            | Method::WalletConfirmations
+            | Method::WalletLockUtxo
+            | Method::WalletUnlockUtxo
+            | Method::WalletTxs
+            | Method::WalletIssuanceTx
+            | Method::WalletTx
+            | Method::WalletDecodeDataOutputs
+            | Method::WalletBlindingFactors
+            | Method::WalletExportWatchOnly
+            | Method::WalletExportTo
+            | Method::WalletSendMany
+            | Method::WalletConsolidate
+            | Method::WalletMaxSpendable
+            | Method::SweepKey
+            | Method::WalletAddToPset
+            | Method::WalletSwapPropose
+            | Method::WalletSwapAccept
+            | Method::WalletIssue
+            | Method::WalletReissue
+            | Method::WalletBurn
+            | Method::WalletCombine
+            | Method::WalletBroadcast
+            | Method::WalletBroadcastBatch
+            | Method::WalletRebroadcastPending
+            | Method::WalletPsetDetails
+            | Method::WalletCheckPsetSpendable
+            | Method::WalletMultisigFeeEstimate
+            | Method::WalletSigningRequest
+            | Method::WalletPrivacyAnalysis
+            | Method::WalletSessionCreate
+            | Method::WalletCheckSpendPath
+            | Method::WalletSetTxMemo
+            | Method::WalletSetAddrMemo => WalletAccess::Params(&["/name"]),
+
+            Method::WalletLocalMultisigSend => WalletAccess::Params(&["/send/name"]),
+            Method::VerifyDeviceAddress | Method::SignerMatchesWallet => {
+                WalletAccess::Params(&["/wallet_name"])
+            }
+            Method::SignerCanSign | Method::SignerRegisterMultisig => {
+                WalletAccess::Params(&["/wallet"])
+            }
+        }
+    }
+}
+
+/// The wallets accessed by a method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletAccess {
+    /// The method doesn't access any wallet
+    None,
+
+    /// The method accesses the wallets named by the params at these JSON pointers
+    Params(&'static [&'static str]),
+
+    /// The method accesses wallets not named in the params, possibly all of them
+    Unnamed,
+}
+
 impl FromStr for Method {
     type Err = MethodNotExist;
 
@@ -165,44 +602,104 @@ impl FromStr for Method {
         Ok(match s {
             "schema" => Method::Schema,
             "version" => Method::Version,
+            "health" => Method::Health,
+            "ping_backend" => Method::PingBackend,
+            "set_defaults" => Method::SetDefaults,
+            "get_defaults" => Method::GetDefaults,
+            "get_config" => Method::GetConfig,
+            "list_variants" => Method::ListVariants,
             "scan" => Method::Scan,
             "stop" => Method::Stop,
+            "unload_all" => Method::UnloadAll,
             "wallet_load" => Method::WalletLoad,
             "wallet_unload" => Method::WalletUnload,
             "wallet_list" => Method::WalletList,
             "wallet_details" => Method::WalletDetails,
+            "wallet_descriptor" => Method::WalletDescriptor,
+            "wallet_sync_progress" => Method::WalletSyncProgress,
+            "wallet_cancel_sync" => Method::WalletCancelSync,
+            "wallet_set_backend" => Method::WalletSetBackend,
             "wallet_address" => Method::WalletAddress,
+            "wallet_confirm_address" => Method::WalletConfirmAddress,
+            "verify_device_address" => Method::VerifyDeviceAddress,
             "wallet_balance" => Method::WalletBalance,
+            "wallet_balance_all" => Method::WalletBalanceAll,
+            "wallet_confirmed_balance" => Method::WalletConfirmedBalance,
             "wallet_utxos" => Method::WalletUtxos,
+            "wallet_used_addresses" => Method::WalletUsedAddresses,
+            "wallet_dust_report" => Method::WalletDustReport,
+            "wallet_discover_funds" => Method::WalletDiscoverFunds,
+            "wallet_confirmations" => Method::WalletConfirmations,
+            "wallet_lock_utxo" => Method::WalletLockUtxo,
+            "wallet_unlock_utxo" => Method::WalletUnlockUtxo,
             "wallet_txs" => Method::WalletTxs,
+            "wallet_issuance_tx" => Method::WalletIssuanceTx,
             "wallet_tx" => Method::WalletTx,
+            "wallet_decode_data_outputs" => Method::WalletDecodeDataOutputs,
+            "wallet_blinding_factors" => Method::WalletBlindingFactors,
+            "wallet_export_watch_only" => Method::WalletExportWatchOnly,
+            "wallet_export_to" => Method::WalletExportTo,
             "wallet_send_many" => Method::WalletSendMany,
+            "wallet_local_multisig_send" => Method::WalletLocalMultisigSend,
+            "wallet_validate_addressees" => Method::WalletValidateAddressees,
+            "wallet_consolidate" => Method::WalletConsolidate,
+            "wallet_max_spendable" => Method::WalletMaxSpendable,
+            "sweep_key" => Method::SweepKey,
+            "wallet_add_to_pset" => Method::WalletAddToPset,
+            "wallet_swap_propose" => Method::WalletSwapPropose,
+            "wallet_swap_accept" => Method::WalletSwapAccept,
             "wallet_issue" => Method::WalletIssue,
             "wallet_reissue" => Method::WalletReissue,
             "wallet_burn" => Method::WalletBurn,
             "wallet_combine" => Method::WalletCombine,
+            "wallet_merge_signatures" => Method::WalletMergeSignatures,
             "wallet_broadcast" => Method::WalletBroadcast,
+            "wallet_broadcast_batch" => Method::WalletBroadcastBatch,
+            "wallet_rebroadcast_pending" => Method::WalletRebroadcastPending,
             "wallet_pset_details" => Method::WalletPsetDetails,
+            "wallet_check_pset_spendable" => Method::WalletCheckPsetSpendable,
+            "wallet_multisig_fee_estimate" => Method::WalletMultisigFeeEstimate,
+            "wallet_signing_request" => Method::WalletSigningRequest,
+            "wallet_privacy_analysis" => Method::WalletPrivacyAnalysis,
+            "wallet_session_create" => Method::WalletSessionCreate,
+            "wallet_session_add" => Method::WalletSessionAdd,
+            "wallet_session_get" => Method::WalletSessionGet,
             "wallet_multisig_descriptor" => Method::WalletMultisigDescriptor,
+            "wallet_compile_policy" => Method::WalletCompilePolicy,
+            "wallet_descriptor_checksum" => Method::WalletDescriptorChecksum,
+            "wallet_check_network" => Method::WalletCheckNetwork,
+            "wallet_convert_blinding_key" => Method::WalletConvertBlindingKey,
+            "wallet_convert_pset_version" => Method::WalletConvertPsetVersion,
+            "wallet_strip_signatures" => Method::WalletStripSignatures,
+            "wallet_pset_fee" => Method::WalletPsetFee,
+            "wallet_check_spend_path" => Method::WalletCheckSpendPath,
             "wallet_set_tx_memo" => Method::WalletSetTxMemo,
             "wallet_set_addr_memo" => Method::WalletSetAddrMemo,
             "signer_generate" => Method::SignerGenerate,
+            "signer_validate_mnemonic" => Method::SignerValidateMnemonic,
             "signer_jade_id" => Method::SignerJadeId,
             "signer_load_software" => Method::SignerLoadSoftware,
             "signer_load_jade" => Method::SignerLoadJade,
             "signer_load_external" => Method::SignerLoadExternal,
+            "signer_load_many" => Method::SignerLoadMany,
             "signer_unload" => Method::SignerUnload,
+            "signer_replace" => Method::SignerReplace,
+            "signer_matches_wallet" => Method::SignerMatchesWallet,
             "signer_list" => Method::SignerList,
             "signer_details" => Method::SignerDetails,
             "signer_xpub" => Method::SignerXpub,
             "signer_sign" => Method::SignerSign,
+            "signer_can_sign" => Method::SignerCanSign,
+            "signer_capabilities" => Method::SignerCapabilities,
             "signer_singlesig_descriptor" => Method::SignerSinglesigDescriptor,
+            "signer_singlesig_descriptor_set" => Method::SignerSinglesigDescriptorSet,
             "signer_register_multisig" => Method::SignerRegisterMultisig,
             "asset_contract" => Method::AssetContract,
             "asset_insert" => Method::AssetInsert,
             "asset_remove" => Method::AssetRemove,
             "asset_list" => Method::AssetList,
             "asset_details" => Method::AssetDetails,
+            "asset_metadata" => Method::AssetMetadata,
             "asset_from_explorer" => Method::AssetFromExplorer,
             "asset_publish" => Method::AssetPublish,
             _ => {
@@ -219,44 +716,104 @@ impl std::fmt::Display for Method {
         let s = match self {
             Method::Schema => "schema",
             Method::Version => "version",
+            Method::Health => "health",
+            Method::PingBackend => "ping_backend",
+            Method::SetDefaults => "set_defaults",
+            Method::GetDefaults => "get_defaults",
+            Method::GetConfig => "get_config",
+            Method::ListVariants => "list_variants",
             Method::Scan => "scan",
             Method::Stop => "stop",
+            Method::UnloadAll => "unload_all",
             Method::WalletLoad => "wallet_load",
             Method::WalletUnload => "wallet_unload",
             Method::WalletList => "wallet_list",
             Method::WalletDetails => "wallet_details",
+            Method::WalletDescriptor => "wallet_descriptor",
+            Method::WalletSyncProgress => "wallet_sync_progress",
+            Method::WalletCancelSync => "wallet_cancel_sync",
+            Method::WalletSetBackend => "wallet_set_backend",
             Method::WalletAddress => "wallet_address",
+            Method::WalletConfirmAddress => "wallet_confirm_address",
+            Method::VerifyDeviceAddress => "verify_device_address",
             Method::WalletBalance => "wallet_balance",
+            Method::WalletBalanceAll => "wallet_balance_all",
+            Method::WalletConfirmedBalance => "wallet_confirmed_balance",
             Method::WalletUtxos => "wallet_utxos",
+            Method::WalletUsedAddresses => "wallet_used_addresses",
+            Method::WalletDustReport => "wallet_dust_report",
+            Method::WalletDiscoverFunds => "wallet_discover_funds",
+            Method::WalletConfirmations => "wallet_confirmations",
+            Method::WalletLockUtxo => "wallet_lock_utxo",
+            Method::WalletUnlockUtxo => "wallet_unlock_utxo",
             Method::WalletTxs => "wallet_txs",
+            Method::WalletIssuanceTx => "wallet_issuance_tx",
             Method::WalletTx => "wallet_tx",
+            Method::WalletDecodeDataOutputs => "wallet_decode_data_outputs",
+            Method::WalletBlindingFactors => "wallet_blinding_factors",
+            Method::WalletExportWatchOnly => "wallet_export_watch_only",
+            Method::WalletExportTo => "wallet_export_to",
             Method::WalletSendMany => "wallet_send_many",
+            Method::WalletLocalMultisigSend => "wallet_local_multisig_send",
+            Method::WalletValidateAddressees => "wallet_validate_addressees",
+            Method::WalletConsolidate => "wallet_consolidate",
+            Method::WalletMaxSpendable => "wallet_max_spendable",
+            Method::SweepKey => "sweep_key",
+            Method::WalletAddToPset => "wallet_add_to_pset",
+            Method::WalletSwapPropose => "wallet_swap_propose",
+            Method::WalletSwapAccept => "wallet_swap_accept",
             Method::WalletIssue => "wallet_issue",
             Method::WalletReissue => "wallet_reissue",
             Method::WalletBurn => "wallet_burn",
             Method::WalletCombine => "wallet_combine",
+            Method::WalletMergeSignatures => "wallet_merge_signatures",
             Method::WalletBroadcast => "wallet_broadcast",
+            Method::WalletBroadcastBatch => "wallet_broadcast_batch",
+            Method::WalletRebroadcastPending => "wallet_rebroadcast_pending",
             Method::WalletPsetDetails => "wallet_pset_details",
+            Method::WalletCheckPsetSpendable => "wallet_check_pset_spendable",
+            Method::WalletMultisigFeeEstimate => "wallet_multisig_fee_estimate",
+            Method::WalletSigningRequest => "wallet_signing_request",
+            Method::WalletPrivacyAnalysis => "wallet_privacy_analysis",
+            Method::WalletSessionCreate => "wallet_session_create",
+            Method::WalletSessionAdd => "wallet_session_add",
+            Method::WalletSessionGet => "wallet_session_get",
             Method::WalletMultisigDescriptor => "wallet_multisig_descriptor",
+            Method::WalletCompilePolicy => "wallet_compile_policy",
+            Method::WalletDescriptorChecksum => "wallet_descriptor_checksum",
+            Method::WalletCheckNetwork => "wallet_check_network",
+            Method::WalletConvertBlindingKey => "wallet_convert_blinding_key",
+            Method::WalletConvertPsetVersion => "wallet_convert_pset_version",
+            Method::WalletStripSignatures => "wallet_strip_signatures",
+            Method::WalletPsetFee => "wallet_pset_fee",
+            Method::WalletCheckSpendPath => "wallet_check_spend_path",
             Method::WalletSetTxMemo => "wallet_set_tx_memo",
             Method::WalletSetAddrMemo => "wallet_set_addr_memo",
             Method::SignerGenerate => "signer_generate",
+            Method::SignerValidateMnemonic => "signer_validate_mnemonic",
             Method::SignerJadeId => "signer_jade_id",
             Method::SignerLoadSoftware => "signer_load_software",
             Method::SignerLoadJade => "signer_load_jade",
             Method::SignerLoadExternal => "signer_load_external",
+            Method::SignerLoadMany => "signer_load_many",
             Method::SignerUnload => "signer_unload",
+            Method::SignerReplace => "signer_replace",
+            Method::SignerMatchesWallet => "signer_matches_wallet",
             Method::SignerList => "signer_list",
             Method::SignerDetails => "signer_details",
             Method::SignerXpub => "signer_xpub",
             Method::SignerSign => "signer_sign",
+            Method::SignerCanSign => "signer_can_sign",
+            Method::SignerCapabilities => "signer_capabilities",
             Method::SignerSinglesigDescriptor => "signer_singlesig_descriptor",
+            Method::SignerSinglesigDescriptorSet => "signer_singlesig_descriptor_set",
             Method::SignerRegisterMultisig => "signer_register_multisig",
             Method::AssetContract => "asset_contract",
             Method::AssetInsert => "asset_insert",
             Method::AssetRemove => "asset_remove",
             Method::AssetList => "asset_list",
             Method::AssetDetails => "asset_details",
+            Method::AssetMetadata => "asset_metadata",
             Method::AssetFromExplorer => "asset_from_explorer",
             Method::AssetPublish => "asset_publish",
         };
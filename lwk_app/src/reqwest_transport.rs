@@ -8,11 +8,17 @@ pub struct ReqwestHttpTransport {
     url: String,
     /// timeout only supports second granularity.
     timeout: Duration,
+    /// Token sent as bearer in the authorization header.
+    auth_token: Option<String>,
 }
 
 impl ReqwestHttpTransport {
-    pub fn new(url: String, timeout: Duration) -> Self {
-        ReqwestHttpTransport { url, timeout }
+    pub fn new(url: String, timeout: Duration, auth_token: Option<String>) -> Self {
+        ReqwestHttpTransport {
+            url,
+            timeout,
+            auth_token,
+        }
     }
     fn request<R>(&self, req: impl serde::Serialize) -> Result<R, crate::Error>
     where
@@ -21,7 +27,11 @@ impl ReqwestHttpTransport {
         let client = reqwest::blocking::ClientBuilder::new()
             .timeout(self.timeout)
             .build()?;
-        let response = client.post(&self.url).json(&req).send()?;
+        let mut request = client.post(&self.url).json(&req);
+        if let Some(token) = self.auth_token.as_ref() {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send()?;
         Ok(response.json()?)
     }
 }
@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::net::SocketAddr;
+use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use lwk_common::Signer;
+use lwk_jade::protocol::VersionInfoResult;
 use lwk_jade::{Jade, Network};
 use lwk_rpc_model::request;
 use lwk_signer::AnySigner;
@@ -17,12 +21,17 @@ use lwk_wollet::bitcoin::XKeyIdentifier;
 use lwk_wollet::elements::encode::serialize;
 use lwk_wollet::elements::hex::ToHex;
 use lwk_wollet::elements::pset::elip100::AssetMetadata;
+use lwk_wollet::elements::pset::PartiallySignedTransaction;
 use lwk_wollet::elements::{Address, AssetId, OutPoint, Transaction, Txid};
 use lwk_wollet::Contract;
-use lwk_wollet::Wollet;
+use lwk_wollet::ScanProgress;
+use lwk_wollet::{BlockchainBackend, ElectrumClient, ElectrumUrl, ElementsNetwork};
+use lwk_wollet::{UtxoChanges, WalletTxOut, Wollet};
+use rand::rngs::StdRng;
 use serde::Serialize;
 
 use crate::config::Config;
+use crate::consts;
 use crate::method::Method;
 use crate::Error;
 
@@ -103,6 +112,41 @@ impl AppSigner {
         }
     }
 
+    /// Derivation standards of which the xpub can be asked to the signer
+    pub fn derivation_standards(&self) -> Vec<String> {
+        match &self.inner {
+            AppSignerInner::ExternalSigner(_) => vec![],
+            _ => ["bip49", "bip84", "bip87"].map(String::from).to_vec(),
+        }
+    }
+
+    /// For Jade signers, the version info if the device answers, `None` for other signers
+    pub fn jade_version_info(&self) -> Option<Option<VersionInfoResult>> {
+        match &self.inner {
+            AppSignerInner::AvailableSigner(AnySigner::Jade(j, _)) => Some(j.version_info().ok()),
+            AppSignerInner::JadeId(_, _) => Some(None),
+            _ => None,
+        }
+    }
+
+    /// Whether the signer can sign without connecting to it first
+    pub fn is_available(&self) -> bool {
+        matches!(self.inner, AppSignerInner::AvailableSigner(_))
+    }
+
+    /// Whether this is a placeholder for a signer known only by its fingerprint
+    pub fn is_external(&self) -> bool {
+        matches!(self.inner, AppSignerInner::ExternalSigner(_))
+    }
+
+    /// Whether signing needs the interaction with a hardware device
+    pub fn requires_device(&self) -> bool {
+        matches!(
+            self.inner,
+            AppSignerInner::JadeId(_, _) | AppSignerInner::AvailableSigner(AnySigner::Jade(_, _))
+        )
+    }
+
     pub fn type_(&self) -> String {
         match &self.inner {
             AppSignerInner::ExternalSigner(_) => "external".into(),
@@ -118,6 +162,14 @@ pub fn id_to_fingerprint(id: &XKeyIdentifier) -> Fingerprint {
     id[0..4].try_into().expect("4 is the fingerprint length")
 }
 
+/// Seconds since the unix epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock may have gone backwards")
+        .as_secs()
+}
+
 #[derive(Debug, Clone)]
 pub struct RegistryAssetData {
     asset_id: AssetId,
@@ -160,14 +212,22 @@ impl RegistryAssetData {
         &self.contract
     }
 
+    pub fn asset_id(&self) -> AssetId {
+        self.asset_id
+    }
+
+    pub fn token_id(&self) -> AssetId {
+        self.token_id
+    }
+
     pub fn issuance_prevout(&self) -> OutPoint {
         self.issuance_tx.input[self.issuance_vin as usize].previous_output
     }
 }
 
 pub enum AppAsset {
-    /// The policy asset (L-BTC)
-    PolicyAsset(AssetId),
+    /// The policy asset (L-BTC) of a network
+    PolicyAsset(AssetId, ElementsNetwork),
 
     /// An asset with contract committed to it
     RegistryAsset(RegistryAssetData),
@@ -177,9 +237,20 @@ pub enum AppAsset {
 }
 
 impl AppAsset {
+    /// The policy asset of the mainnet or of the testnet, whatever the server network is
+    pub fn known_policy_asset(asset: &AssetId) -> Option<AppAsset> {
+        [ElementsNetwork::Liquid, ElementsNetwork::LiquidTestnet]
+            .into_iter()
+            .find(|n| &n.policy_asset() == asset)
+            .map(|n| AppAsset::PolicyAsset(*asset, n))
+    }
+
     pub fn name(&self) -> String {
         match self {
-            AppAsset::PolicyAsset(_) => "liquid bitcoin".into(),
+            AppAsset::PolicyAsset(_, ElementsNetwork::LiquidTestnet) => {
+                "testnet liquid bitcoin".into()
+            }
+            AppAsset::PolicyAsset(..) => "liquid bitcoin".into(),
             AppAsset::RegistryAsset(d) => d.contract.name.clone(),
             AppAsset::ReissuanceToken(d) => {
                 format!("reissuance token for {}", d.contract.name)
@@ -189,7 +260,8 @@ impl AppAsset {
 
     pub fn ticker(&self) -> String {
         match self {
-            AppAsset::PolicyAsset(_) => "L-BTC".into(),
+            AppAsset::PolicyAsset(_, ElementsNetwork::LiquidTestnet) => "tL-BTC".into(),
+            AppAsset::PolicyAsset(..) => "L-BTC".into(),
             AppAsset::RegistryAsset(d) => d.contract.ticker.clone(),
             AppAsset::ReissuanceToken(d) => {
                 format!("reissuance token for {}", d.contract.ticker)
@@ -199,7 +271,7 @@ impl AppAsset {
 
     pub fn asset_metadata(&self) -> Option<AssetMetadata> {
         match self {
-            AppAsset::PolicyAsset(_) => None,
+            AppAsset::PolicyAsset(..) => None,
             AppAsset::RegistryAsset(d) => {
                 Some(AssetMetadata::new(d.contract_str(), d.issuance_prevout()))
             }
@@ -211,7 +283,7 @@ impl AppAsset {
 
     pub fn asset_id(&self) -> AssetId {
         match self {
-            AppAsset::PolicyAsset(asset) => *asset,
+            AppAsset::PolicyAsset(asset, _) => *asset,
             AppAsset::RegistryAsset(d) => d.asset_id,
             AppAsset::ReissuanceToken(d) => d.token_id,
         }
@@ -237,6 +309,7 @@ impl AppAsset {
                     id: None,
                     method: Method::AssetInsert.to_string(),
                     params: Some(serde_json::to_value(params).expect("derived")),
+                    auth_token: None,
                 })
             }
             _ => None,
@@ -259,6 +332,254 @@ pub struct TxMemos(HashMap<String, HashMap<Txid, String>>);
 #[derive(Default)]
 pub struct AddrMemos(HashMap<String, HashMap<Address, String>>);
 
+/// Outputs reserved by transactions waiting to be signed, with the unix time in seconds when
+/// the reservation expires. Coin selection skips them.
+#[derive(Default)]
+pub struct UtxoLocks(HashMap<String, HashMap<OutPoint, u64>>);
+
+/// Values used by the methods creating transactions when the caller omits them
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Defaults {
+    pub fee_rate: Option<f32>,
+
+    /// Blocks within which transactions should confirm, used to estimate the fee rate
+    pub confirmation_target: Option<u32>,
+}
+
+/// PSET signing sessions, see [`Config::session_ttl`]
+#[derive(Default)]
+pub struct Sessions(HashMap<String, Session>);
+
+pub struct Session {
+    /// The wallet spending in the PSET
+    pub wallet: String,
+
+    /// The PSET with the signatures collected so far
+    pub pset: PartiallySignedTransaction,
+
+    /// When the session has been created
+    pub created_at: Instant,
+}
+
+/// Contracts fetched from the registry, see [`Config::registry_cache_ttl`]
+#[derive(Default)]
+pub struct RegistryCache(HashMap<AssetId, CachedContract>);
+
+#[derive(Clone)]
+pub struct CachedContract {
+    pub contract: Contract,
+
+    /// When the contract has been fetched from the registry
+    pub fetched_at: Instant,
+
+    /// A background refresh of this entry is in progress
+    pub refreshing: bool,
+}
+
+/// The UTXOs and the balance of a wallet, kept up to date with the changes made by the updates,
+/// see [`State::wallet_balance`]
+pub struct BalanceCache {
+    policy_asset: AssetId,
+    utxos: HashMap<OutPoint, (AssetId, u64)>,
+    balance: HashMap<AssetId, u64>,
+}
+
+/// The wallet currently scanned, the flag to stop its scan and its progress
+///
+/// It's kept outside [`State`] because the scan loop holds the state lock while scanning
+#[derive(Debug, Default)]
+pub struct ScanCancel {
+    scanning: Mutex<Option<String>>,
+    cancel: AtomicBool,
+    progress: ScanProgress,
+
+    /// The blockchain tip height when the scan started, 0 if unknown
+    tip_height: AtomicU32,
+
+    /// Skip the wallets not yet scanned in the current scan loop
+    cancel_loop: AtomicBool,
+}
+
+impl ScanCancel {
+    /// Mark the given wallet, synced up to `scanned_height`, as the one being scanned, returning
+    /// the flag to check and the progress to update
+    pub fn start(
+        &self,
+        name: &str,
+        scanned_height: u32,
+        tip_height: Option<u32>,
+    ) -> Result<(&AtomicBool, &ScanProgress), Error> {
+        let mut scanning = self.scanning.lock()?;
+        *scanning = Some(name.to_string());
+        self.cancel.store(false, Ordering::Relaxed);
+        self.progress.start(scanned_height);
+        self.tip_height
+            .store(tip_height.unwrap_or(0), Ordering::Relaxed);
+        Ok((&self.cancel, &self.progress))
+    }
+
+    /// The scanned height and the tip height of the given wallet, if it's being scanned
+    pub fn progress(&self, name: &str) -> Result<Option<(u32, Option<u32>)>, Error> {
+        let scanning = self.scanning.lock()?;
+        if scanning.as_deref() != Some(name) {
+            return Ok(None);
+        }
+        let tip_height = self.tip_height.load(Ordering::Relaxed);
+        Ok(Some((
+            self.progress.scanned_height(),
+            (tip_height > 0).then_some(tip_height),
+        )))
+    }
+
+    pub fn finish(&self) -> Result<(), Error> {
+        *self.scanning.lock()? = None;
+        Ok(())
+    }
+
+    /// Signal the scan of the given wallet to stop, returns false if it's not being scanned
+    pub fn cancel(&self, name: &str) -> Result<bool, Error> {
+        let scanning = self.scanning.lock()?;
+        let is_scanning = scanning.as_deref() == Some(name);
+        if is_scanning {
+            self.cancel.store(true, Ordering::Relaxed);
+        }
+        Ok(is_scanning)
+    }
+
+    /// Signal the current scan loop to stop, returns the wallet whose scan has been interrupted
+    pub fn cancel_all(&self) -> Result<Option<String>, Error> {
+        let scanning = self.scanning.lock()?;
+        self.cancel_loop.store(true, Ordering::Relaxed);
+        self.cancel.store(true, Ordering::Relaxed);
+        Ok(scanning.clone())
+    }
+
+    /// Must be called at the start of a scan loop, clearing a previous [`Self::cancel_all`]
+    pub fn start_loop(&self) {
+        self.cancel_loop.store(false, Ordering::Relaxed);
+    }
+
+    /// True if the remaining wallets of the current scan loop should be skipped
+    pub fn loop_cancelled(&self) -> bool {
+        self.cancel_loop.load(Ordering::Relaxed)
+    }
+}
+
+/// Requests counted in the current one second window, for each auth token and method
+///
+/// It's kept outside [`State`] so that counting a request doesn't need the state lock. The check
+/// runs after the permission one, which locks the state, so that requests with unknown auth
+/// tokens don't open windows; requests over the limit are then rejected before the method does
+/// any work.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    windows: Mutex<HashMap<(Option<String>, Method), Window>>,
+}
+
+/// The start of a rate limit window and the requests counted since then
+type Window = (Instant, u32);
+
+impl RateLimiter {
+    /// Count a request of `method` authenticated with `auth_token`, failing if it's more than
+    /// `limit` in the last second
+    pub fn check(
+        &self,
+        auth_token: Option<&str>,
+        method: &Method,
+        limit: u32,
+        now: Instant,
+    ) -> Result<(), Error> {
+        let mut windows = self.windows.lock()?;
+        let key = (auth_token.map(String::from), method.clone());
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.duration_since(*start) >= Duration::from_secs(1) {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= limit {
+            return Err(Error::RateLimited(method.to_string(), limit));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Bounds the number of connections open at the same time to the backends, by every wallet,
+/// background scans and requests, so that public servers don't ban the server for opening too
+/// many
+#[derive(Debug)]
+pub struct BackendPermits {
+    available: Mutex<usize>,
+    released: Condvar,
+}
+
+impl BackendPermits {
+    pub fn new(max: usize) -> Arc<Self> {
+        Arc::new(Self {
+            available: Mutex::new(max),
+            released: Condvar::new(),
+        })
+    }
+
+    /// Wait up to [`consts::BACKEND_PERMIT_TIMEOUT`] for a free connection, then open it with
+    /// `connect`
+    ///
+    /// The connection counts until the returned client is dropped.
+    pub fn connect(
+        self: &Arc<Self>,
+        connect: impl FnOnce() -> Result<ElectrumClient, Error>,
+    ) -> Result<BackendClient, Error> {
+        let available = self.available.lock()?;
+        let (mut available, wait) = self
+            .released
+            .wait_timeout_while(available, consts::BACKEND_PERMIT_TIMEOUT, |a| *a == 0)
+            .map_err(|e| Error::PoisonError(e.to_string()))?;
+        if wait.timed_out() {
+            return Err(Error::BackendBusy(consts::BACKEND_PERMIT_TIMEOUT.as_secs()));
+        }
+        *available -= 1;
+        drop(available);
+        let permit = BackendPermit(self.clone());
+        Ok(BackendClient {
+            client: connect()?,
+            _permit: permit,
+        })
+    }
+}
+
+/// A connection counted by [`BackendPermits`], released on drop
+#[derive(Debug)]
+struct BackendPermit(Arc<BackendPermits>);
+
+impl Drop for BackendPermit {
+    fn drop(&mut self) {
+        if let Ok(mut available) = self.0.available.lock() {
+            *available += 1;
+            self.0.released.notify_one();
+        }
+    }
+}
+
+/// An Electrum client holding one of the [`BackendPermits`]
+pub struct BackendClient {
+    client: ElectrumClient,
+    _permit: BackendPermit,
+}
+
+impl Deref for BackendClient {
+    type Target = ElectrumClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl DerefMut for BackendClient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
 pub struct State {
     // TODO: config is read-only, so it's not useful to wrap it in a mutex.
     // Ideally it should be in _another_ struct accessible by method_handler.
@@ -268,8 +589,12 @@ pub struct State {
     pub assets: Assets,
     pub tx_memos: TxMemos,
     pub addr_memos: AddrMemos,
+    pub utxo_locks: UtxoLocks,
     pub do_persist: bool,
 
+    /// The datadir is not writable, the state and the wallets data are kept in memory only
+    pub in_memory: bool,
+
     /// Number of scan loops started
     pub scan_loops_started: u32,
 
@@ -278,6 +603,41 @@ pub struct State {
 
     /// Signal the scanning thread that we don't want to wait anymore
     pub interrupt_wait: bool,
+
+    /// True if the backend was reachable in the last scan loop
+    pub backend_reachable: bool,
+
+    /// Wallets whose last scan succeeded
+    pub synced_wallets: HashSet<String>,
+
+    /// Height of the blockchain tip seen in the last successful scan loop or tip request
+    pub tip_height: Option<u32>,
+
+    /// When `tip_height` has been fetched
+    pub tip_fetched_at: Option<Instant>,
+
+    /// Electrum servers to use for specific wallets instead of the configured one
+    ///
+    /// Not persisted: replaying the request at startup would need the server to be reachable,
+    /// and a failed replay prevents the app from starting.
+    pub wallet_backends: HashMap<String, ElectrumUrl>,
+
+    /// Fee rates used by specific wallets when the request doesn't have one
+    pub wallet_fee_rates: HashMap<String, f32>,
+
+    /// The connections to the backends that can still be opened
+    pub backend_permits: Arc<BackendPermits>,
+
+    pub defaults: Defaults,
+
+    /// Wallet balances, see [`State::wallet_balance`]
+    pub balance_cache: HashMap<String, BalanceCache>,
+
+    pub registry_cache: RegistryCache,
+    pub sessions: Sessions,
+
+    /// Random generator seeded with [`Config::insecure_signer_seed`], used to generate signers
+    pub signer_rng: Option<StdRng>,
 }
 
 impl Wollets {
@@ -328,6 +688,10 @@ impl Wollets {
         self.0.iter()
     }
 
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut Wollet)> {
         self.0.iter_mut()
     }
@@ -403,20 +767,47 @@ impl Signers {
         }
     }
 
-    pub fn insert(&mut self, name: &str, signer: AppSigner) -> Result<(), Error> {
-        if self.0.contains_key(name) {
-            return Err(Error::SignerAlreadyLoaded(name.to_string()));
-        }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Insert a signer, returning whether it replaced one with the same name.
+    ///
+    /// A signer can replace another with the same name only if `force` is given, so that the
+    /// key material behind a name doesn't change by mistake.
+    pub fn insert(&mut self, name: &str, signer: AppSigner, force: bool) -> Result<bool, Error> {
+        self.check_insert(name, &signer, force)?;
+        Ok(self.0.insert(name.to_string(), signer).is_some())
+    }
+
+    /// Check the signer can be inserted with [`Signers::insert`], without inserting it
+    pub fn check_insert(&self, name: &str, signer: &AppSigner, force: bool) -> Result<(), Error> {
         let inserting_fingerprint = signer.fingerprint()?;
+        if let Some(existing) = self.0.get(name) {
+            if !force {
+                return Err(Error::SignerNameInUse {
+                    name: name.to_string(),
+                    fingerprint: existing.fingerprint()?.to_string(),
+                });
+            }
+        }
 
         // TODO: matchin for fingerprint is not ideal, we could have collisions
-        let vec: Vec<_> = self.names_matching_fingerprint(&inserting_fingerprint)?;
+        // An external signer can coexist with the available signer having its fingerprint,
+        // until the latter replaces it with `replace_external`
+        let vec: Vec<_> = self
+            .names_matching_fingerprint(&inserting_fingerprint)?
+            .into_iter()
+            .filter(|n| n != name && self.0[n].is_external() == signer.is_external())
+            .collect();
         if let Some(existing) = vec.first() {
             // TODO: maybe a different error more clear?
             return Err(Error::SignerAlreadyLoaded(existing.to_string()));
         }
-
-        self.0.insert(name.to_string(), signer);
         Ok(())
     }
 
@@ -426,6 +817,35 @@ impl Signers {
             .ok_or_else(|| Error::SignerNotExist(name.to_string()))
     }
 
+    /// Remove the external signers with the same fingerprint of the signer identified by name,
+    /// so that the latter is used for the wallets referencing that fingerprint.
+    ///
+    /// Returns the names of the removed external signers.
+    pub fn replace_external(&mut self, name: &str) -> Result<Vec<String>, Error> {
+        let signer = self.get(name)?;
+        if signer.is_external() {
+            return Err(Error::Generic(
+                "Invalid operation for external signer".to_string(),
+            ));
+        }
+        let fingerprint = signer.fingerprint()?;
+        let mut replaced: Vec<_> = self
+            .names_matching_fingerprint(&fingerprint)?
+            .into_iter()
+            .filter(|n| self.0[n].is_external())
+            .collect();
+        if replaced.is_empty() {
+            return Err(Error::Generic(format!(
+                "No external signer with fingerprint {fingerprint}"
+            )));
+        }
+        replaced.sort();
+        for n in replaced.iter() {
+            self.0.remove(n);
+        }
+        Ok(replaced)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&String, &AppSigner)> {
         self.0.iter()
     }
@@ -472,6 +892,109 @@ impl Assets {
     }
 }
 
+impl Sessions {
+    /// Insert a new session returning its identifier
+    pub fn insert(&mut self, wallet: &str, pset: PartiallySignedTransaction) -> String {
+        let session_id = rand::random::<[u8; 16]>().to_hex();
+        let session = Session {
+            wallet: wallet.to_string(),
+            pset,
+            created_at: Instant::now(),
+        };
+        self.0.insert(session_id.clone(), session);
+        session_id
+    }
+
+    /// Get the session with the given id, sessions older than `ttl` are removed
+    pub fn get_mut(&mut self, session_id: &str, ttl: Duration) -> Result<&mut Session, Error> {
+        self.0.retain(|_, s| s.created_at.elapsed() < ttl);
+        self.0
+            .get_mut(session_id)
+            .ok_or_else(|| Error::SessionNotExist(session_id.to_string()))
+    }
+
+    /// Remove all the sessions of the given wallet
+    pub fn remove_wallet(&mut self, wallet: &str) {
+        self.0.retain(|_, s| s.wallet != wallet);
+    }
+}
+
+impl BalanceCache {
+    pub fn new(wollet: &Wollet) -> Result<Self, Error> {
+        let mut cache = BalanceCache {
+            policy_asset: wollet.policy_asset(),
+            utxos: HashMap::new(),
+            balance: HashMap::new(),
+        };
+        cache.balance.insert(cache.policy_asset, 0);
+        cache.add(wollet.utxos()?);
+        Ok(cache)
+    }
+
+    fn add(&mut self, utxos: Vec<WalletTxOut>) {
+        for utxo in utxos {
+            let (asset, value) = (utxo.unblinded.asset, utxo.unblinded.value);
+            if self.utxos.insert(utxo.outpoint, (asset, value)).is_none() {
+                *self.balance.entry(asset).or_default() += value;
+            }
+        }
+    }
+
+    fn remove(&mut self, outpoints: Vec<OutPoint>) {
+        for outpoint in outpoints {
+            if let Some((asset, value)) = self.utxos.remove(&outpoint) {
+                let amount = self.balance.entry(asset).or_default();
+                *amount -= value;
+                if *amount == 0 && asset != self.policy_asset {
+                    self.balance.remove(&asset);
+                }
+            }
+        }
+    }
+
+    /// Apply the changes, added outputs first since they may be spent by the same update
+    pub fn apply(&mut self, changes: UtxoChanges) {
+        self.add(changes.added);
+        self.remove(changes.spent);
+    }
+
+    pub fn balance(&self) -> &HashMap<AssetId, u64> {
+        &self.balance
+    }
+}
+
+impl RegistryCache {
+    pub fn get(&self, asset: &AssetId) -> Option<&CachedContract> {
+        self.0.get(asset)
+    }
+
+    pub fn insert(&mut self, asset: AssetId, contract: Contract) {
+        let entry = CachedContract {
+            contract,
+            fetched_at: Instant::now(),
+            refreshing: false,
+        };
+        self.0.insert(asset, entry);
+    }
+
+    /// Mark the entry as being refreshed, returns false if a refresh was already in progress
+    pub fn start_refresh(&mut self, asset: &AssetId) -> bool {
+        match self.0.get_mut(asset) {
+            Some(entry) if !entry.refreshing => {
+                entry.refreshing = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn end_refresh(&mut self, asset: &AssetId) {
+        if let Some(entry) = self.0.get_mut(asset) {
+            entry.refreshing = false;
+        }
+    }
+}
+
 impl TxMemos {
     // TODO; return Option<&HashMap<Txid, String>>
     pub fn for_wollet(&self, wollet: &str) -> HashMap<Txid, String> {
@@ -498,6 +1021,42 @@ impl TxMemos {
     }
 }
 
+impl UtxoLocks {
+    pub fn lock(&mut self, wollet: &str, outpoint: OutPoint, expires_at: u64) {
+        self.0
+            .entry(wollet.to_string())
+            .or_default()
+            .insert(outpoint, expires_at);
+    }
+
+    /// Returns false if the output was not locked
+    pub fn unlock(&mut self, wollet: &str, outpoint: &OutPoint) -> bool {
+        self.0
+            .get_mut(wollet)
+            .and_then(|locks| locks.remove(outpoint))
+            .is_some()
+    }
+
+    /// The outputs of the wallet locked at the unix time `now`, expired locks are removed
+    pub fn locked(&mut self, wollet: &str, now: u64) -> Vec<OutPoint> {
+        self.0
+            .get_mut(wollet)
+            .map(|locks| {
+                locks.retain(|_, expires_at| *expires_at > now);
+                locks.keys().cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn remove(&mut self, wollet: &str) {
+        self.0.remove(wollet);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &HashMap<OutPoint, u64>)> {
+        self.0.iter()
+    }
+}
+
 impl AddrMemos {
     // TODO; return Option<&HashMap<Address, String>>
     pub fn for_wollet(&self, wollet: &str) -> HashMap<Address, String> {
@@ -525,11 +1084,127 @@ impl AddrMemos {
 }
 
 impl State {
+    /// Remove the wallet and everything related to it
+    pub fn unload_wallet(&mut self, name: &str) -> Result<Wollet, Error> {
+        let removed = self.wollets.remove(name)?;
+        self.tx_memos.remove(name);
+        self.addr_memos.remove(name);
+        self.utxo_locks.remove(name);
+        self.sessions.remove_wallet(name);
+        self.synced_wallets.remove(name);
+        self.wallet_backends.remove(name);
+        self.wallet_fee_rates.remove(name);
+        self.balance_cache.remove(name);
+        Ok(removed)
+    }
+
+    /// The balance of the given wallet
+    ///
+    /// The balance is computed from all the wallet UTXOs only the first time, after a reorg and
+    /// when the wallet is reloaded, otherwise the cached one is updated with the outputs added
+    /// and spent by the updates applied since the previous call.
+    pub fn wallet_balance(&mut self, name: &str) -> Result<HashMap<AssetId, u64>, Error> {
+        let wollet = self.wollets.get_mut(name)?;
+        let changes = wollet.take_utxo_changes();
+        match self.balance_cache.get_mut(name) {
+            Some(cache) if !changes.reset => {
+                tracing::debug!(wallet = name, cache_hit = true, "wallet balance");
+                cache.apply(changes);
+                Ok(cache.balance().clone())
+            }
+            _ => {
+                tracing::debug!(wallet = name, cache_hit = false, "wallet balance");
+                let cache = BalanceCache::new(wollet)?;
+                let balance = cache.balance().clone();
+                self.balance_cache.insert(name.to_string(), cache);
+                Ok(balance)
+            }
+        }
+    }
+
+    /// The Electrum client to use for the given wallet
+    pub fn electrum_client(&self, wallet: &str) -> Result<BackendClient, Error> {
+        self.backend_permits
+            .connect(|| match self.wallet_backends.get(wallet) {
+                Some(url) => Ok(ElectrumClient::new(url)?),
+                None => self.config.electrum_client(),
+            })
+    }
+
+    /// The height of the blockchain tip, fetched from the backend of the given wallet only if
+    /// the known one is older than [`consts::TIP_CACHE_TTL`]
+    pub fn tip_height(&mut self, wallet: &str) -> Result<u32, Error> {
+        let fresh = self
+            .tip_fetched_at
+            .is_some_and(|t| t.elapsed() < consts::TIP_CACHE_TTL);
+        match self.tip_height {
+            Some(height) if fresh => Ok(height),
+            _ => {
+                let height = self.electrum_client(wallet)?.tip()?.height;
+                self.set_tip_height(height);
+                Ok(height)
+            }
+        }
+    }
+
+    pub fn set_tip_height(&mut self, height: u32) {
+        self.tip_height = Some(height);
+        self.tip_fetched_at = Some(Instant::now());
+    }
+
+    /// The outputs of the given wallet that must not be spent
+    pub fn locked_utxos(&mut self, wallet: &str) -> Vec<OutPoint> {
+        self.utxo_locks.locked(wallet, unix_now())
+    }
+
+    /// The fee rate for a transaction of the given wallet, the wallet default fee rate or the
+    /// configured defaults are used if `fee_rate` is `None`
+    ///
+    /// With a default confirmation target the fee rate is estimated by the wallet Electrum
+    /// server, if it can't estimate `None` is returned and the builder default is used.
+    pub fn fee_rate(&self, wallet: &str, fee_rate: Option<f32>) -> Result<Option<f32>, Error> {
+        if fee_rate.is_some() {
+            return Ok(fee_rate);
+        }
+        if let Some(fee_rate) = self.wallet_fee_rates.get(wallet) {
+            return Ok(Some(*fee_rate));
+        }
+        if let Some(fee_rate) = self.defaults.fee_rate {
+            return Ok(Some(fee_rate));
+        }
+        let Some(target) = self.defaults.confirmation_target else {
+            return Ok(None);
+        };
+        let btc_per_kvb = self
+            .electrum_client(wallet)?
+            .estimate_fee(target as usize)?;
+        Ok((btc_per_kvb > 0.0).then_some((btc_per_kvb * 100_000_000.0) as f32))
+    }
+
+    /// Fail if loading another wallet would exceed the configured maximum
+    pub fn check_wallets_limit(&self) -> Result<(), Error> {
+        let loaded = self.wollets.len();
+        if loaded >= self.config.max_wallets {
+            return Err(Error::TooManyWallets(loaded));
+        }
+        Ok(())
+    }
+
+    /// Fail if loading another signer would exceed the configured maximum
+    pub fn check_signers_limit(&self) -> Result<(), Error> {
+        let loaded = self.signers.len();
+        if loaded >= self.config.max_signers {
+            return Err(Error::TooManySigners(loaded));
+        }
+        Ok(())
+    }
+
     pub fn insert_policy_asset(&mut self) {
-        let asset_id = self.config.network.policy_asset();
+        let network = self.config.network;
+        let asset_id = network.policy_asset();
         self.assets
             .0
-            .insert(asset_id, AppAsset::PolicyAsset(asset_id));
+            .insert(asset_id, AppAsset::PolicyAsset(asset_id, network));
     }
 
     pub fn get_asset(&self, asset: &AssetId) -> Result<&AppAsset, Error> {
@@ -563,7 +1238,7 @@ impl State {
         Ok(())
     }
 
-    fn get_asset_from_str(&self, asset: &str) -> Result<&AppAsset, Error> {
+    pub fn get_asset_from_str(&self, asset: &str) -> Result<&AppAsset, Error> {
         let asset = AssetId::from_str(asset).map_err(|e| Error::Generic(e.to_string()))?;
         self.get_asset(&asset)
     }
@@ -586,7 +1261,7 @@ impl State {
     }
 
     pub fn persist<T: Serialize>(&mut self, data: T) -> Result<(), Error> {
-        if self.do_persist {
+        if self.do_persist && !self.in_memory {
             let data = serde_json::to_string(&data)?;
             let path = self.config.state_path()?;
             let mut file = OpenOptions::new()
@@ -600,6 +1275,9 @@ impl State {
     }
 
     pub fn persist_all(&mut self) -> Result<(), Error> {
+        if self.in_memory {
+            return Ok(());
+        }
         let path = self.config.state_path()?;
         let mut temp = path.clone();
         let millis = SystemTime::now()
@@ -626,12 +1304,14 @@ impl State {
             let params = request::WalletLoad {
                 descriptor: w.descriptor().to_string(),
                 name: n.to_string(),
+                default_fee_rate: self.wallet_fee_rates.get(n).copied(),
             };
             let r = Request {
                 jsonrpc: "2.0".into(),
                 id: None,
                 method: Method::WalletLoad.to_string(),
                 params: Some(serde_json::to_value(params)?),
+                auth_token: None,
             };
             requests.push(r);
         }
@@ -649,6 +1329,7 @@ impl State {
                     id: None,
                     method: Method::WalletSetTxMemo.to_string(),
                     params: Some(serde_json::to_value(params)?),
+                    auth_token: None,
                 };
                 requests.push(r);
             }
@@ -667,6 +1348,26 @@ impl State {
                     id: None,
                     method: Method::WalletSetAddrMemo.to_string(),
                     params: Some(serde_json::to_value(params)?),
+                    auth_token: None,
+                };
+                requests.push(r);
+            }
+        }
+
+        // Utxo locks, the expired ones are skipped when loading
+        for (name, locks) in self.utxo_locks.iter() {
+            for (outpoint, expires_at) in locks.iter() {
+                let params = request::WalletLockUtxo {
+                    name: name.to_string(),
+                    outpoint: outpoint.to_string(),
+                    expires_at: Some(*expires_at),
+                };
+                let r = Request {
+                    jsonrpc: "2.0".into(),
+                    id: None,
+                    method: Method::WalletLockUtxo.to_string(),
+                    params: Some(serde_json::to_value(params)?),
+                    auth_token: None,
                 };
                 requests.push(r);
             }
@@ -680,6 +1381,7 @@ impl State {
                         name: n.to_string(),
                         id: id.to_string(),
                         emulator: None, // ?
+                        force: false,
                     };
                     (serde_json::to_value(params)?, Method::SignerLoadJade)
                 }
@@ -692,6 +1394,7 @@ impl State {
                                 .expect("we only create signers from mnemonic")
                                 .to_string(),
                             persist: s.persist,
+                            force: false,
                         };
                         (serde_json::to_value(params)?, Method::SignerLoadSoftware)
                     }
@@ -700,6 +1403,7 @@ impl State {
                             name: n.to_string(),
                             id: id.to_string(),
                             emulator: None, // ?
+                            force: false,
                         };
                         (serde_json::to_value(params)?, Method::SignerLoadJade)
                     }
@@ -708,6 +1412,7 @@ impl State {
                     let params = request::SignerLoadExternal {
                         name: n.to_string(),
                         fingerprint: f.to_string(),
+                        force: false,
                     };
                     (serde_json::to_value(params)?, Method::SignerLoadExternal)
                 }
@@ -718,6 +1423,23 @@ impl State {
                 id: None,
                 method: method.to_string(),
                 params: Some(params),
+                auth_token: None,
+            };
+            requests.push(r);
+        }
+
+        // Defaults
+        if self.defaults.fee_rate.is_some() || self.defaults.confirmation_target.is_some() {
+            let params = request::SetDefaults {
+                fee_rate: self.defaults.fee_rate,
+                confirmation_target: self.defaults.confirmation_target,
+            };
+            let r = Request {
+                jsonrpc: "2.0".into(),
+                id: None,
+                method: Method::SetDefaults.to_string(),
+                params: Some(serde_json::to_value(params)?),
+                auth_token: None,
             };
             requests.push(r);
         }
@@ -23,6 +23,10 @@ pub struct Cli {
     #[arg(long, env)]
     pub addr: Option<SocketAddr>,
 
+    /// Token to authenticate to the server, needed if the server was started with `--allow-token`
+    #[arg(long, env)]
+    pub auth_token: Option<String>,
+
     /// The sub command
     #[command(subcommand)]
     pub command: CliCommand,
@@ -137,7 +141,14 @@ pub struct ServerSubCommands {
 #[derive(Debug, Subcommand, ValueEnum, Clone)]
 pub enum ServerSubCommandsEnum {
     // Start is a special command
+    Health,
+    PingBackend,
+    SetDefaults,
+    GetDefaults,
+    GetConfig,
+    ListVariants,
     Scan,
+    UnloadAll,
     Stop,
 }
 
@@ -153,17 +164,62 @@ pub enum WalletSubCommandsEnum {
     Unload,
     List,
     Address,
+    ConfirmAddress,
+    VerifyDeviceAddress,
     Balance,
+    BalanceAll,
+    ConfirmedBalance,
     Send,
+    LocalMultisigSend,
+    Consolidate,
+    MaxSpendable,
+    SweepKey,
+    ValidateAddressees,
+    AddToPset,
+    SwapPropose,
+    SwapAccept,
     Issue,
     Reissue,
     MultisigDesc,
+    CompilePolicy,
+    DescriptorChecksum,
+    CheckNetwork,
+    ConvertBlindingKey,
+    ConvertPsetVersion,
+    StripSignatures,
+    PsetFee,
+    CheckSpendPath,
     Broadcast,
+    BroadcastBatch,
+    RebroadcastPending,
     Details,
+    Descriptor,
+    SyncProgress,
+    CancelSync,
+    SetBackend,
     Combine,
+    MergeSignatures,
     PsetDetails,
+    SigningRequest,
+    PrivacyAnalysis,
+    CheckPsetSpendable,
+    MultisigFeeEstimate,
+    SessionCreate,
+    SessionAdd,
+    SessionGet,
     Utxos,
+    UsedAddresses,
+    DustReport,
+    DiscoverFunds,
+    Confirmations,
+    LockUtxo,
+    UnlockUtxo,
     Txs,
+    IssuanceTx,
+    DecodeDataOutputs,
+    BlindingFactors,
+    ExportWatchOnly,
+    ExportTo,
     SetTxMemo,
     SetAddrMemo,
 }
@@ -177,15 +233,22 @@ pub struct SignerSubCommands {
 #[derive(Debug, Subcommand, ValueEnum, Clone)]
 pub enum SignerSubCommandsEnum {
     Generate,
+    ValidateMnemonic,
     JadeId,
     LoadSoftware,
     LoadJade,
     LoadExternal,
+    LoadMany,
     Unload,
+    Replace,
+    MatchesWallet,
     Details,
     List,
     Sign,
+    CanSign,
+    Capabilities,
     SinglesigDesc,
+    SinglesigDescSet,
     Xpub,
 }
 
@@ -199,6 +262,7 @@ pub struct AssetSubCommands {
 pub enum AssetSubCommandsEnum {
     Contract,
     Details,
+    Metadata,
     List,
     Insert,
     Remove,
@@ -239,11 +303,32 @@ impl std::fmt::Display for XpubKind {
     }
 }
 
+#[derive(ValueEnum, Clone, Debug)]
+pub enum UtxosFormat {
+    Native,
+    Core,
+}
+
+impl std::fmt::Display for UtxosFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UtxosFormat::Native => write!(f, "native"),
+            UtxosFormat::Core => write!(f, "core"),
+        }
+    }
+}
+
 #[derive(Debug, Subcommand)]
 pub enum SignerCommand {
     /// Generate a software signer, returns a mnemonic
     Generate,
 
+    /// Check a mnemonic is valid and return the fingerprint of its signer, without loading it
+    ValidateMnemonic {
+        #[arg(long)]
+        mnemonic: String,
+    },
+
     /// Probe connected Jades, unlocks and returns identifiers that allows to load a Jade
     JadeId {
         /// The socket address to connect to jade emulator
@@ -267,6 +352,10 @@ pub enum SignerCommand {
         /// availabled.
         #[arg(long, required(true))]
         persist: Option<bool>,
+
+        /// Replace a signer already loaded with the same name
+        #[arg(long, action)]
+        force: bool,
     },
 
     /// Load a Jade signer giving it a name
@@ -281,6 +370,10 @@ pub enum SignerCommand {
         /// The socket address to connect to jade emulator
         #[arg(long)]
         emulator: Option<SocketAddr>,
+
+        /// Replace a signer already loaded with the same name
+        #[arg(long, action)]
+        force: bool,
     },
 
     /// Load a signer (software, serial, external) giving it a name
@@ -290,6 +383,27 @@ pub enum SignerCommand {
 
         #[arg(long)]
         fingerprint: String,
+
+        /// Replace a signer already loaded with the same name
+        #[arg(long, action)]
+        force: bool,
+    },
+
+    /// Load several signers in one call
+    ///
+    /// Every signer is reported as loaded or with the reason it couldn't be loaded.
+    LoadMany {
+        /// A signer to load, as a JSON object with the "kind" ("software", "jade" or "external")
+        /// and the parameters of the corresponding load command, e.g.
+        /// '{"kind":"external","name":"s1","fingerprint":"11111111"}'
+        ///
+        /// Can be specified multiple times.
+        #[arg(long, required = true)]
+        signer: Vec<String>,
+
+        /// Load either every signer or none of them
+        #[arg(long, action)]
+        atomic: bool,
     },
 
     /// Details of a signer
@@ -304,6 +418,26 @@ pub enum SignerCommand {
         signer: String,
     },
 
+    /// Use the given signer in place of the external signers with its fingerprint
+    ///
+    /// The external signers are unloaded, so that signing with the wallets referencing that
+    /// fingerprint resolves to the given signer.
+    Replace {
+        #[arg(short, long, env)]
+        signer: String,
+    },
+
+    /// Check whether the signer fingerprint appears in the wallet descriptor
+    ///
+    /// Useful to avoid sending a PSET to a signer that is not a participant of the wallet.
+    MatchesWallet {
+        #[arg(short, long, env)]
+        signer: String,
+
+        #[arg(short, long, env)]
+        wallet: String,
+    },
+
     /// List loaded signers
     List,
 
@@ -314,6 +448,33 @@ pub enum SignerCommand {
 
         #[arg(long)]
         pset: String,
+
+        /// Index of an input to sign, if missing every input the signer can sign is signed
+        ///
+        /// Can be specified multiple times.
+        #[arg(long)]
+        input: Vec<u32>,
+    },
+
+    /// Check whether the signer can sign a transaction of a wallet, without signing it
+    ///
+    /// The inputs to sign are the ones having a key with the signer fingerprint.
+    CanSign {
+        #[arg(short, long, env)]
+        signer: String,
+
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        #[arg(long)]
+        pset: String,
+    },
+
+    /// Get what the signer can do, like signing without a hardware device
+    Capabilities {
+        #[arg(short, long, env)]
+        signer: String,
     },
 
     ///  Prints a singlesig descriptor using this signer key
@@ -328,6 +489,14 @@ pub enum SignerCommand {
         kind: SinglesigKind,
     },
 
+    /// Prints every standard singlesig descriptor using this signer key
+    ///
+    /// One descriptor for every kind and deterministic descriptor blinding key.
+    SinglesigDescSet {
+        #[arg(short, long, env)]
+        signer: String,
+    },
+
     /// Get an extended public key from the signer
     Xpub {
         #[arg(short, long, env)]
@@ -413,6 +582,12 @@ pub enum WalletCommand {
 
         #[arg(short, long)]
         descriptor: String,
+
+        /// Fee rate used by the transactions of this wallet when not specified
+        ///
+        /// Takes precedence over the server defaults.
+        #[arg(long)]
+        default_fee_rate: Option<f32>,
     },
 
     /// Unload a wallet
@@ -426,6 +601,9 @@ pub enum WalletCommand {
     List,
 
     /// Get an address from the given wallet name
+    ///
+    /// Without index the first unused address is returned, it doesn't change until a received
+    /// transaction is found by a scan.
     Address {
         /// Wallet name
         #[arg(short, long, env)]
@@ -452,6 +630,46 @@ pub enum WalletCommand {
         /// the given number is the number of pixel per qr code module
         #[arg(long)]
         with_uri_qr: Option<u8>,
+
+        /// Returns the confidential and explicit address, the script pubkey and the uri
+        #[arg(long)]
+        with_payment_request: bool,
+    },
+
+    /// Display an address on a hardware signer, verifying it matches the one derived by the wallet
+    ///
+    /// Compare the address shown on the device with the one given to the payer, to protect
+    /// from malware replacing it.
+    ConfirmAddress {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// The derivation index of the address
+        #[arg(long)]
+        index: u32,
+
+        /// Name of the hardware signer displaying the address
+        #[arg(short, long, env)]
+        signer: String,
+    },
+
+    /// Compare the address derived by the wallet with the one computed by a signer
+    ///
+    /// Returns both the confidential and explicit forms of the address. If the signer is a Jade,
+    /// the device displays its computed address and the result tells whether they match.
+    VerifyDeviceAddress {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// The derivation index of the address
+        #[arg(long)]
+        index: u32,
+
+        /// Name of the signer computing the address
+        #[arg(short, long, env)]
+        signer: String,
     },
 
     /// Get the balance of the given wallet name
@@ -463,6 +681,40 @@ pub enum WalletCommand {
         /// Replace asset ids with tickers when possible
         #[arg(long, action)]
         with_tickers: bool,
+
+        /// Return which assets in the balance are reissuance tokens and the asset they control
+        #[arg(long, action)]
+        with_reissuance_tokens: bool,
+
+        /// Return also the confirmed balance and the amounts incoming and outgoing in unconfirmed
+        /// transactions
+        #[arg(long, action)]
+        with_pending: bool,
+    },
+
+    /// Get the balance of every loaded wallet
+    ///
+    /// A wallet whose balance can't be computed has an error instead, without failing the others.
+    BalanceAll {
+        /// Replace asset ids with tickers when possible
+        #[arg(long, action)]
+        with_tickers: bool,
+
+        /// Wait for a scan of every wallet before computing the balances
+        #[arg(long, action)]
+        sync: bool,
+    },
+
+    /// Get the balance of a wallet split between confirmed and pending outputs
+    ///
+    /// The confirmations are computed against the tip the wallet is synced to.
+    ConfirmedBalance {
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Confirmations an output needs to be counted as confirmed
+        #[arg(long)]
+        min_confirmations: Option<u32>,
     },
 
     /// Create an unsigned transaction (PSET)
@@ -480,6 +732,204 @@ pub enum WalletCommand {
         #[arg(long, required = true)]
         recipient: Vec<String>,
 
+        /// Fee rate to use
+        #[arg(long, conflicts_with = "fee")]
+        fee_rate: Option<f32>,
+
+        /// Exact fee to pay in satoshi, instead of computing it from the fee rate
+        #[arg(long)]
+        fee: Option<u64>,
+
+        /// Timelocked branch of the descriptor to satisfy, eg "older(144)" or "after(1000)"
+        ///
+        /// Needed to spend with recovery branches, fails if the timelock is not expired yet.
+        #[arg(long)]
+        spend_path: Option<String>,
+
+        /// Randomize the outputs order and split the L-BTC change in two outputs
+        ///
+        /// Makes it harder to tell which output is the change, at the cost of a higher fee for
+        /// the additional output. The fee output is always last.
+        #[arg(long, action)]
+        privacy: bool,
+    },
+
+    /// Create a transaction, sign it with the given software signers and finalize it
+    ///
+    /// The transaction is not broadcast. Fails if the signers can't reach the wallet threshold.
+    /// Useful to test multisig wallets with signers loaded in the same server.
+    LocalMultisigSend {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Specify a recipient in the form "address:satoshi:asset_id"
+        ///
+        /// Can be specified multiple times.
+        #[arg(long, required = true)]
+        recipient: Vec<String>,
+
+        /// A software signer signing the transaction, can be specified multiple times
+        #[arg(long, required = true)]
+        signer: Vec<String>,
+
+        /// Fee rate to use
+        #[arg(long, conflicts_with = "fee")]
+        fee_rate: Option<f32>,
+
+        /// Exact fee to pay in satoshi, instead of computing it from the fee rate
+        #[arg(long)]
+        fee: Option<u64>,
+    },
+
+    /// Create an unsigned transaction (PSET) sweeping the smallest L-BTC utxos into one output
+    ///
+    /// Useful to reduce the fees of future transactions when the wallet has many small utxos.
+    Consolidate {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Fee rate to use
+        #[arg(long)]
+        fee_rate: Option<f32>,
+
+        /// Maximum number of utxos to spend, the smallest are chosen first [default: 100]
+        #[arg(long)]
+        max_inputs: Option<u32>,
+
+        /// Address receiving the consolidated output, a change address of the wallet if not given
+        #[arg(long)]
+        target_address: Option<String>,
+    },
+
+    /// Get the maximum amount of an asset the wallet can send, and the fee it would pay
+    ///
+    /// For L-BTC the fee is subtracted from the balance, other assets are sent entirely and
+    /// the fee is paid with L-BTC. Locked utxos are not spent.
+    MaxSpendable {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Asset identifier in hex, L-BTC if not given
+        #[arg(long)]
+        asset: Option<String>,
+
+        /// Fee rate to use
+        #[arg(long)]
+        fee_rate: Option<f32>,
+
+        /// Destination address, an address of the wallet if not given
+        #[arg(long)]
+        address: Option<String>,
+    },
+
+    /// Create a transaction sending all the funds of a private key to the wallet
+    ///
+    /// The funds are searched at the `elwpkh` address of the key. The returned transaction is
+    /// signed and finalized, it's not broadcast.
+    SweepKey {
+        /// Wallet name receiving the funds
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Private key in WIF format
+        #[arg(long)]
+        wif: String,
+
+        /// Descriptor blinding key of the private key funds, like "slip77(...)" or a view key
+        #[arg(long)]
+        blinding_key: String,
+
+        /// Script type of the private key address, "wpkh" or "shwpkh". If missing both are
+        /// scanned
+        #[arg(long)]
+        script_type: Option<String>,
+
+        /// Fee rate to use
+        #[arg(long)]
+        fee_rate: Option<f32>,
+    },
+
+    /// Validate recipients without creating a transaction
+    ValidateAddressees {
+        /// Specify a recipient in the form "address:satoshi:asset_id"
+        ///
+        /// Can be specified multiple times.
+        #[arg(long, required = true)]
+        recipient: Vec<String>,
+    },
+
+    /// Add inputs and outputs of the wallet to a PSET shared with other parties
+    ///
+    /// Every party adds its contribution, then each one blinds its outputs with `--blind`.
+    AddToPset {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET to add to
+        #[arg(short, long)]
+        pset: String,
+
+        /// Wallet unspent output to add as input, in the form "txid:vout"
+        ///
+        /// Can be specified multiple times.
+        #[arg(long)]
+        input: Vec<String>,
+
+        /// Specify an output in the form "address:satoshi:asset_id"
+        ///
+        /// Can be specified multiple times.
+        #[arg(long)]
+        recipient: Vec<String>,
+
+        /// Satoshi of the policy asset contributed to the fee
+        #[arg(long, default_value_t = 0)]
+        fee: u64,
+
+        /// Blind the outputs of the wallet, once every party has added its contribution
+        #[arg(long)]
+        blind: bool,
+    },
+
+    /// Propose to swap an asset of the wallet for another asset
+    ///
+    /// The counterparty completes the proposal with `swap-accept`, then the proposer blinds its
+    /// outputs with `add-to-pset --blind` and both parties sign.
+    SwapPropose {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Asset offered
+        #[arg(long)]
+        send_asset: String,
+
+        /// Satoshi of the asset offered
+        #[arg(long)]
+        send_amount: u64,
+
+        /// Asset asked in exchange
+        #[arg(long)]
+        receive_asset: String,
+
+        /// Satoshi of the asset asked
+        #[arg(long)]
+        receive_amount: u64,
+    },
+
+    /// Accept a swap proposal, adding the wallet inputs and outputs and paying the fee
+    SwapAccept {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Swap proposal PSET
+        #[arg(short, long)]
+        pset: String,
+
         /// Fee rate to use
         #[arg(long)]
         fee_rate: Option<f32>,
@@ -517,6 +967,11 @@ pub enum WalletCommand {
         /// To optionally specify a fee
         #[arg(long)]
         fee_rate: Option<f32>,
+
+        /// Make the issued asset and token amounts confidential.
+        /// Explicit amounts let anyone verify the asset supply, as registries usually require
+        #[arg(long)]
+        blinded_issuance: bool,
     },
 
     /// Reissue a previously issued asset, needs ownership of the issuance token
@@ -577,29 +1032,194 @@ pub enum WalletCommand {
 
         #[arg(long, required = true)]
         keyorigin_xpub: Vec<String>,
-    },
 
-    /// Try to finalize the PSET and broadcast the transaction
-    Broadcast {
-        /// Wallet name
-        #[arg(short, long, env)]
-        wallet: String,
+        /// Also return a deterministic name to use as wallet name when registering on Jade
+        #[arg(long, action)]
+        with_jade_name: bool,
+    },
 
-        /// Do the finalization without the broadcast
+    /// Compile a miniscript policy into a descriptor, without loading it
+    ///
+    /// The policy must be safe and non-malleable, e.g. "thresh(2,pk(A),pk(B),older(144))" where
+    /// keys are ranged like "[fingerprint/87h/1h/0h]xpub/<0;1>/*".
+    CompilePolicy {
         #[arg(long)]
-        dry_run: bool,
+        policy: String,
 
         #[arg(long)]
-        pset: String,
+        descriptor_blinding_key: BlindingKeyKind,
     },
 
-    /// Get detailed information about the wallet
-    Details {
-        /// Wallet name
+    /// Compute the checksum of a descriptor, without loading it
+    DescriptorChecksum {
+        #[arg(short, long)]
+        descriptor: String,
+    },
+
+    /// Check the keys of a descriptor are for the server network, without loading it
+    ///
+    /// Loading the wallet does the same check.
+    CheckNetwork {
+        #[arg(short, long)]
+        descriptor: String,
+    },
+
+    /// Convert a descriptor blinding key to another representation, without loading a wallet
+    ///
+    /// A slip77 key can only be normalized, while a view key can be converted to elip151 and
+    /// back when the descriptor is given.
+    ConvertBlindingKey {
+        /// The blinding key: "slip77(<hex>)", a view key (hex or WIF) or "elip151"
+        #[arg(short, long)]
+        input: String,
+
+        /// The requested representation: "slip77", "view" or "elip151"
+        #[arg(short, long)]
+        to: String,
+
+        /// The descriptor the key must be compatible with, required for "elip151"
+        #[arg(short, long)]
+        descriptor: Option<String>,
+    },
+
+    /// Get the fee, the virtual size and the fee rate of a PSET, without loading a wallet
+    PsetFee {
+        /// PSET
+        #[arg(short, long)]
+        pset: String,
+    },
+
+    /// Re-serialize a PSET with the requested version, without loading a wallet
+    ///
+    /// Only PSET version 2 is currently supported, other versions return an error.
+    ConvertPsetVersion {
+        /// PSET
+        #[arg(short, long)]
+        pset: String,
+
+        /// The requested PSET version
+        #[arg(short, long, default_value_t = 2)]
+        version: u32,
+    },
+
+    /// Remove every signature of a PSET, keeping its inputs and outputs, without loading a wallet
+    ///
+    /// Useful to sign again the transaction from scratch, e.g. with different signers.
+    StripSignatures {
+        /// PSET
+        #[arg(short, long)]
+        pset: String,
+    },
+
+    /// Try to finalize the PSET and broadcast the transaction
+    Broadcast {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Do the finalization without the broadcast
+        #[arg(long)]
+        dry_run: bool,
+
+        #[arg(long)]
+        pset: String,
+
+        /// Wait until the transaction has this number of confirmations before returning
+        #[arg(long)]
+        wait_confirmations: Option<u32>,
+
+        /// The maximum number of seconds to wait for the confirmations, 60 if missing, at most 600
+        #[arg(long, requires = "wait_confirmations")]
+        wait_timeout: Option<u64>,
+    },
+
+    /// Try to finalize and broadcast several independent PSETs
+    ///
+    /// A failure doesn't stop the following PSETs, the result of each one is reported.
+    BroadcastBatch {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// A PSET to broadcast, can be specified multiple times
+        #[arg(long, required = true)]
+        pset: Vec<String>,
+    },
+
+    /// Broadcast again the unconfirmed transactions of the wallet
+    ///
+    /// Useful when they were dropped from the mempool, for example after a node restart.
+    RebroadcastPending {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+    },
+
+    /// Get detailed information about the wallet
+    Details {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+    },
+
+    /// Get the descriptor of the wallet
+    Descriptor {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Also return the descriptor of the internal (change) chain
+        #[arg(long, action)]
+        include_internal: bool,
+    },
+
+    /// Get how far the wallet has been synced compared to the blockchain tip
+    SyncProgress {
+        /// Wallet name
         #[arg(short, long, env)]
         wallet: String,
     },
 
+    /// Stop the running scan of the wallet
+    CancelSync {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+    },
+
+    /// Scan the wallet with a different Electrum server, keeping its synced data
+    ///
+    /// The change is not persisted, after a restart the configured server is used
+    SetBackend {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Electrum server url, like "blockstream.info:995"
+        #[arg(long)]
+        electrum_url: String,
+
+        /// Connect to the Electrum server with TLS
+        #[arg(long, action)]
+        tls: bool,
+
+        /// Accept only an Electrum server certificate with this SHA-256 fingerprint, in hex with
+        /// optional `:` separators
+        #[arg(long)]
+        cert_fingerprint: Option<String>,
+    },
+
+    /// Merge the signatures of PSETs describing the same transaction, without loading a wallet
+    ///
+    /// Fails if the PSETs describe different transactions.
+    MergeSignatures {
+        /// PSETs to merge
+        ///
+        /// Can be specified multiple times.
+        #[arg(short, long, required = true)]
+        pset: Vec<String>,
+    },
+
     /// Combine PSETs
     Combine {
         /// Wallet name
@@ -609,8 +1229,15 @@ pub enum WalletCommand {
         /// PSETs to combine
         ///
         /// Can be specified multiple times.
-        #[arg(short, long, required = true)]
+        #[arg(short, long, required_unless_present = "pset_file")]
         pset: Vec<String>,
+
+        /// Files containing PSETs to combine, in base64 or binary, read by the server
+        ///
+        /// Paths are relative to the directory given with `--pset-dir` when starting the server.
+        /// Can be specified multiple times and together with `--pset`.
+        #[arg(long)]
+        pset_file: Vec<String>,
     },
 
     /// Get the details of a PSET
@@ -628,11 +1255,183 @@ pub enum WalletCommand {
         with_tickers: bool,
     },
 
+    /// Check which spending branches of the wallet descriptor the inputs of a PSET satisfy
+    ///
+    /// Inputs are checked with their signatures, sequence and locktime, so this should be used
+    /// before finalizing. Useful with descriptors having alternative branches, like recovery or
+    /// escrow paths, to know which one a transaction is going to use.
+    CheckSpendPath {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Bundle a PSET with the wallet data needed to verify it, to be signed by an offline signer
+    SigningRequest {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Look for privacy issues of a PSET, like address reuse or change that can be told apart
+    PrivacyAnalysis {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Scan the wallet and check the inputs of a PSET are still unspent outputs of the wallet
+    ///
+    /// Useful before signing a PSET created a while ago, whose inputs might have been spent by
+    /// another transaction in the meantime.
+    CheckPsetSpendable {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Estimate the size and the fee of a PSET once every input is signed
+    ///
+    /// Useful for multisig to check the fee is adequate before collecting all the signatures.
+    MultisigFeeEstimate {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Start a signing session for a PSET
+    ///
+    /// The server keeps the PSET combining the signatures added to the session,
+    /// see `session-add` and `session-get`
+    SessionCreate {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Add the signatures contained in the given PSET to a signing session
+    SessionAdd {
+        /// The session identifier
+        #[arg(long)]
+        session_id: String,
+
+        /// PSET
+        #[arg(short, long, required = true)]
+        pset: String,
+    },
+
+    /// Get the PSET of a signing session and the signatures still missing
+    SessionGet {
+        /// The session identifier
+        #[arg(long)]
+        session_id: String,
+    },
+
     /// Get the wallet unspent transaction outputs
     Utxos {
         /// Wallet name
         #[arg(short, long, env)]
         wallet: String,
+
+        /// With `core` return the outputs as the Elements Core `listunspent` call
+        #[arg(long, default_value_t = UtxosFormat::Native)]
+        format: UtxosFormat,
+    },
+
+    /// Get the addresses of the wallet that received funds, with their balances
+    UsedAddresses {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+    },
+
+    /// Get the L-BTC outputs of the wallet worth less than the fee needed to spend them
+    ///
+    /// Useful to decide whether to consolidate, and to understand why the spendable balance is
+    /// lower than the total.
+    DustReport {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Fee rate used to compute the cost of spending the outputs
+        #[arg(long)]
+        fee_rate: Option<f32>,
+    },
+
+    /// Scan the wallet with a custom gap limit and get the derivation indexes that received funds
+    ///
+    /// Useful to recover old wallets that used more addresses than the default gap limit. The
+    /// wallet is not changed, every chain is checked up to index 10000 at most.
+    DiscoverFunds {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Number of consecutive unused addresses after which the scan of a chain stops
+        #[arg(long)]
+        max_gap: Option<u32>,
+    },
+
+    /// Reserve an output, so that the transactions created afterwards don't spend it
+    LockUtxo {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// The output to lock, as "txid:vout"
+        #[arg(long)]
+        outpoint: String,
+
+        /// Unix time in seconds when the lock expires, if missing in one hour
+        #[arg(long)]
+        expires_at: Option<u64>,
+    },
+
+    /// Release an output locked with `lock-utxo`
+    UnlockUtxo {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// The output to unlock, as "txid:vout"
+        #[arg(long)]
+        outpoint: String,
+    },
+
+    /// Get the number of confirmations of a wallet transaction
+    Confirmations {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Transaction id
+        #[arg(long)]
+        txid: String,
     },
 
     /// Get the wallet transactions
@@ -646,6 +1445,19 @@ pub enum WalletCommand {
         with_tickers: bool,
     },
 
+    /// Get the transaction issuing an asset of the wallet
+    ///
+    /// Fails if the asset was not issued by the wallet.
+    IssuanceTx {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Asset identifier in hex
+        #[arg(long)]
+        asset: String,
+    },
+
     /// Get a transaction
     Tx {
         /// Wallet name
@@ -661,6 +1473,49 @@ pub enum WalletCommand {
         from_explorer: bool,
     },
 
+    /// Decode the data (OP_RETURN) outputs of a wallet transaction, like burns
+    DecodeDataOutputs {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Transaction ID
+        #[arg(short, long)]
+        txid: String,
+    },
+
+    /// Get what is needed to load the wallet as watch-only elsewhere
+    ExportWatchOnly {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+    },
+
+    /// Export the wallet in the format another wallet software imports to view it as watch-only
+    ExportTo {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// The wallet software: "green" (Blockstream Green), "core" (Elements Core
+        /// `importdescriptors`) or "generic"
+        #[arg(short, long)]
+        target: String,
+    },
+
+    /// Get the blinding factors of the wallet outputs of a transaction
+    ///
+    /// Requires the server to be started with `--allow-secret-export`.
+    BlindingFactors {
+        /// Wallet name
+        #[arg(short, long, env)]
+        wallet: String,
+
+        /// Transaction ID
+        #[arg(short, long)]
+        txid: String,
+    },
+
     /// Set a wallet tx memo
     SetTxMemo {
         /// Wallet name
@@ -735,6 +1590,15 @@ pub enum AssetCommand {
         asset: String,
     },
 
+    /// Get the metadata committed in the contract of an asset
+    ///
+    /// If the asset is not stored in the server, the contract is fetched from the registry
+    Metadata {
+        /// Asset ID in hex
+        #[arg(short, long)]
+        asset: String,
+    },
+
     /// List assets
     List,
 
@@ -795,13 +1659,18 @@ pub struct ServerArgs {
     pub command: ServerCommand,
 }
 
+// Parsed once at startup, boxing the start variant doesn't save anything
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Subcommand)]
 pub enum ServerCommand {
     /// Start the server
     Start {
         /// Electrum URL, if not specified a reasonable default is used according to the network
+        ///
+        /// Can be specified multiple times, the following ones are fallbacks tried in order when
+        /// the connection to the previous ones fails.
         #[arg(short, long)]
-        electrum_url: Option<String>,
+        electrum_url: Vec<String>,
 
         #[arg(long)]
         #[cfg(feature = "registry")]
@@ -825,11 +1694,136 @@ pub enum ServerCommand {
         /// Interval between blockchain scans (seconds)
         #[arg(long)]
         scanning_interval: Option<u64>,
+
+        /// How long asset contracts fetched from the registry are cached before being refreshed (seconds)
+        #[arg(long)]
+        registry_cache_ttl: Option<u64>,
+
+        /// Don't fail broadcasting a transaction already in mempool or in chain
+        ///
+        /// The response signals it with `already_known`, by default it's an error.
+        #[arg(long)]
+        already_known_is_success: bool,
+
+        /// Maximum number of wallets that can be loaded at the same time
+        #[arg(long)]
+        max_wallets: Option<usize>,
+
+        /// Maximum number of signers that can be loaded at the same time
+        #[arg(long)]
+        max_signers: Option<usize>,
+
+        /// Maximum number of connections open at the same time to the Electrum servers
+        ///
+        /// Bounds the scans, broadcasts and fee estimations of every wallet, to avoid being
+        /// banned by public servers.
+        #[arg(long)]
+        max_backend_connections: Option<usize>,
+
+        /// Warn in PSET details if the fee is higher than this fraction of the amount sent
+        #[arg(long)]
+        fee_warning_fraction: Option<f64>,
+
+        /// Allow methods returning secret data, like the blinding factors of transactions
+        #[arg(long)]
+        allow_secret_export: bool,
+
+        /// Limit the requests per second of an rpc method for each auth token, in the form
+        /// `<METHOD>:<REQUESTS>`, e.g. `scan:2`
+        ///
+        /// A limit of 0 removes the default limit of the method. Can be given multiple times.
+        #[arg(long)]
+        rate_limit: Vec<String>,
+
+        /// If the datadir is not writable, keep everything in memory instead of failing
+        ///
+        /// Nothing is persisted, loaded wallets and signers are lost when the server stops.
+        #[arg(long)]
+        in_memory_fallback: bool,
+
+        /// Accept only an Electrum server certificate with this SHA-256 fingerprint, in hex with
+        /// optional `:` separators
+        ///
+        /// Allows to connect to servers with self-signed certificates, the domain is not validated.
+        /// It applies to `--electrum-url` only, not to the fallback servers.
+        #[arg(long)]
+        electrum_cert_fingerprint: Option<String>,
+
+        /// Directory from which PSET files can be read, e.g. by `wallet combine --pset-file`
+        ///
+        /// If not given, PSET files are refused.
+        #[arg(long)]
+        pset_dir: Option<PathBuf>,
+
+        /// Accept requests authenticated with the given token, in the form
+        /// `<TOKEN>:<PERMISSIONS>[:<WALLETS>]`
+        ///
+        /// Permissions are one of the builtin roles `readonly` and `full`, or a comma separated
+        /// list of rpc method names, e.g. `mytoken:wallet_balance,wallet_txs`.
+        ///
+        /// Wallets is an optional comma separated list of wallet names, if given the token can
+        /// call wallet methods only on those wallets, e.g. `mytoken:readonly:w1`.
+        ///
+        /// Can be given multiple times. If never given, requests are not authenticated.
+        #[arg(long)]
+        allow_token: Vec<String>,
+
+        /// INSECURE, ONLY FOR TESTS: generate signers deterministically from this seed
+        ///
+        /// Anyone knowing the seed knows the keys of the generated signers. Refused on mainnet.
+        #[arg(long)]
+        insecure_signer_seed: Option<u64>,
+    },
+
+    /// Show whether the server is alive and ready to serve
+    ///
+    /// Ready means the backend was reachable and all wallets have been scanned
+    Health,
+
+    /// Open a new connection to the Electrum backend and measure its latency
+    ///
+    /// Useful to check if the backend is the bottleneck when wallets are syncing slowly
+    PingBackend,
+
+    /// Set the defaults used when creating transactions without specifying them
+    ///
+    /// Options not given are unset, the defaults are persisted in the server state.
+    SetDefaults {
+        /// Default fee rate
+        #[arg(long)]
+        fee_rate: Option<f32>,
+
+        /// Default number of blocks within which transactions should confirm, the fee rate is
+        /// estimated by the Electrum server
+        #[arg(long, conflicts_with = "fee_rate")]
+        confirmation_target: Option<u32>,
     },
 
+    /// Show the defaults used when creating transactions
+    GetDefaults,
+
+    /// Show the configuration the server is running with
+    ///
+    /// Auth tokens are not shown and credentials in urls are redacted.
+    GetConfig,
+
+    /// List the variants accepted when creating descriptors and xpubs, like "wpkh" or "slip77"
+    ListVariants,
+
     /// Wait until an entire blockchain scan has been completed
     Scan,
 
+    /// Unload every wallet, every signer or both, e.g. before stopping the server
+    UnloadAll {
+        /// Unload every wallet, interrupting their scan
+        #[arg(long)]
+        wallets: bool,
+
+        /// Unload every signer
+        #[arg(long)]
+        signers: bool,
+    },
+
     /// Stop the server
     ///
     /// Alternatively the server can be stopped also with SIGINT (ctrl-c)
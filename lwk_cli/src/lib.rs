@@ -9,7 +9,9 @@ use std::{
 
 use anyhow::{anyhow, Context};
 use clap::CommandFactory;
+use lwk_app::method::Method;
 use lwk_app::Config;
+use lwk_rpc_model::request;
 use serde_json::Value;
 use tracing_subscriber::{filter::LevelFilter, EnvFilter, FmtSubscriber};
 
@@ -58,7 +60,7 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
     let addr = args
         .addr
         .unwrap_or_else(|| SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), default_port));
-    let client = lwk_app::Client::new(addr)?;
+    let client = lwk_app::Client::with_auth_token(addr, args.auth_token.clone())?;
 
     // verify the server is up if needed
     if args.command.requires_server_running() {
@@ -85,6 +87,19 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                     datadir,
                     timeout,
                     scanning_interval,
+                    registry_cache_ttl,
+                    already_known_is_success,
+                    max_wallets,
+                    max_signers,
+                    max_backend_connections,
+                    fee_warning_fraction,
+                    allow_secret_export,
+                    in_memory_fallback,
+                    pset_dir,
+                    electrum_cert_fingerprint,
+                    rate_limit,
+                    allow_token,
+                    insecure_signer_seed,
                 } => {
                     let (tx, rx) = std::sync::mpsc::channel();
                     let _ = ctrlc::try_set_handler(move || {
@@ -106,8 +121,31 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                     if let Some(scanning_interval) = scanning_interval {
                         config.scanning_interval = Duration::from_secs(scanning_interval);
                     };
-                    if let Some(url) = electrum_url {
-                        config.electrum_url = url;
+                    if let Some(registry_cache_ttl) = registry_cache_ttl {
+                        config.registry_cache_ttl = Duration::from_secs(registry_cache_ttl);
+                    };
+                    config.already_known_is_success = already_known_is_success;
+                    if let Some(max_wallets) = max_wallets {
+                        config.max_wallets = max_wallets;
+                    };
+                    if let Some(max_signers) = max_signers {
+                        config.max_signers = max_signers;
+                    };
+                    if let Some(max_backend_connections) = max_backend_connections {
+                        config.max_backend_connections = max_backend_connections;
+                    };
+                    if let Some(fee_warning_fraction) = fee_warning_fraction {
+                        config.fee_warning_fraction = fee_warning_fraction;
+                    };
+                    config.allow_secret_export = allow_secret_export;
+                    config.in_memory_fallback = in_memory_fallback;
+                    config.pset_dir = pset_dir;
+                    config.insecure_signer_seed = insecure_signer_seed;
+                    config.electrum_cert_fingerprint = electrum_cert_fingerprint;
+                    config.electrum_cert_sha256()?;
+                    if let Some((url, fallbacks)) = electrum_url.split_first() {
+                        config.electrum_url = url.clone();
+                        config.electrum_fallback_urls = fallbacks.to_vec();
                     } else if let Network::Regtest = args.network {
                         anyhow::bail!("on regtest you have to specify --electrum-url");
                     };
@@ -120,6 +158,43 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                         config.registry_url = url;
                     };
 
+                    for l in rate_limit {
+                        let parsed = l.split_once(':').and_then(|(method, limit)| {
+                            Some((method.parse::<Method>().ok()?, limit.parse::<u32>().ok()?))
+                        });
+                        match parsed {
+                            Some((method, 0)) => {
+                                config.rate_limits.remove(&method);
+                            }
+                            Some((method, limit)) => {
+                                config.rate_limits.insert(method, limit);
+                            }
+                            None => {
+                                return Err(anyhow!(
+                                    "Invalid rate limit '{l}', expected <METHOD>:<REQUESTS>"
+                                ))
+                            }
+                        }
+                    }
+
+                    for t in allow_token {
+                        let mut parts = t.splitn(3, ':');
+                        let (Some(token), Some(permissions)) = (parts.next(), parts.next()) else {
+                            return Err(anyhow!(
+                                "Invalid token '{t}', expected <TOKEN>:<PERMISSIONS>[:<WALLETS>]"
+                            ));
+                        };
+                        config
+                            .auth_tokens
+                            .insert(token.to_string(), permissions.parse()?);
+                        if let Some(wallets) = parts.next() {
+                            let wallets = wallets.split(',').map(|w| w.trim().to_string());
+                            config
+                                .auth_token_wallets
+                                .insert(token.to_string(), wallets.collect());
+                        }
+                    }
+
                     config.addr = addr;
                     let mut app = lwk_app::App::new(config)?;
 
@@ -149,9 +224,33 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                     app.join_threads()?;
                     tracing::info!("Threads ended");
                 }
+                ServerCommand::Health => {
+                    return Ok(serde_json::to_value(client.health()?)?);
+                }
+                ServerCommand::PingBackend => {
+                    return Ok(serde_json::to_value(client.ping_backend()?)?);
+                }
+                ServerCommand::SetDefaults {
+                    fee_rate,
+                    confirmation_target,
+                } => {
+                    client.set_defaults(fee_rate, confirmation_target)?;
+                }
+                ServerCommand::GetDefaults => {
+                    return Ok(serde_json::to_value(client.get_defaults()?)?);
+                }
+                ServerCommand::GetConfig => {
+                    return Ok(serde_json::to_value(client.get_config()?)?);
+                }
+                ServerCommand::ListVariants => {
+                    return Ok(serde_json::to_value(client.list_variants()?)?);
+                }
                 ServerCommand::Scan => {
                     client.scan()?;
                 }
+                ServerCommand::UnloadAll { wallets, signers } => {
+                    return Ok(serde_json::to_value(client.unload_all(wallets, signers)?)?);
+                }
                 ServerCommand::Stop => {
                     client.stop()?;
                 }
@@ -164,38 +263,74 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 let j = client.signer_generate()?;
                 serde_json::to_value(j)?
             }
+            SignerCommand::ValidateMnemonic { mnemonic } => {
+                let r = client.signer_validate_mnemonic(mnemonic)?;
+                serde_json::to_value(r)?
+            }
             SignerCommand::JadeId { emulator } => {
                 let j = client.signer_jade_id(emulator)?;
                 serde_json::to_value(j)?
             }
-            SignerCommand::Sign { signer, pset } => {
-                let r = client.signer_sign(signer, pset)?;
+            SignerCommand::Sign {
+                signer,
+                pset,
+                input,
+            } => {
+                let inputs = (!input.is_empty()).then_some(input);
+                let r = client.signer_sign(signer, pset, inputs)?;
+                serde_json::to_value(r)?
+            }
+            SignerCommand::CanSign {
+                signer,
+                wallet,
+                pset,
+            } => {
+                let r = client.signer_can_sign(signer, wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            SignerCommand::Capabilities { signer } => {
+                let r = client.signer_capabilities(signer)?;
                 serde_json::to_value(r)?
             }
             SignerCommand::LoadSoftware {
                 signer,
                 mnemonic,
                 persist,
+                force,
             } => {
                 let persist = persist.expect("required");
-                let j = client.signer_load_software(signer, mnemonic, persist)?;
+                let j = client.signer_load_software(signer, mnemonic, persist, force)?;
                 serde_json::to_value(j)?
             }
             SignerCommand::LoadJade {
                 signer,
                 id,
                 emulator,
+                force,
             } => {
-                let j = client.signer_load_jade(signer, id, emulator)?;
+                let j = client.signer_load_jade(signer, id, emulator, force)?;
                 serde_json::to_value(j)?
             }
             SignerCommand::LoadExternal {
                 signer,
                 fingerprint,
+                force,
             } => {
-                let j = client.signer_load_external(signer, fingerprint)?;
+                let j = client.signer_load_external(signer, fingerprint, force)?;
                 serde_json::to_value(j)?
             }
+            SignerCommand::LoadMany { signer, atomic } => {
+                let signers = signer
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| {
+                        // Not showing the given signer since it may contain a mnemonic
+                        serde_json::from_str(s).with_context(|| format!("Invalid signer {i}"))
+                    })
+                    .collect::<Result<_, _>>()?;
+                let r = client.signer_load_many(signers, atomic)?;
+                serde_json::to_value(r)?
+            }
             SignerCommand::List => serde_json::to_value(client.signer_list()?)?,
             SignerCommand::Details { signer } => {
                 let r = client.signer_details(signer)?;
@@ -205,6 +340,14 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 let r = client.signer_unload(signer)?;
                 serde_json::to_value(r)?
             }
+            SignerCommand::Replace { signer } => {
+                let r = client.signer_replace(signer)?;
+                serde_json::to_value(r)?
+            }
+            SignerCommand::MatchesWallet { signer, wallet } => {
+                let r = client.signer_matches_wallet(signer, wallet)?;
+                serde_json::to_value(r)?
+            }
             SignerCommand::SinglesigDesc {
                 signer,
                 descriptor_blinding_key,
@@ -217,6 +360,10 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 )?;
                 serde_json::to_value(r)?
             }
+            SignerCommand::SinglesigDescSet { signer } => {
+                let r = client.signer_singlesig_descriptor_set(signer)?;
+                serde_json::to_value(r)?
+            }
             SignerCommand::Xpub { signer, kind } => {
                 let r = client.signer_xpub(signer, kind.to_string())?;
                 serde_json::to_value(r)?
@@ -227,8 +374,12 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
             }
         },
         CliCommand::Wallet(a) => match a.command {
-            WalletCommand::Load { descriptor, wallet } => {
-                let r = client.wallet_load(descriptor, wallet)?;
+            WalletCommand::Load {
+                descriptor,
+                wallet,
+                default_fee_rate,
+            } => {
+                let r = client.wallet_load_with_fee_rate(descriptor, wallet, default_fee_rate)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::Unload { wallet } => {
@@ -238,14 +389,124 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
             WalletCommand::Balance {
                 wallet,
                 with_tickers,
+                with_reissuance_tokens,
+                with_pending,
+            } => {
+                let r = client.wallet_balance(
+                    wallet,
+                    with_tickers,
+                    with_reissuance_tokens,
+                    with_pending,
+                )?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::BalanceAll { with_tickers, sync } => {
+                let r = client.wallet_balance_all(with_tickers, sync)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ConfirmedBalance {
+                wallet,
+                min_confirmations,
             } => {
-                let r = client.wallet_balance(wallet, with_tickers)?;
+                let r = client.wallet_confirmed_balance(wallet, min_confirmations)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::Send {
                 wallet,
                 recipient,
                 fee_rate,
+                fee,
+                spend_path,
+                privacy,
+            } => {
+                let mut addressees = vec![];
+                for rec in recipient {
+                    addressees.push(
+                        rec.try_into()
+                            .with_context(|| "error parsing recipient argument")?,
+                    );
+                }
+
+                let req = request::WalletSendMany {
+                    addressees: addressees
+                        .into_iter()
+                        .map(lwk_app::unvalidate_addressee)
+                        .collect(),
+                    fee_rate,
+                    fee,
+                    name: wallet,
+                    spend_path,
+                    privacy,
+                };
+                let r = client.wallet_send_many_with_request(req)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::LocalMultisigSend {
+                wallet,
+                recipient,
+                signer,
+                fee_rate,
+                fee,
+            } => {
+                let mut addressees = vec![];
+                for rec in recipient {
+                    addressees.push(
+                        rec.try_into()
+                            .with_context(|| "error parsing recipient argument")?,
+                    );
+                }
+
+                let r =
+                    client.wallet_local_multisig_send(wallet, addressees, fee_rate, fee, signer)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::Consolidate {
+                wallet,
+                fee_rate,
+                max_inputs,
+                target_address,
+            } => {
+                let r = client.wallet_consolidate(wallet, fee_rate, max_inputs, target_address)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::MaxSpendable {
+                wallet,
+                asset,
+                fee_rate,
+                address,
+            } => {
+                let r = client.wallet_max_spendable(wallet, asset, fee_rate, address)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SweepKey {
+                wallet,
+                wif,
+                blinding_key,
+                script_type,
+                fee_rate,
+            } => {
+                let r = client.sweep_key(wallet, wif, blinding_key, script_type, fee_rate)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ValidateAddressees { recipient } => {
+                let mut addressees = vec![];
+                for rec in recipient {
+                    addressees.push(
+                        rec.try_into()
+                            .with_context(|| "error parsing recipient argument")?,
+                    );
+                }
+
+                let r = client.wallet_validate_addressees(addressees)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::AddToPset {
+                wallet,
+                pset,
+                input,
+                recipient,
+                fee,
+                blind,
             } => {
                 let mut addressees = vec![];
                 for rec in recipient {
@@ -255,7 +516,31 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                     );
                 }
 
-                let r = client.wallet_send_many(wallet, addressees, fee_rate)?;
+                let r = client.wallet_add_to_pset(wallet, pset, input, addressees, fee, blind)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SwapPropose {
+                wallet,
+                send_asset,
+                send_amount,
+                receive_asset,
+                receive_amount,
+            } => {
+                let r = client.wallet_swap_propose(
+                    wallet,
+                    send_asset,
+                    send_amount,
+                    receive_asset,
+                    receive_amount,
+                )?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SwapAccept {
+                wallet,
+                pset,
+                fee_rate,
+            } => {
+                let r = client.wallet_swap_accept(wallet, pset, fee_rate)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::Address {
@@ -264,8 +549,32 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 signer,
                 with_text_qr,
                 with_uri_qr,
+                with_payment_request,
+            } => {
+                let r = client.wallet_address(
+                    wallet,
+                    index,
+                    signer,
+                    with_text_qr,
+                    with_uri_qr,
+                    with_payment_request,
+                )?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ConfirmAddress {
+                wallet,
+                index,
+                signer,
+            } => {
+                let r = client.wallet_confirm_address(wallet, index, signer)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::VerifyDeviceAddress {
+                wallet,
+                index,
+                signer,
             } => {
-                let r = client.wallet_address(wallet, index, signer, with_text_qr, with_uri_qr)?;
+                let r = client.verify_device_address(wallet, signer, index)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::List => serde_json::to_value(client.wallet_list()?)?,
@@ -277,6 +586,7 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 address_token,
                 contract,
                 fee_rate,
+                blinded_issuance,
             } => {
                 let r = client.wallet_issue(
                     wallet,
@@ -286,6 +596,7 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                     address_token,
                     contract,
                     fee_rate,
+                    blinded_issuance,
                 )?;
                 serde_json::to_value(r)?
             }
@@ -314,29 +625,119 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 kind,
                 threshold,
                 keyorigin_xpub,
+                with_jade_name,
             } => {
                 let r = client.wallet_multisig_descriptor(
                     descriptor_blinding_key.to_string(),
                     kind.to_string(),
                     threshold,
                     keyorigin_xpub,
+                    with_jade_name,
                 )?;
                 serde_json::to_value(r)?
             }
+            WalletCommand::CompilePolicy {
+                policy,
+                descriptor_blinding_key,
+            } => {
+                let r =
+                    client.wallet_compile_policy(policy, descriptor_blinding_key.to_string())?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::DescriptorChecksum { descriptor } => {
+                let r = client.wallet_descriptor_checksum(descriptor)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::CheckNetwork { descriptor } => {
+                let r = client.wallet_check_network(descriptor)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ConvertBlindingKey {
+                input,
+                to,
+                descriptor,
+            } => {
+                let r = client.wallet_convert_blinding_key(input, to, descriptor)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::PsetFee { pset } => {
+                let r = client.wallet_pset_fee(pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::CheckSpendPath { wallet, pset } => {
+                let r = client.wallet_check_spend_path(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ConvertPsetVersion { pset, version } => {
+                let r = client.wallet_convert_pset_version(pset, version)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::StripSignatures { pset } => {
+                let r = client.wallet_strip_signatures(pset)?;
+                serde_json::to_value(r)?
+            }
             WalletCommand::Broadcast {
                 dry_run,
                 pset,
                 wallet,
+                wait_confirmations,
+                wait_timeout,
             } => {
-                let r = client.wallet_broadcast(wallet, dry_run, pset)?;
+                let r = client.wallet_broadcast_with_wait(
+                    wallet,
+                    dry_run,
+                    pset,
+                    wait_confirmations,
+                    wait_timeout,
+                )?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::BroadcastBatch { wallet, pset } => {
+                let r = client.wallet_broadcast_batch(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::RebroadcastPending { wallet } => {
+                let r = client.wallet_rebroadcast_pending(wallet)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::Details { wallet } => {
                 let r = client.wallet_details(wallet)?;
                 serde_json::to_value(r)?
             }
-            WalletCommand::Combine { wallet, pset } => {
-                let r = client.wallet_combine(wallet, pset)?;
+            WalletCommand::Descriptor {
+                wallet,
+                include_internal,
+            } => {
+                let r = client.wallet_descriptor(wallet, include_internal)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SyncProgress { wallet } => {
+                let r = client.wallet_sync_progress(wallet)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::CancelSync { wallet } => {
+                let r = client.wallet_cancel_sync(wallet)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SetBackend {
+                wallet,
+                electrum_url,
+                tls,
+                cert_fingerprint,
+            } => {
+                let r = client.wallet_set_backend(wallet, electrum_url, tls, cert_fingerprint)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::Combine {
+                wallet,
+                pset,
+                pset_file,
+            } => {
+                let r = client.wallet_combine(wallet, pset, pset_file)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::MergeSignatures { pset } => {
+                let r = client.wallet_merge_signatures(pset)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::PsetDetails {
@@ -347,8 +748,67 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 let r = client.wallet_pset_details(wallet, pset, with_tickers)?;
                 serde_json::to_value(r)?
             }
-            WalletCommand::Utxos { wallet } => {
-                let r = client.wallet_utxos(wallet)?;
+            WalletCommand::SigningRequest { wallet, pset } => {
+                let r = client.wallet_signing_request(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::PrivacyAnalysis { wallet, pset } => {
+                let r = client.wallet_privacy_analysis(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::CheckPsetSpendable { wallet, pset } => {
+                let r = client.wallet_check_pset_spendable(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::MultisigFeeEstimate { wallet, pset } => {
+                let r = client.wallet_multisig_fee_estimate(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SessionCreate { wallet, pset } => {
+                let r = client.wallet_session_create(wallet, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SessionAdd { session_id, pset } => {
+                let r = client.wallet_session_add(session_id, pset)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::SessionGet { session_id } => {
+                let r = client.wallet_session_get(session_id)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::Utxos { wallet, format } => {
+                let r = client.wallet_utxos(wallet, Some(format.to_string()))?;
+                match r.core {
+                    Some(core) => serde_json::to_value(core)?,
+                    None => serde_json::to_value(r)?,
+                }
+            }
+            WalletCommand::UsedAddresses { wallet } => {
+                let r = client.wallet_used_addresses(wallet)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::DustReport { wallet, fee_rate } => {
+                let r = client.wallet_dust_report(wallet, fee_rate)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::DiscoverFunds { wallet, max_gap } => {
+                let r = client.wallet_discover_funds(wallet, max_gap)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::LockUtxo {
+                wallet,
+                outpoint,
+                expires_at,
+            } => {
+                let r = client.wallet_lock_utxo(wallet, outpoint, expires_at)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::UnlockUtxo { wallet, outpoint } => {
+                let r = client.wallet_unlock_utxo(wallet, outpoint)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::Confirmations { wallet, txid } => {
+                let r = client.wallet_confirmations(wallet, txid)?;
                 serde_json::to_value(r)?
             }
             WalletCommand::Txs {
@@ -358,6 +818,10 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 let r = client.wallet_txs(wallet, with_tickers)?;
                 serde_json::to_value(r)?
             }
+            WalletCommand::IssuanceTx { wallet, asset } => {
+                let r = client.wallet_issuance_tx(wallet, asset)?;
+                serde_json::to_value(r)?
+            }
             WalletCommand::Tx {
                 wallet,
                 txid,
@@ -366,6 +830,22 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 let r = client.wallet_tx(wallet, txid, from_explorer)?;
                 serde_json::to_value(r)?
             }
+            WalletCommand::DecodeDataOutputs { wallet, txid } => {
+                let r = client.wallet_decode_data_outputs(wallet, txid)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ExportWatchOnly { wallet } => {
+                let r = client.wallet_export_watch_only(wallet)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::ExportTo { wallet, target } => {
+                let r = client.wallet_export_to(wallet, target)?;
+                serde_json::to_value(r)?
+            }
+            WalletCommand::BlindingFactors { wallet, txid } => {
+                let r = client.wallet_blinding_factors(wallet, txid)?;
+                serde_json::to_value(r)?
+            }
             WalletCommand::SetTxMemo { wallet, txid, memo } => {
                 let r = client.wallet_set_tx_memo(wallet, txid, memo)?;
                 serde_json::to_value(r)?
@@ -402,6 +882,10 @@ pub fn inner_main(args: args::Cli) -> anyhow::Result<Value> {
                 let r = client.asset_details(asset)?;
                 serde_json::to_value(r)?
             }
+            AssetCommand::Metadata { asset } => {
+                let r = client.asset_metadata(asset)?;
+                serde_json::to_value(r)?
+            }
             AssetCommand::List => serde_json::to_value(client.asset_list()?)?,
             AssetCommand::Insert {
                 asset,
@@ -10,7 +10,7 @@ fn main() -> anyhow::Result<()> {
     let value = match inner_main(args) {
         Ok(value) => value,
         Err(e) => {
-            if let Some(Error::RpcError(e)) = e.downcast_ref::<Error>() {
+            if let Some(e) = e.downcast_ref::<Error>().and_then(Error::as_rpc_error) {
                 serde_json::to_value(e)?
             } else {
                 return Err(e);
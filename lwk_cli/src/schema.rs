@@ -29,7 +29,14 @@ pub(crate) fn schema(a: SchemaArgs, client: Client) -> Result<Value, anyhow::Err
 impl From<ServerSubCommandsEnum> for Method {
     fn from(value: ServerSubCommandsEnum) -> Self {
         match value {
+            ServerSubCommandsEnum::Health => Method::Health,
+            ServerSubCommandsEnum::PingBackend => Method::PingBackend,
+            ServerSubCommandsEnum::SetDefaults => Method::SetDefaults,
+            ServerSubCommandsEnum::GetDefaults => Method::GetDefaults,
+            ServerSubCommandsEnum::GetConfig => Method::GetConfig,
+            ServerSubCommandsEnum::ListVariants => Method::ListVariants,
             ServerSubCommandsEnum::Scan => Method::Scan,
+            ServerSubCommandsEnum::UnloadAll => Method::UnloadAll,
             ServerSubCommandsEnum::Stop => Method::Stop,
         }
     }
@@ -42,17 +49,62 @@ impl From<WalletSubCommandsEnum> for Method {
             WalletSubCommandsEnum::Unload => Method::WalletUnload,
             WalletSubCommandsEnum::List => Method::WalletList,
             WalletSubCommandsEnum::Address => Method::WalletAddress,
+            WalletSubCommandsEnum::ConfirmAddress => Method::WalletConfirmAddress,
+            WalletSubCommandsEnum::VerifyDeviceAddress => Method::VerifyDeviceAddress,
             WalletSubCommandsEnum::Balance => Method::WalletBalance,
+            WalletSubCommandsEnum::BalanceAll => Method::WalletBalanceAll,
+            WalletSubCommandsEnum::ConfirmedBalance => Method::WalletConfirmedBalance,
             WalletSubCommandsEnum::Send => Method::WalletSendMany,
+            WalletSubCommandsEnum::LocalMultisigSend => Method::WalletLocalMultisigSend,
+            WalletSubCommandsEnum::Consolidate => Method::WalletConsolidate,
+            WalletSubCommandsEnum::MaxSpendable => Method::WalletMaxSpendable,
+            WalletSubCommandsEnum::SweepKey => Method::SweepKey,
+            WalletSubCommandsEnum::ValidateAddressees => Method::WalletValidateAddressees,
+            WalletSubCommandsEnum::AddToPset => Method::WalletAddToPset,
+            WalletSubCommandsEnum::SwapPropose => Method::WalletSwapPropose,
+            WalletSubCommandsEnum::SwapAccept => Method::WalletSwapAccept,
             WalletSubCommandsEnum::Issue => Method::WalletIssue,
             WalletSubCommandsEnum::Reissue => Method::WalletReissue,
             WalletSubCommandsEnum::MultisigDesc => Method::WalletMultisigDescriptor,
+            WalletSubCommandsEnum::CompilePolicy => Method::WalletCompilePolicy,
+            WalletSubCommandsEnum::DescriptorChecksum => Method::WalletDescriptorChecksum,
+            WalletSubCommandsEnum::CheckNetwork => Method::WalletCheckNetwork,
+            WalletSubCommandsEnum::ConvertBlindingKey => Method::WalletConvertBlindingKey,
+            WalletSubCommandsEnum::ConvertPsetVersion => Method::WalletConvertPsetVersion,
+            WalletSubCommandsEnum::StripSignatures => Method::WalletStripSignatures,
+            WalletSubCommandsEnum::PsetFee => Method::WalletPsetFee,
+            WalletSubCommandsEnum::CheckSpendPath => Method::WalletCheckSpendPath,
             WalletSubCommandsEnum::Broadcast => Method::WalletBroadcast,
+            WalletSubCommandsEnum::BroadcastBatch => Method::WalletBroadcastBatch,
+            WalletSubCommandsEnum::RebroadcastPending => Method::WalletRebroadcastPending,
             WalletSubCommandsEnum::Details => Method::WalletDetails,
+            WalletSubCommandsEnum::Descriptor => Method::WalletDescriptor,
+            WalletSubCommandsEnum::SyncProgress => Method::WalletSyncProgress,
+            WalletSubCommandsEnum::CancelSync => Method::WalletCancelSync,
+            WalletSubCommandsEnum::SetBackend => Method::WalletSetBackend,
             WalletSubCommandsEnum::Combine => Method::WalletCombine,
+            WalletSubCommandsEnum::MergeSignatures => Method::WalletMergeSignatures,
             WalletSubCommandsEnum::PsetDetails => Method::WalletPsetDetails,
+            WalletSubCommandsEnum::SigningRequest => Method::WalletSigningRequest,
+            WalletSubCommandsEnum::PrivacyAnalysis => Method::WalletPrivacyAnalysis,
+            WalletSubCommandsEnum::CheckPsetSpendable => Method::WalletCheckPsetSpendable,
+            WalletSubCommandsEnum::MultisigFeeEstimate => Method::WalletMultisigFeeEstimate,
+            WalletSubCommandsEnum::SessionCreate => Method::WalletSessionCreate,
+            WalletSubCommandsEnum::SessionAdd => Method::WalletSessionAdd,
+            WalletSubCommandsEnum::SessionGet => Method::WalletSessionGet,
             WalletSubCommandsEnum::Utxos => Method::WalletUtxos,
+            WalletSubCommandsEnum::UsedAddresses => Method::WalletUsedAddresses,
+            WalletSubCommandsEnum::DustReport => Method::WalletDustReport,
+            WalletSubCommandsEnum::DiscoverFunds => Method::WalletDiscoverFunds,
+            WalletSubCommandsEnum::Confirmations => Method::WalletConfirmations,
+            WalletSubCommandsEnum::LockUtxo => Method::WalletLockUtxo,
+            WalletSubCommandsEnum::UnlockUtxo => Method::WalletUnlockUtxo,
             WalletSubCommandsEnum::Txs => Method::WalletTxs,
+            WalletSubCommandsEnum::IssuanceTx => Method::WalletIssuanceTx,
+            WalletSubCommandsEnum::DecodeDataOutputs => Method::WalletDecodeDataOutputs,
+            WalletSubCommandsEnum::BlindingFactors => Method::WalletBlindingFactors,
+            WalletSubCommandsEnum::ExportWatchOnly => Method::WalletExportWatchOnly,
+            WalletSubCommandsEnum::ExportTo => Method::WalletExportTo,
             WalletSubCommandsEnum::SetTxMemo => Method::WalletSetTxMemo,
             WalletSubCommandsEnum::SetAddrMemo => Method::WalletSetAddrMemo,
         }
@@ -63,15 +115,22 @@ impl From<SignerSubCommandsEnum> for Method {
     fn from(value: SignerSubCommandsEnum) -> Self {
         match value {
             SignerSubCommandsEnum::Generate => Method::SignerGenerate,
+            SignerSubCommandsEnum::ValidateMnemonic => Method::SignerValidateMnemonic,
             SignerSubCommandsEnum::JadeId => Method::SignerJadeId,
             SignerSubCommandsEnum::LoadSoftware => Method::SignerLoadSoftware,
             SignerSubCommandsEnum::LoadJade => Method::SignerLoadJade,
             SignerSubCommandsEnum::LoadExternal => Method::SignerLoadExternal,
+            SignerSubCommandsEnum::LoadMany => Method::SignerLoadMany,
             SignerSubCommandsEnum::Unload => Method::SignerUnload,
+            SignerSubCommandsEnum::Replace => Method::SignerReplace,
+            SignerSubCommandsEnum::MatchesWallet => Method::SignerMatchesWallet,
             SignerSubCommandsEnum::Details => Method::SignerDetails,
             SignerSubCommandsEnum::List => Method::SignerList,
             SignerSubCommandsEnum::Sign => Method::SignerSign,
+            SignerSubCommandsEnum::CanSign => Method::SignerCanSign,
+            SignerSubCommandsEnum::Capabilities => Method::SignerCapabilities,
             SignerSubCommandsEnum::SinglesigDesc => Method::SignerSinglesigDescriptor,
+            SignerSubCommandsEnum::SinglesigDescSet => Method::SignerSinglesigDescriptorSet,
             SignerSubCommandsEnum::Xpub => Method::SignerXpub,
         }
     }
@@ -82,6 +141,7 @@ impl From<AssetSubCommandsEnum> for Method {
         match value {
             AssetSubCommandsEnum::Contract => Method::AssetContract,
             AssetSubCommandsEnum::Details => Method::AssetDetails,
+            AssetSubCommandsEnum::Metadata => Method::AssetMetadata,
             AssetSubCommandsEnum::List => Method::AssetList,
             AssetSubCommandsEnum::Insert => Method::AssetInsert,
             AssetSubCommandsEnum::Remove => Method::AssetRemove,
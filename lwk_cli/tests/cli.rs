@@ -8,8 +8,11 @@ use std::{
 };
 
 use clap::{Parser, ValueEnum};
+use elements::confidential::{self, Asset, AssetBlindingFactor, ValueBlindingFactor};
 use elements::encode::serialize;
-use elements::hex::ToHex;
+use elements::hashes::Hash;
+use elements::hex::{FromHex, ToHex};
+use elements::issuance::ContractHash;
 use elements::{pset::PartiallySignedTransaction, Address};
 use lwk_containers::{testcontainers::clients, JadeEmulator, EMULATOR_PORT};
 use serde_json::Value;
@@ -272,12 +275,19 @@ fn complete(cli: &str, wallet: &str, pset: &str, signers: &[&str]) -> String {
     let pset_combined = get_str(&r, "pset");
     // In general PSETs are not equal since order of keys and signatures might differ
 
-    sh(&format!(
+    let r = sh(&format!(
         "{cli} wallet broadcast -w {wallet} --pset {pset_serial} --dry-run"
     ));
+    let fee = r.get("fee").unwrap().as_u64().unwrap();
+    assert!(fee > 0);
+    assert_eq!(get_len(&r, "warnings"), 0);
     let r = sh(&format!(
         "{cli} wallet broadcast -w {wallet} --pset {pset_combined}"
     ));
+    assert_eq!(r.get("fee").unwrap().as_u64(), Some(fee));
+    let vsize = r.get("vsize").unwrap().as_u64().unwrap();
+    let fee_rate = r.get("fee_rate").unwrap().as_f64().unwrap();
+    assert!((fee_rate - fee as f64 * 1000.0 / vsize as f64).abs() < 0.01);
     let txid = get_str(&r, "txid");
     wait_tx(cli, wallet, txid);
     txid.to_string()
@@ -450,6 +460,53 @@ fn test_start_stop_persist() {
     t.join().unwrap();
 }
 
+#[test]
+fn test_auth_tokens() {
+    let (t, _tmp, cli, params, _server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+
+    let t = {
+        let cli = cli.clone();
+        let params = params.clone();
+        std::thread::spawn(move || {
+            sh(&format!(
+                "{cli} --auth-token f server start --allow-token r:readonly --allow-token f:full --allow-token w:readonly:w2 {params}"
+            ));
+        })
+    };
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+
+    let err = sh_err(&format!("{cli} wallet list"));
+    assert!(err.contains("Missing or unknown auth token"));
+    let err = sh_err(&format!("{cli} --auth-token wrong wallet list"));
+    assert!(err.contains("Missing or unknown auth token"));
+
+    // persisted state is loaded even if auth is required
+    let readonly = format!("{cli} --auth-token r");
+    let r = sh(&format!("{readonly} wallet list"));
+    assert_eq!(get_len(&r, "wallets"), 1);
+    sh(&format!("{readonly} wallet balance --wallet w1"));
+
+    let recipient = "el1qqdtwgfchn6rtl8peyw6afhrkpphqlyxls04vlwycez2fz6l7chlhxr8wtvy9s2v34f9sk0e2g058p0dwdp9kj38296xw5ur70:2:5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let err = sh_err(&format!(
+        "{readonly} wallet send --wallet w1 --recipient {recipient}"
+    ));
+    assert!(err.contains("'wallet_send_many' is not permitted"));
+    let err = sh_err(&format!("{readonly} server stop"));
+    assert!(err.contains("'stop' is not permitted"));
+
+    let scoped = format!("{cli} --auth-token w");
+    let err = sh_err(&format!("{scoped} wallet balance --wallet w1"));
+    assert!(err.contains("'wallet_balance' is not permitted on wallet 'w1'"));
+
+    sh(&format!("{cli} --auth-token f server stop"));
+    t.join().unwrap();
+}
+
 #[test]
 fn test_signer_load_unload_list() {
     let (t, _tmp, cli, _params, _server, _) = setup_cli(false);
@@ -468,6 +525,25 @@ fn test_signer_load_unload_list() {
         "{cli} signer load-software --persist true --mnemonic '{m2}' --signer s1"
     ));
     assert!(err.contains("Signer 's1' is already loaded"));
+    let fingerprint =
+        get_str(&sh(&format!("{cli} signer details -s s1")), "fingerprint").to_string();
+    assert!(err.contains(&format!("with fingerprint {fingerprint}")));
+
+    // Same mnemonic, same name
+    let err = sh_err(&format!(
+        "{cli} signer load-software --persist true --mnemonic '{m1}' --signer s1"
+    ));
+    assert!(err.contains(&format!("with fingerprint {fingerprint}")));
+
+    // Same name, different mnemonic, forced
+    sh(&format!(
+        "{cli} signer load-software --persist true --mnemonic '{m2}' --signer s1 --force"
+    ));
+    let r = sh(&format!("{cli} signer details -s s1"));
+    assert_eq!(get_str(&r, "mnemonic"), m2);
+    sh(&format!(
+        "{cli} signer load-software --persist true --mnemonic '{m1}' --signer s1 --force"
+    ));
 
     // Same mnemonic, different name
     let err = sh_err(&format!(
@@ -524,6 +600,431 @@ fn test_signer_external() {
     t.join().unwrap();
 }
 
+#[test]
+fn test_signer_replace() {
+    let (t, _tmp, cli, _params, _server, _) = setup_cli(false);
+
+    let r = sh(&format!("{cli} signer generate"));
+    let mnemonic = get_str(&r, "mnemonic").to_string();
+    sh(&format!(
+        "{cli} signer load-software --persist true --mnemonic \"{mnemonic}\" --signer tmp"
+    ));
+    let fingerprint =
+        get_str(&sh(&format!("{cli} signer details -s tmp")), "fingerprint").to_string();
+    singlesig_wallet(&cli, "w1", "tmp", "slip77", "wpkh");
+    sh(&format!("{cli} signer unload --signer tmp"));
+
+    // The wallet references the external signer
+    sh(&format!(
+        "{cli} signer load-external --fingerprint {fingerprint} --signer ext"
+    ));
+    let r = sh(&format!("{cli} wallet details --wallet w1"));
+    let signers = r.get("signers").unwrap().as_array().unwrap();
+    assert_eq!(signers[0].get("name").unwrap().as_str().unwrap(), "ext");
+
+    // Load the actual signer and promote it
+    sh(&format!(
+        "{cli} signer load-software --persist true --mnemonic \"{mnemonic}\" --signer s1"
+    ));
+    let err = sh_err(&format!("{cli} signer replace --signer ext"));
+    assert!(err.contains("Invalid operation for external signer"));
+    let r = sh(&format!("{cli} signer replace --signer s1"));
+    assert_eq!(get_str(r.get("signer").unwrap(), "name"), "s1");
+    assert_eq!(r.get("replaced").unwrap(), &serde_json::json!(["ext"]));
+
+    let r = sh(&format!("{cli} wallet details --wallet w1"));
+    let signers = r.get("signers").unwrap().as_array().unwrap();
+    assert_eq!(signers[0].get("name").unwrap().as_str().unwrap(), "s1");
+    let r = sh(&format!("{cli} signer list"));
+    assert_eq!(get_len(&r, "signers"), 1);
+
+    let err = sh_err(&format!("{cli} signer replace --signer s1"));
+    assert!(err.contains("No external signer"));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_signer_load_many() {
+    let (t, _tmp, cli, _params, _server, _) = setup_cli(false);
+
+    let r = sh(&format!("{cli} signer generate"));
+    let mnemonic = get_str(&r, "mnemonic").to_string();
+    let s1 =
+        format!(r#"{{"kind":"software","name":"s1","mnemonic":"{mnemonic}","persist":false}}"#);
+    let e1 = r#"{"kind":"external","name":"e1","fingerprint":"11111111"}"#;
+    let r = sh(&format!(
+        "{cli} signer load-many --signer '{s1}' --signer '{e1}' --atomic"
+    ));
+    assert_eq!(r.get("loaded").unwrap().as_u64().unwrap(), 2);
+    let r = sh(&format!("{cli} signer list"));
+    assert_eq!(get_len(&r, "signers"), 2);
+
+    let err = sh_err(&format!("{cli} signer load-many --signer '{{}}'"));
+    assert!(err.contains("Invalid signer 0"), "{err}");
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_signer_matches_wallet() {
+    let (t, _tmp, cli, _params, _server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    sw_signer(&cli, "s2");
+    sw_signer(&cli, "s3");
+    multisig_wallet(&cli, "multi", 2, &["s1", "s2"], "slip77");
+
+    for (signer, expected) in [("s1", true), ("s2", true), ("s3", false)] {
+        let r = sh(&format!(
+            "{cli} signer matches-wallet --signer {signer} --wallet multi"
+        ));
+        assert_eq!(r.get("matches").unwrap().as_bool().unwrap(), expected);
+    }
+
+    let err = sh_err(&format!("{cli} signer matches-wallet -s s1 -w x"));
+    assert!(err.contains("Wallet 'x' does not exist"));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_signer_can_sign() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    sw_signer(&cli, "s2");
+    sw_signer(&cli, "s3");
+    multisig_wallet(&cli, "multi", 2, &["s1", "s2"], "slip77");
+    fund(&server, &cli, "multi", 1_000_000);
+
+    let node_address = server.node_getnewaddress();
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let r = sh(&format!(
+        "{cli} wallet send --wallet multi --recipient {node_address}:1000:{policy_asset}"
+    ));
+    let pset = get_str(&r, "pset").to_string();
+
+    let can_sign = |signer: &str, pset: &str| {
+        sh(&format!(
+            "{cli} signer can-sign -s {signer} -w multi --pset {pset}"
+        ))
+    };
+    let r = can_sign("s1", &pset);
+    assert!(r.get("available").unwrap().as_bool().unwrap());
+    assert!(r.get("is_wallet_signer").unwrap().as_bool().unwrap());
+    assert!(r.get("can_sign").unwrap().as_bool().unwrap());
+    assert_eq!(get_len(&r, "inputs_to_sign"), 1);
+    assert_eq!(get_len(&r, "inputs_signed"), 0);
+
+    let r = sh(&format!("{cli} signer sign --signer s1 --pset {pset}"));
+    let signed = get_str(&r, "pset").to_string();
+    let r = can_sign("s1", &signed);
+    assert!(!r.get("can_sign").unwrap().as_bool().unwrap());
+    assert_eq!(get_len(&r, "inputs_to_sign"), 0);
+    assert_eq!(get_len(&r, "inputs_signed"), 1);
+    let r = can_sign("s2", &signed);
+    assert!(r.get("can_sign").unwrap().as_bool().unwrap());
+
+    let r = can_sign("s3", &pset);
+    assert!(!r.get("is_wallet_signer").unwrap().as_bool().unwrap());
+    assert!(!r.get("can_sign").unwrap().as_bool().unwrap());
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_convert_pset_version() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let node_address = server.node_getnewaddress();
+    let r = sh(&format!(
+        "{cli} wallet send --wallet w1 --recipient {node_address}:1000:5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225"
+    ));
+    let pset = get_str(&r, "pset").to_string();
+
+    let r = sh(&format!("{cli} wallet convert-pset-version -p {pset}"));
+    assert_eq!(r.get("from_version").unwrap().as_u64(), Some(2));
+    assert_eq!(r.get("version").unwrap().as_u64(), Some(2));
+    let converted = PartiallySignedTransaction::from_str(get_str(&r, "pset")).unwrap();
+    assert_eq!(
+        converted,
+        PartiallySignedTransaction::from_str(&pset).unwrap()
+    );
+
+    let err = sh_err(&format!(
+        "{cli} wallet convert-pset-version -p {pset} --version 0"
+    ));
+    assert!(err.contains("PSET version 0 is not supported"));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_confirmed_balance() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+
+    // Receive without confirming
+    let addr = Address::from_str(&address(&cli, "w1")).unwrap();
+    let txid = server.node_sendtoaddress(&addr, 10_000, None).to_string();
+    wait_tx(&cli, "w1", &txid);
+
+    let r = sh(&format!("{cli} wallet confirmed-balance -w w1"));
+    let b = r.get("balance").unwrap().get(policy_asset).unwrap();
+    assert_eq!(b.get("confirmed").unwrap().as_u64(), Some(1_000_000));
+    assert_eq!(b.get("pending").unwrap().as_u64(), Some(10_000));
+
+    // The funding output has fewer confirmations than requested
+    let r = sh(&format!(
+        "{cli} wallet confirmed-balance -w w1 --min-confirmations 1000"
+    ));
+    let b = r.get("balance").unwrap().get(policy_asset).unwrap();
+    assert_eq!(b.get("confirmed").unwrap().as_u64(), Some(0));
+    assert_eq!(b.get("pending").unwrap().as_u64(), Some(1_010_000));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_sign_inputs() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    fund(&server, &cli, "w1", 1_000_000);
+
+    // Spend both outputs
+    let node_address = server.node_getnewaddress();
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let r = sh(&format!(
+        "{cli} wallet send --wallet w1 --recipient {node_address}:1500000:{policy_asset}"
+    ));
+    let pset = get_str(&r, "pset").to_string();
+
+    let r = sh(&format!("{cli} signer sign -s s1 --pset {pset} --input 1"));
+    let pset_1 = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} wallet pset-details -w w1 -p {pset_1}"));
+    let inputs = r.get("signing_progress").unwrap().get("inputs").unwrap();
+    let present = |vin: usize| inputs[vin].get("signatures_present").unwrap().as_u64();
+    assert_eq!(present(0), Some(0));
+    assert_eq!(present(1), Some(1));
+
+    let err = sh_err(&format!("{cli} signer sign -s s1 --pset {pset} --input 2"));
+    assert!(err.contains("input 2 is out of range"));
+
+    let r = sh(&format!(
+        "{cli} signer sign -s s1 --pset {pset_1} --input 0"
+    ));
+    let pset_s = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} wallet pset-details -w w1 -p {pset_s}"));
+    let progress = r.get("signing_progress").unwrap();
+    assert_eq!(progress.get("is_complete").unwrap().as_bool(), Some(true));
+    let r = sh(&format!("{cli} wallet broadcast -w w1 --pset {pset_s}"));
+    assert!(r.get("txid").is_some());
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_local_multisig_send() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    for signer in ["s1", "s2", "s3", "other"] {
+        sw_signer(&cli, signer);
+    }
+    multisig_wallet(&cli, "multi", 2, &["s1", "s2", "s3"], "slip77");
+    fund(&server, &cli, "multi", 1_000_000);
+
+    let node_address = server.node_getnewaddress();
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let send = format!(
+        "{cli} wallet local-multisig-send -w multi --recipient {node_address}:1000:{policy_asset}"
+    );
+
+    let err = sh_err(&format!("{send} --signer s1"));
+    assert!(
+        err.contains("1 distinct signers given, wallet 'multi' needs 2"),
+        "{err}"
+    );
+    let err = sh_err(&format!("{send} --signer s1 --signer s1"));
+    assert!(err.contains("1 distinct signers given"), "{err}");
+    let err = sh_err(&format!("{send} --signer s1 --signer other"));
+    assert!(
+        err.contains("Signer 'other' is not a signer of wallet 'multi'"),
+        "{err}"
+    );
+
+    let r = sh(&format!("{send} --signer s1 --signer s3"));
+    let tx: elements::Transaction =
+        elements::encode::deserialize(&Vec::<u8>::from_hex(get_str(&r, "tx")).unwrap()).unwrap();
+    assert_eq!(tx.txid().to_string(), get_str(&r, "txid"));
+    assert!(tx
+        .input
+        .iter()
+        .all(|i| !i.witness.script_witness.is_empty()));
+    let fee = r.get("fee").unwrap().as_u64().unwrap();
+    assert!(fee > 0);
+    assert_eq!(tx.fee_in(policy_asset.parse().unwrap()), fee);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_send_privacy() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+
+    let node_address = server.node_getnewaddress();
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+    let r = sh(&format!("{cli} wallet send --wallet w1 {recipient}"));
+    assert_eq!(get_len(&r, "change"), 1);
+    let fee = r.get("fee").unwrap().as_u64().unwrap();
+
+    // The change is split in two outputs, paying a higher fee
+    let r = sh(&format!(
+        "{cli} wallet send --wallet w1 {recipient} --privacy"
+    ));
+    assert_eq!(get_len(&r, "change"), 2);
+    let privacy_fee = r.get("fee").unwrap().as_u64().unwrap();
+    assert!(privacy_fee > fee);
+    let pset = get_str(&r, "pset").to_string();
+
+    let r = sh(&format!("{cli} signer sign -s s1 --pset {pset}"));
+    let pset = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} wallet broadcast -w w1 --pset {pset}"));
+    let txid = get_str(&r, "txid").to_string();
+    wait_tx(&cli, "w1", &txid);
+
+    let r = sh(&format!("{cli} wallet balance -w w1"));
+    let balance = r.get("balance").unwrap().get(policy_asset).unwrap();
+    assert_eq!(balance.as_u64(), Some(1_000_000 - 1_000 - privacy_fee));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_check_spend_path() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    sw_signer(&cli, "s2");
+    multisig_wallet(&cli, "multi", 2, &["s1", "s2"], "slip77");
+    fund(&server, &cli, "multi", 1_000_000);
+
+    let node_address = server.node_getnewaddress();
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let r = sh(&format!(
+        "{cli} wallet send --wallet multi --recipient {node_address}:1000:{policy_asset}"
+    ));
+    let pset = get_str(&r, "pset").to_string();
+
+    let check = |pset: &str| sh(&format!("{cli} wallet check-spend-path -w multi -p {pset}"));
+    let r = check(&pset);
+    assert_eq!(r.get("is_satisfied").unwrap().as_bool(), Some(false));
+    let input = &r.get("inputs").unwrap().as_array().unwrap()[0];
+    let branches = input.get("branches").unwrap().as_array().unwrap();
+    assert_eq!(branches.len(), 1);
+    let policy = branches[0].get("policy").unwrap().as_str().unwrap();
+    assert!(policy.starts_with("and(pk("), "{policy}");
+    assert!(input.get("satisfied_branch").unwrap().is_null());
+
+    // A single signature is not enough for the 2-of-2
+    let r = sh(&format!("{cli} signer sign -s s1 --pset {pset}"));
+    let pset = get_str(&r, "pset").to_string();
+    let r = check(&pset);
+    assert_eq!(r.get("is_satisfied").unwrap().as_bool(), Some(false));
+
+    let r = sh(&format!("{cli} signer sign -s s2 --pset {pset}"));
+    let pset = get_str(&r, "pset").to_string();
+    let r = check(&pset);
+    assert_eq!(r.get("is_satisfied").unwrap().as_bool(), Some(true));
+    let input = &r.get("inputs").unwrap().as_array().unwrap()[0];
+    assert_eq!(input.get("satisfied_branch").unwrap().as_u64(), Some(0));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_export_watch_only() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    let r = sh(&format!("{cli} wallet export-watch-only -w w1"));
+    assert!(r.get("birthday_height").unwrap().is_null());
+
+    fund(&server, &cli, "w1", 1_000_000);
+    let r = sh(&format!("{cli} wallet export-watch-only -w w1"));
+    assert_eq!(get_str(&r, "network"), "liquid-regtest");
+    assert!(get_str(&r, "descriptor").starts_with("elwpkh("));
+    assert!(r.get("birthday_height").unwrap().as_u64().unwrap() > 0);
+    let blinding_key = get_str(&r, "blinding_key");
+    assert!(blinding_key.starts_with("slip77("));
+    let confidential = get_str(&r, "confidential_descriptor");
+    assert!(confidential.contains(blinding_key));
+    let details = sh(&format!("{cli} wallet details -w w1"));
+    assert_eq!(confidential, get_str(&details, "descriptor"));
+    let r = sh(&format!("{cli} wallet export-to -w w1 -t green"));
+    assert!(get_str(&r, "content").contains(blinding_key));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_address_reuse_warning() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let r = sh(&format!("{cli} wallet details -w w1"));
+    assert!(!get_str(&r, "warnings").contains("received more than once"));
+
+    // Receive twice on the same address
+    let r = sh(&format!("{cli} wallet address -w w1 --index 5"));
+    let address = get_str(&r, "address").to_string();
+    let addr = Address::from_str(&address).unwrap();
+    server.node_sendtoaddress(&addr, 10_000, None);
+    let txid = server.node_sendtoaddress(&addr, 20_000, None).to_string();
+    wait_tx(&cli, "w1", &txid);
+
+    let r = sh(&format!("{cli} wallet details -w w1"));
+    let warnings = get_str(&r, "warnings");
+    assert!(
+        warnings.contains(&format!(
+            "1 addresses received more than once ({address} 2 times)"
+        )),
+        "{warnings}"
+    );
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
 #[test]
 fn test_wallet_load_unload_list() {
     let (t, _tmp, cli, _params, _server, _) = setup_cli(false);
@@ -694,15 +1195,21 @@ fn test_wallet_details() {
     let xpub2 = get_str(&r, "keyorigin_xpub");
     let r = sh(&format!("{cli} wallet multisig-desc --descriptor-blinding-key slip77-rand --kind wsh --threshold 2 --keyorigin-xpub {xpub1} --keyorigin-xpub {xpub2}"));
     let desc_ms = get_str(&r, "descriptor");
+    assert!(r.get("jade_multisig_name").is_none());
     sh(&format!("{cli} wallet load --wallet ms -d {desc_ms}"));
 
+    let r = sh(&format!("{cli} wallet multisig-desc --descriptor-blinding-key slip77-rand --kind wsh --threshold 2 --keyorigin-xpub {xpub1} --keyorigin-xpub {xpub2} --with-jade-name"));
+    let jade_name = get_str(&r, "jade_multisig_name");
+    assert_eq!(jade_name.len(), 15);
+
     let err = sh_err(&format!("{cli} wallet multisig-desc --descriptor-blinding-key slip77 --kind wsh --threshold 2 --keyorigin-xpub {xpub1} --keyorigin-xpub {xpub2}"));
     let exp_err = "Deterministic slip77 key not supported in multisig descriptor generation";
     assert!(err.contains(exp_err));
 
-    // Multi sig wallet, same signers
-    let r = sh(&format!("{cli} wallet multisig-desc --descriptor-blinding-key slip77-rand --kind wsh --threshold 2 --keyorigin-xpub {xpub1} --keyorigin-xpub {xpub1}"));
-    let desc_ms_same_signers = get_str(&r, "descriptor");
+    // Multi sig wallet, same signers, refused by multisig-desc but can still be loaded
+    let err = sh_err(&format!("{cli} wallet multisig-desc --descriptor-blinding-key slip77-rand --kind wsh --threshold 2 --keyorigin-xpub {xpub1} --keyorigin-xpub {xpub1}"));
+    assert!(err.contains("Duplicate xpub"));
+    let desc_ms_same_signers = remove_checksum(desc_ms).replace(xpub2, xpub1);
     sh(&format!(
         "{cli} wallet load --wallet ms_same_signers -d {desc_ms_same_signers}"
     ));
@@ -715,6 +1222,9 @@ fn test_wallet_details() {
     let signers = r.get("signers").unwrap().as_array().unwrap();
     assert_eq!(signers.len(), 1);
     assert_eq!(get_str(&signers[0], "name"), "s1");
+    let keyorigin_xpub = keyorigin(&cli, "s1", "bip84");
+    assert_eq!(get_str(&signers[0], "keyorigin_xpub"), keyorigin_xpub);
+    assert!(keyorigin_xpub.ends_with(get_str(&signers[0], "xpub")));
 
     let r = sh(&format!("{cli} wallet details --wallet sssh"));
     assert_eq!(get_desc(&r), remove_checksum(desc_sssh));
@@ -732,6 +1242,9 @@ fn test_wallet_details() {
     assert_eq!(signers.len(), 2);
     assert_eq!(get_str(&signers[0], "name"), "s1");
     assert_eq!(get_str(&signers[1], "name"), "s2");
+    // The keys are enough to generate the same descriptor again
+    assert_eq!(get_str(&signers[0], "keyorigin_xpub"), xpub1);
+    assert_eq!(get_str(&signers[1], "keyorigin_xpub"), xpub2);
 
     sh(&format!("{cli} signer unload --signer s2"));
     let r = sh(&format!("{cli} wallet details --wallet ms"));
@@ -757,7 +1270,7 @@ fn test_wallet_details() {
 
 #[test]
 fn test_broadcast() {
-    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+    let (t, _tmp, cli, params, server, _) = setup_cli(false);
 
     sw_signer(&cli, "s1");
     singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
@@ -769,7 +1282,94 @@ fn test_broadcast() {
     send(&cli, "w1", &addr, policy_asset, 1000, &["s1"]);
     assert!(1_000_000 > get_balance(&cli, "w1", policy_asset));
 
-    sh(&format!("{cli} server stop"));
+    // Broadcasting the same transaction twice is not an error
+    let recipient = format!("--recipient {addr}:1000:{policy_asset}");
+    let r = sh(&format!("{cli} wallet send --wallet w1 {recipient}"));
+    let pset = get_str(&r, "pset").to_string();
+
+    // The signing request contains what an offline signer needs to verify the PSET
+    let r = sh(&format!("{cli} wallet signing-request -w w1 -p {pset}"));
+    let fingerprint =
+        get_str(&sh(&format!("{cli} signer details -s s1")), "fingerprint").to_string();
+    let descriptor = get_str(&sh(&format!("{cli} wallet details -w w1")), "descriptor").to_string();
+    assert_eq!(get_str(&r, "descriptor"), descriptor);
+    assert_eq!(get_str(&r, "network"), "liquid-regtest");
+    assert_eq!(
+        r.get("fingerprints").unwrap(),
+        &serde_json::json!([fingerprint])
+    );
+    assert_eq!(
+        r.get("missing_signatures_from").unwrap(),
+        &serde_json::json!([fingerprint])
+    );
+    let outputs = r.get("outputs").unwrap().as_array().unwrap();
+    let recipient = outputs
+        .iter()
+        .find(|o| o.get("address").unwrap() == addr.as_str())
+        .unwrap();
+    assert_eq!(recipient.get("satoshi").unwrap().as_u64().unwrap(), 1000);
+    assert!(!recipient.get("is_mine").unwrap().as_bool().unwrap());
+    assert!(outputs
+        .iter()
+        .any(|o| o.get("is_mine").unwrap().as_bool().unwrap()));
+    let fee = outputs
+        .iter()
+        .find(|o| o.get("is_fee").unwrap().as_bool().unwrap())
+        .unwrap();
+    assert_eq!(fee.get("satoshi").unwrap(), r.get("fee").unwrap());
+
+    let r = sh(&format!("{cli} signer sign -s s1 --pset {pset}"));
+    let pset = get_str(&r, "pset");
+    let r = sh(&format!("{cli} wallet broadcast -w w1 --pset {pset}"));
+    assert!(!r.get("already_known").unwrap().as_bool().unwrap());
+    let txid = get_str(&r, "txid").to_string();
+    // By default broadcasting it again fails
+    let err = sh_err(&format!("{cli} wallet broadcast -w w1 --pset {pset}"));
+    assert!(err.contains("txn-already"), "{err}");
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+    let t = {
+        let cli = cli.clone();
+        let params = params.clone();
+        std::thread::spawn(move || {
+            sh(&format!(
+                "{cli} server start --scanning-interval 1 --already-known-is-success {params}"
+            ));
+        })
+    };
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+
+    let r = sh(&format!("{cli} wallet broadcast -w w1 --pset {pset}"));
+    assert!(r.get("already_known").unwrap().as_bool().unwrap());
+    assert_eq!(get_str(&r, "txid"), txid);
+    assert!(r.get("confirmation_height").unwrap().is_null());
+
+    // Without blocks the confirmation is not reached in time
+    let r = sh(&format!(
+        "{cli} wallet broadcast -w w1 --pset {pset} --wait-confirmations 1 --wait-timeout 1"
+    ));
+    assert_eq!(get_str(&r, "txid"), txid);
+    assert!(r.get("wait_timed_out").unwrap().as_bool().unwrap());
+    assert!(r.get("confirmation_height").unwrap().is_null());
+
+    server.generate(1);
+    let r = sh(&format!(
+        "{cli} wallet broadcast -w w1 --pset {pset} --wait-confirmations 1"
+    ));
+    assert_eq!(get_str(&r, "txid"), txid);
+    assert!(!r.get("wait_timed_out").unwrap().as_bool().unwrap());
+    assert!(r.get("confirmation_height").unwrap().as_u64().unwrap() > 0);
+
+    // Pointing the wallet to the same server keeps its data
+    let electrum_url = &server.electrs.electrum_url;
+    let r = sh(&format!(
+        "{cli} wallet set-backend -w w1 --electrum-url {electrum_url}"
+    ));
+    assert!(r.get("tip_height").unwrap().as_u64().unwrap() > 0);
+    assert!(1_000_000 > get_balance(&cli, "w1", policy_asset));
+
+    sh(&format!("{cli} server stop"));
     t.join().unwrap();
 }
 
@@ -786,8 +1386,12 @@ fn test_issue() {
     let r = sh(&format!(
         "{cli} wallet issue --wallet w1 --satoshi-asset 1000 --satoshi-token 1 --contract '{contract}'"
     ));
+    let contract_hash = get_str(&r, "contract_hash").to_string();
     let pset = get_str(&r, "pset");
     let pset_unsigned: PartiallySignedTransaction = pset.parse().unwrap();
+    let contract_hash_committed =
+        ContractHash::from_byte_array(pset_unsigned.inputs()[0].issuance_asset_entropy.unwrap());
+    assert_eq!(contract_hash, contract_hash_committed.to_string());
 
     let r = sh(&format!("{cli} wallet pset-details --wallet w1 -p {pset}"));
     assert!(get_str(&r, "warnings").is_empty());
@@ -804,6 +1408,11 @@ fn test_issue() {
     let token_sats = issuance.get("token_satoshi").unwrap().as_u64().unwrap();
     assert_eq!(asset_sats, 1000);
     assert_eq!(token_sats, 1);
+    // The contract is embedded in the PSET so signers can review the asset being issued
+    assert_eq!(issuance.get("name").unwrap().as_str().unwrap(), "example");
+    assert_eq!(issuance.get("ticker").unwrap().as_str().unwrap(), "EXMP");
+    let embedded = issuance.get("contract").unwrap();
+    assert_eq!(embedded.get("name").unwrap().as_str().unwrap(), "example");
 
     let balance = r.get("balance").unwrap().as_object().unwrap();
     // TODO: util to check balance with less unwrap
@@ -828,6 +1437,22 @@ fn test_issue() {
     let issuance_txid = get_str(&r, "txid");
     sh(&format!("{cli} server scan"));
 
+    let r = sh(&format!("{cli} server health"));
+    assert!(r.get("alive").unwrap().as_bool().unwrap());
+    assert!(r.get("ready").unwrap().as_bool().unwrap());
+
+    let r = sh(&format!("{cli} server ping-backend"));
+    assert!(r.get("tip_height").unwrap().as_u64().unwrap() > 0);
+    assert!(!get_str(&r, "server_version").is_empty());
+
+    let r = sh(&format!("{cli} wallet sync-progress --wallet w1"));
+    let tip_height = r.get("tip_height").unwrap().as_u64().unwrap();
+    assert_eq!(
+        r.get("scanned_height").unwrap().as_u64().unwrap(),
+        tip_height
+    );
+    assert_eq!(r.get("percent").unwrap().as_f64().unwrap(), 100.0);
+
     let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
     assert_eq!(get_balance(&cli, "w1", asset), 1000);
 
@@ -835,6 +1460,31 @@ fn test_issue() {
     let balance = r.get("balance").unwrap().as_object().unwrap();
     assert!(balance.get("L-BTC").unwrap().as_u64().unwrap() > 0);
 
+    let r = sh(&format!(
+        "{cli} wallet balance --wallet w1 --with-reissuance-tokens"
+    ));
+    let tokens = r.get("reissuance_tokens").unwrap().as_object().unwrap();
+    assert_eq!(tokens.get(token).unwrap().as_str().unwrap(), asset);
+
+    let r = sh(&format!(
+        "{cli} wallet issuance-tx --wallet w1 --asset {asset}"
+    ));
+    assert_eq!(get_str(&r, "txid"), issuance_txid);
+    assert_eq!(get_str(&r, "token"), token);
+    assert_eq!(r.get("asset_satoshi").unwrap().as_u64().unwrap(), 1000);
+    assert_eq!(r.get("token_satoshi").unwrap().as_u64().unwrap(), 1);
+    assert!(r.get("holds_token").unwrap().as_bool().unwrap());
+    assert_eq!(get_len(&r, "reissuance_txids"), 0);
+    let err = sh_err(&format!(
+        "{cli} wallet issuance-tx --wallet w1 --asset {policy_asset}"
+    ));
+    assert!(err.contains("was not issued by wallet 'w1'"), "{err}");
+
+    let r = sh(&format!("{cli} wallet details --wallet w1"));
+    assert_eq!(r.get("assets_held").unwrap().as_u64(), Some(3));
+    assert!(r.get("has_issued").unwrap().as_bool().unwrap());
+    assert!(r.get("holds_reissuance_tokens").unwrap().as_bool().unwrap());
+
     let r = sh(&format!("{cli} asset details --asset {policy_asset}"));
     assert_eq!(get_str(&r, "name"), "liquid bitcoin");
     assert_eq!(get_str(&r, "ticker"), "L-BTC");
@@ -859,6 +1509,15 @@ fn test_issue() {
     let r = sh(&format!("{cli} asset details --asset {token}"));
     assert_eq!(get_str(&r, "name"), reissuance_token_name);
 
+    let r = sh(&format!("{cli} asset metadata --asset {asset}"));
+    assert_eq!(get_str(&r, "name"), "example");
+    assert_eq!(get_str(&r, "ticker"), "EXMP");
+    assert_eq!(get_str(&r, "domain"), "example.com");
+    assert_eq!(r.get("precision").unwrap().as_u64().unwrap(), 0);
+
+    let err = sh_err(&format!("{cli} asset metadata --asset {token}"));
+    assert!(err.contains("No contract is known for asset"));
+
     sh(&format!("{cli} asset remove --asset {token}"));
     let r = sh(&format!("{cli} asset list"));
     assert_eq!(get_len(&r, "assets"), 2);
@@ -877,6 +1536,10 @@ fn test_issue() {
     ));
     complete(&cli, "w1", get_str(&r, "pset"), &["s1"]);
     assert_eq!(asset_balance_post + 1, get_balance(&cli, "w1", asset));
+    let r = sh(&format!(
+        "{cli} wallet issuance-tx --wallet w1 --asset {asset}"
+    ));
+    assert_eq!(get_len(&r, "reissuance_txids"), 1);
 
     let recipient = format!("--recipient burn:1:{asset}");
     let r = sh(&format!("{cli} wallet send --wallet w1 {recipient}"));
@@ -886,12 +1549,35 @@ fn test_issue() {
     let r = sh(&format!(
         "{cli} wallet burn -w w1 --asset {asset} --satoshi-asset 1"
     ));
-    complete(&cli, "w1", get_str(&r, "pset"), &["s1"]);
+    let txid = complete(&cli, "w1", get_str(&r, "pset"), &["s1"]);
     assert_eq!(asset_balance_post - 1, get_balance(&cli, "w1", asset));
+    let r = sh(&format!(
+        "{cli} wallet decode-data-outputs -w w1 --txid {txid}"
+    ));
+    let outputs = r.get("outputs").unwrap().as_array().unwrap();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(get_str(&outputs[0], "kind"), "burn");
+    assert_eq!(get_str(&outputs[0], "asset"), asset);
+    assert_eq!(outputs[0].get("satoshi").unwrap().as_u64(), Some(1));
 
     let r = sh(&format!("{cli} wallet utxos --wallet w1"));
     assert_eq!(get_len(&r, "utxos"), 4);
 
+    let r = sh(&format!("{cli} wallet utxos --wallet w1 --format core"));
+    let core = r.as_array().unwrap();
+    assert_eq!(core.len(), 4);
+    assert!(core[0].get("scriptPubKey").is_some());
+    assert!(core[0].get("confirmations").unwrap().as_u64().unwrap() > 0);
+
+    let r = sh(&format!("{cli} wallet used-addresses --wallet w1"));
+    let addresses = r.get("addresses").unwrap().as_array().unwrap();
+    let with_funds: usize = addresses
+        .iter()
+        .map(|a| a.get("confirmed").unwrap().as_object().unwrap().len())
+        .sum();
+    assert_eq!(with_funds, 4);
+    assert!(addresses.iter().any(|a| a.get("internal").unwrap() == true));
+
     let r = sh(&format!("{cli} wallet txs --wallet w1"));
     let txs = r.get("txs").unwrap().as_array().unwrap();
     assert!(!txs.is_empty());
@@ -955,6 +1641,31 @@ fn test_issue() {
         "{cli} wallet tx -w w2 -t {issuance_txid} --from-explorer"
     ));
 
+    // Sending two assets at once returns the change of each asset
+    let asset_balance_pre = get_balance(&cli, "w1", asset);
+    let lbtc_balance_pre = get_balance(&cli, "w1", policy_asset);
+    let node_address = server.node_getnewaddress();
+    let recipients = format!(
+        "--recipient {node_address}:1:{asset} --recipient {node_address}:1000:{policy_asset}"
+    );
+    let r = sh(&format!("{cli} wallet send --wallet w1 {recipients}"));
+    let change = r.get("change").unwrap().as_array().unwrap();
+    assert_eq!(change.len(), 2);
+    let change_of = |a: &str| {
+        let c = change.iter().find(|c| get_str(c, "asset") == a).unwrap();
+        c.get("satoshi").unwrap().as_u64().unwrap()
+    };
+    assert!(change_of(asset) > 0);
+    let pset = get_str(&r, "pset");
+    let details = sh(&format!("{cli} wallet pset-details --wallet w1 -p {pset}"));
+    let fee = details.get("fee").unwrap().as_u64().unwrap();
+    let balance = details.get("balance").unwrap().as_object().unwrap();
+    assert_eq!(balance.get(asset).unwrap().as_i64().unwrap(), -1);
+    // All L-BTC utxos are spent
+    assert_eq!(change_of(policy_asset), lbtc_balance_pre - 1000 - fee);
+    complete(&cli, "w1", pset, &["s1"]);
+    assert_eq!(get_balance(&cli, "w1", asset), asset_balance_pre - 1);
+
     sh(&format!("{cli} server stop"));
     t.join().unwrap();
 }
@@ -979,6 +1690,8 @@ fn test_jade_emulator() {
     assert!(r.get("id").is_some());
     assert!(r.get("mnemonic").is_none());
     assert_eq!(get_str(&r, "type"), "jade");
+    assert!(r.get("connected").unwrap().as_bool().unwrap());
+    assert!(r.get("jade_version").is_some());
     // Load singlesig wallets
     singlesig_wallet(&cli, "ss-wpkh", "emul", "slip77", "wpkh");
     singlesig_wallet(&cli, "ss-shwpkh", "emul", "slip77", "shwpkh");
@@ -1004,6 +1717,18 @@ fn test_jade_emulator() {
     let err = sh_err(&format!("{cli} wallet address -w ss-sw -s sw"));
     assert!(err.contains("Cannot display address with software signer"));
 
+    let r = sh(&format!("{cli} wallet address -w ss-wpkh --index 3"));
+    let expected = get_str(&r, "address");
+    let r = sh(&format!(
+        "{cli} wallet confirm-address -w ss-wpkh -s emul --index 3"
+    ));
+    assert_eq!(get_str(&r, "address"), expected);
+    assert_eq!(r.get("index").unwrap().as_u64().unwrap(), 3);
+    let err = sh_err(&format!(
+        "{cli} wallet confirm-address -w ss-sw -s sw --index 0"
+    ));
+    assert!(err.contains("Cannot display address with software signer"));
+
     sh(&format!("{cli} server stop"));
     std::thread::sleep(std::time::Duration::from_millis(100));
     t.join().unwrap();
@@ -1055,6 +1780,24 @@ fn test_commands() {
     assert!(get_str(&r, "text_qr").contains('█'));
     assert!(get_str(&r, "uri_qr").contains("data:image/bmp;base64"));
 
+    assert!(r.get("payment_request").is_none());
+    let r = sh(&format!("{cli_addr} --index 0 --with-payment-request"));
+    let address = get_str(&r, "address");
+    let payment_request = r.get("payment_request").unwrap();
+    assert_eq!(get_str(payment_request, "confidential_address"), address);
+    let explicit: Address = get_str(payment_request, "explicit_address")
+        .parse()
+        .unwrap();
+    assert!(!explicit.is_blinded());
+    assert_eq!(
+        get_str(payment_request, "script_pubkey"),
+        explicit.script_pubkey().to_hex()
+    );
+    assert_eq!(
+        get_str(payment_request, "uri"),
+        format!("liquidnetwork:{address}")
+    );
+
     let result = sh(&format!("{cli} wallet send --wallet custody --recipient el1qqdtwgfchn6rtl8peyw6afhrkpphqlyxls04vlwycez2fz6l7chlhxr8wtvy9s2v34f9sk0e2g058p0dwdp9kj38296xw5ur70:2:5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225"));
     let pset = result.get("pset").unwrap().as_str().unwrap();
     let _: PartiallySignedTransaction = pset.parse().unwrap();
@@ -1206,6 +1949,77 @@ fn test_multisig() {
     ));
     let pset_s = r.get("pset").unwrap().as_str().unwrap();
 
+    // Merging the signatures gives the same PSET without going through the wallet
+    let r = sh(&format!(
+        "{cli} wallet merge-signatures -p {pset_s1} -p {pset_s2}"
+    ));
+    assert_eq!(get_str(&r, "pset"), pset_s);
+    let r = sh(&format!(
+        "{cli} wallet send --wallet multi --recipient {node_address}:2000:{policy_asset}"
+    ));
+    let other = get_str(&r, "pset");
+    let err = sh_err(&format!(
+        "{cli} wallet merge-signatures -p {pset_s1} -p {other}"
+    ));
+    assert!(
+        err.contains("psets describe different transactions"),
+        "{err}"
+    );
+
+    for (pset, percentage, complete) in [
+        (pset_u, 0, false),
+        (pset_s1, 50, false),
+        (pset_s, 100, true),
+    ] {
+        let r = sh(&format!(
+            "{cli} wallet pset-details --wallet multi -p {pset}"
+        ));
+        let progress = r.get("signing_progress").unwrap();
+        assert_eq!(
+            progress.get("signatures_required").unwrap().as_u64(),
+            Some(2)
+        );
+        assert_eq!(
+            progress.get("percentage").unwrap().as_u64(),
+            Some(percentage)
+        );
+        assert_eq!(
+            progress.get("is_complete").unwrap().as_bool(),
+            Some(complete)
+        );
+        assert!(!progress
+            .get("inputs")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+
+    // Collect the signatures with a signing session
+    let r = sh(&format!("{cli} wallet session-create -w multi -p {pset_u}"));
+    let session_id = get_str(&r, "session_id").to_string();
+    assert_eq!(get_len(&r, "has_signatures_from"), 0);
+    assert_eq!(get_len(&r, "missing_signatures_from"), 2);
+    let r = sh(&format!(
+        "{cli} wallet session-add --session-id {session_id} -p {pset_s1}"
+    ));
+    assert_eq!(get_len(&r, "has_signatures_from"), 1);
+    assert_eq!(get_len(&r, "missing_signatures_from"), 1);
+    sh(&format!(
+        "{cli} wallet session-add --session-id {session_id} -p {pset_s2}"
+    ));
+    let r = sh(&format!(
+        "{cli} wallet session-get --session-id {session_id}"
+    ));
+    assert_eq!(get_len(&r, "has_signatures_from"), 2);
+    assert_eq!(get_len(&r, "missing_signatures_from"), 0);
+    sh(&format!(
+        "{cli} wallet broadcast --wallet multi --pset {} --dry-run",
+        get_str(&r, "pset")
+    ));
+    let err = sh_err(&format!("{cli} wallet session-get --session-id wrong"));
+    assert!(err.contains("Signing session 'wrong' does not exist"));
+
     let r = sh(&format!(
         "{cli} wallet broadcast --wallet multi --pset {pset_s}"
     ));
@@ -1322,6 +2136,14 @@ fn test_registry_publish() {
 
     sh(&format!("{cli} asset remove --asset {token}"));
 
+    // not inserted, details and metadata come from the registry (and then from the cache)
+    for _ in 0..2 {
+        let r = sh(&format!("{cli} asset details --asset {asset}"));
+        assert_eq!(get_str(&r, "name"), "example");
+        let r = sh(&format!("{cli} asset metadata --asset {asset}"));
+        assert_eq!(get_str(&r, "ticker"), "EXMP");
+    }
+
     sh(&format!("{cli} asset list"));
 
     sh(&format!("{cli} asset from-explorer --asset {asset}"));
@@ -1393,6 +2215,575 @@ fn test_elip151() {
     t.join().unwrap();
 }
 
+#[test]
+fn test_confirmations() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    let confirmations = |txid: &str| {
+        let r = sh(&format!("{cli} wallet confirmations -w w1 --txid {txid}"));
+        r.get("confirmations").unwrap().as_u64()
+    };
+    assert_eq!(confirmations(&"00".repeat(32)), None);
+
+    let addr = Address::from_str(&address(&cli, "w1")).unwrap();
+    let txid = server.node_sendtoaddress(&addr, 1_000, None).to_string();
+    wait_tx(&cli, "w1", &txid);
+    assert_eq!(confirmations(&txid), Some(0));
+
+    server.generate(2);
+    sh(&format!("{cli} server scan"));
+    for _ in 0..20 {
+        if confirmations(&txid) == Some(2) {
+            break;
+        }
+        wait_ms(500);
+    }
+    assert_eq!(confirmations(&txid), Some(2));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_consolidate() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    let err = sh_err(&format!("{cli} wallet consolidate -w w1"));
+    assert!(err.contains("at least 2 are needed to consolidate"));
+
+    for sats in [10_000, 20_000, 1_000_000] {
+        fund(&server, &cli, "w1", sats);
+    }
+    let target = address(&cli, "w1");
+    let r = sh(&format!(
+        "{cli} wallet consolidate -w w1 --max-inputs 2 --target-address {target}"
+    ));
+    assert_eq!(r.get("consolidated").unwrap().as_u64().unwrap(), 2);
+    assert_eq!(r.get("remaining").unwrap().as_u64().unwrap(), 1);
+    let fee = r.get("fee").unwrap().as_u64().unwrap();
+    let output = r.get("output").unwrap();
+    assert_eq!(get_str(output, "address"), target);
+    assert_eq!(
+        output.get("satoshi").unwrap().as_u64().unwrap(),
+        30_000 - fee
+    );
+
+    complete(&cli, "w1", get_str(&r, "pset"), &["s1"]);
+    let r = sh(&format!("{cli} wallet utxos -w w1"));
+    assert_eq!(get_len(&r, "utxos"), 2);
+
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let balance = get_balance(&cli, "w1", policy_asset);
+    let r = sh(&format!("{cli} wallet max-spendable -w w1"));
+    assert_eq!(get_str(&r, "asset"), policy_asset);
+    let fee = r.get("fee").unwrap().as_u64().unwrap();
+    assert!(fee > 0);
+    assert_eq!(r.get("satoshi").unwrap().as_u64().unwrap(), balance - fee);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_check_pset_spendable() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let node_address = server.node_getnewaddress().to_string();
+    let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+
+    // Two PSETs spending the same single utxo
+    let r = sh(&format!("{cli} wallet send -w w1 {recipient}"));
+    let pset1 = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} wallet send -w w1 {recipient}"));
+    let pset2 = get_str(&r, "pset").to_string();
+
+    let r = sh(&format!(
+        "{cli} wallet check-pset-spendable -w w1 -p {pset2}"
+    ));
+    assert!(r.get("spendable").unwrap().as_bool().unwrap());
+    assert_eq!(get_len(&r, "stale"), 0);
+
+    let txid = complete(&cli, "w1", &pset1, &["s1"]);
+    wait_tx(&cli, "w1", &txid);
+
+    let r = sh(&format!(
+        "{cli} wallet check-pset-spendable -w w1 -p {pset2}"
+    ));
+    assert!(!r.get("spendable").unwrap().as_bool().unwrap());
+    assert_eq!(get_len(&r, "stale"), 1);
+    let input = &r.get("inputs").unwrap().as_array().unwrap()[0];
+    assert_eq!(get_str(input, "status"), "spent");
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_multisig_fee_estimate() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    let signers = &["s1", "s2", "s3"];
+    for signer in signers {
+        sw_signer(&cli, signer);
+    }
+    multisig_wallet(&cli, "multi", 2, signers, "slip77-rand");
+    fund(&server, &cli, "multi", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let node_address = server.node_getnewaddress().to_string();
+    let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+
+    let estimate = |pset: &str| {
+        sh(&format!(
+            "{cli} wallet multisig-fee-estimate -w multi -p {pset}"
+        ))
+    };
+    let r = sh(&format!("{cli} wallet send -w multi {recipient}"));
+    let pset = get_str(&r, "pset").to_string();
+    let unsigned = estimate(&pset);
+    assert!(unsigned.get("adequate").unwrap().as_bool().unwrap());
+    assert!(unsigned.get("vsize").unwrap().as_u64().unwrap() > 0);
+
+    // The estimate doesn't change while collecting signatures
+    let r = sh(&format!("{cli} signer sign -s s1 --pset {pset}"));
+    let pset = get_str(&r, "pset").to_string();
+    assert_eq!(estimate(&pset), unsigned);
+
+    // A lower fee than the one of the wallet is not adequate
+    let r = sh(&format!(
+        "{cli} wallet send -w multi {recipient} --fee-rate 50"
+    ));
+    let pset = get_str(&r, "pset").to_string();
+    let r = estimate(&pset);
+    assert!(!r.get("adequate").unwrap().as_bool().unwrap());
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_send_absolute_fee() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let node_address = server.node_getnewaddress().to_string();
+    let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+
+    let r = sh(&format!("{cli} wallet send -w w1 {recipient}"));
+    let fee_rate = r.get("fee_rate").unwrap().as_f64().unwrap();
+    assert!((99.0..120.0).contains(&fee_rate), "{fee_rate}");
+
+    let r = sh(&format!("{cli} wallet send -w w1 {recipient} --fee 2000"));
+    assert_eq!(r.get("fee").unwrap().as_u64().unwrap(), 2_000);
+    assert!(r.get("fee_rate").unwrap().as_f64().unwrap() > fee_rate);
+    let pset = get_str(&r, "pset");
+    let r = sh(&format!("{cli} wallet pset-details -w w1 -p {pset}"));
+    assert_eq!(r.get("fee").unwrap().as_u64().unwrap(), 2_000);
+    let txid = complete(&cli, "w1", pset, &["s1"]);
+    wait_tx(&cli, "w1", &txid);
+
+    let err = sh_err(&format!("{cli} wallet send -w w1 {recipient} --fee 1"));
+    assert!(err.contains("below the minimum"));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_pset_fee() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let node_address = server.node_getnewaddress().to_string();
+    let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+
+    let r = sh(&format!("{cli} wallet send -w w1 {recipient} --fee 2000"));
+    let pset = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} wallet pset-fee -p {pset}"));
+    assert_eq!(r.get("fee").unwrap().as_u64(), Some(2_000));
+    assert!(!r.get("finalized").unwrap().as_bool().unwrap());
+    let vsize = r.get("vsize").unwrap().as_u64().unwrap();
+    let fee_rate = r.get("fee_rate").unwrap().as_f64().unwrap();
+    assert!((fee_rate - 2_000_000.0 / vsize as f64).abs() < 1.0);
+
+    let err = sh_err(&format!("{cli} wallet pset-fee -p invalid"));
+    assert!(!err.is_empty());
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_rebroadcast_pending() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+
+    // The funding transaction is confirmed
+    let r = sh(&format!("{cli} wallet rebroadcast-pending -w w1"));
+    assert_eq!(get_len(&r, "rebroadcast"), 0);
+    assert_eq!(get_len(&r, "failed"), 0);
+
+    let node_address = server.node_getnewaddress();
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let r = sh(&format!(
+        "{cli} wallet send --wallet w1 --recipient {node_address}:1000:{policy_asset}"
+    ));
+    let pset = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} signer sign -s s1 --pset {pset}"));
+    let pset = get_str(&r, "pset").to_string();
+    let r = sh(&format!("{cli} wallet broadcast -w w1 --pset {pset}"));
+    let txid = get_str(&r, "txid").to_string();
+    wait_tx(&cli, "w1", &txid);
+
+    // Still in mempool, so already known by the backend
+    let r = sh(&format!("{cli} wallet rebroadcast-pending -w w1"));
+    let rebroadcast = r.get("rebroadcast").unwrap().as_array().unwrap();
+    assert_eq!(rebroadcast.len(), 1);
+    assert_eq!(rebroadcast[0].as_str(), Some(txid.as_str()));
+    assert_eq!(get_len(&r, "failed"), 0);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_sweep_key() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let wif = "cMpMxK92W1DjqDvWV3pMn4xLwAuQJhNF3MFqkEHUQRPQofUJku8R";
+    let slip77 = "slip77(ab5824f4477b4ebb00a132adfd8eb0b7935cf24f6ac151add5d1913db374ce92)";
+    let sweep = format!("{cli} wallet sweep-key -w w1 --wif {wif} --blinding-key {slip77}");
+
+    // The error tells the addresses to fund, the wpkh one first
+    let err = sh_err(&sweep);
+    assert!(err.contains("(shwpkh)"), "{err}");
+    let (_, key_address) = err
+        .split_once("No funds found at the addresses of the key: ")
+        .unwrap();
+    let key_address: String = key_address
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    let key_address = Address::from_str(&key_address).unwrap();
+    assert!(key_address.is_blinded());
+    server.node_sendtoaddress(&key_address, 100_000, None);
+    server.generate(2);
+
+    let mut r = sh_result(&sweep);
+    for _ in 0..20 {
+        if r.is_ok() {
+            break;
+        }
+        wait_ms(500);
+        r = sh_result(&sweep);
+    }
+    let r = r.unwrap();
+    assert_eq!(get_str(&r, "swept_address"), key_address.to_string());
+    assert_eq!(get_str(&r, "script_type"), "wpkh");
+    assert_eq!(r.get("utxos").unwrap().as_u64().unwrap(), 1);
+    let fee = r.get("fee").unwrap().as_u64().unwrap();
+    let satoshi = r.get("satoshi").unwrap().get(policy_asset).unwrap();
+    assert_eq!(satoshi.as_u64().unwrap() + fee, 100_000);
+
+    let tx: elements::Transaction =
+        elements::encode::deserialize(&Vec::<u8>::from_hex(get_str(&r, "tx")).unwrap()).unwrap();
+    assert_eq!(tx.txid().to_string(), get_str(&r, "txid"));
+    assert_eq!(tx.input.len(), 1);
+    assert!(!tx.input[0].witness.script_witness.is_empty());
+    let address = Address::from_str(get_str(&r, "address")).unwrap();
+    assert!(tx
+        .output
+        .iter()
+        .any(|o| o.script_pubkey == address.script_pubkey()));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_broadcast_batch() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let node_address = server.node_getnewaddress().to_string();
+    let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+    let r = sh(&format!("{cli} wallet send -w w1 {recipient}"));
+    let r = sh(&format!(
+        "{cli} signer sign -s s1 --pset {}",
+        get_str(&r, "pset")
+    ));
+    let pset = get_str(&r, "pset");
+
+    // The second entry fails, the third is the first one again and by default it fails too
+    let r = sh(&format!(
+        "{cli} wallet broadcast-batch -w w1 --pset {pset} --pset invalid --pset {pset}"
+    ));
+    assert_eq!(r.get("broadcast").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(r.get("failed").unwrap().as_u64().unwrap(), 2);
+    let results = r.get("results").unwrap().as_array().unwrap();
+    let txid = get_str(&results[0], "txid");
+    assert!(results[0].get("error").unwrap().is_null());
+    assert!(!results[0].get("already_known").unwrap().as_bool().unwrap());
+    assert!(results[1].get("txid").unwrap().is_null());
+    assert!(!get_str(&results[1], "error").is_empty());
+    assert_eq!(get_str(&results[2], "txid"), txid);
+    assert!(!results[2].get("already_known").unwrap().as_bool().unwrap());
+    assert!(get_str(&results[2], "error").contains("txn-already"));
+    wait_tx(&cli, "w1", txid);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_balance_all() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    singlesig_wallet(&cli, "w2", "s1", "elip151", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+
+    let r = sh(&format!("{cli} wallet balance-all --sync"));
+    let wallets = r.get("wallets").unwrap();
+    let balance = |name: &str| {
+        let entry = wallets.get(name).unwrap();
+        assert!(entry.get("synced").unwrap().as_bool().unwrap());
+        assert!(entry.get("error").is_none());
+        let balance = entry.get("balance").unwrap();
+        balance.get(policy_asset).unwrap().as_u64().unwrap()
+    };
+    assert_eq!(balance("w1"), 1_000_000);
+    assert_eq!(balance("w1"), get_balance(&cli, "w1", policy_asset));
+    assert_eq!(balance("w2"), 0);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_discover_funds() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    // Beyond the default gap limit
+    let r = sh(&format!("{cli} wallet address -w w1 --index 50"));
+    let far = get_str(&r, "address").to_string();
+    let addr = Address::from_str(&far).unwrap();
+    server.node_sendtoaddress(&addr, 10_000, None);
+    server.generate(1);
+
+    let used = |max_gap: u32| {
+        let r = sh(&format!(
+            "{cli} wallet discover-funds -w w1 --max-gap {max_gap}"
+        ));
+        let chains = r.get("chains").unwrap().as_array().unwrap();
+        assert_eq!(chains.len(), 2);
+        assert!(!chains[0].get("internal").unwrap().as_bool().unwrap());
+        assert!(!chains[0]
+            .get("max_index_reached")
+            .unwrap()
+            .as_bool()
+            .unwrap());
+        let used = chains[0].get("used").unwrap().as_array().unwrap();
+        used.iter()
+            .map(|u| u.get("index").unwrap().as_u64().unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(used(20), vec![0]);
+    assert_eq!(used(60), vec![0, 50]);
+    let r = sh(&format!("{cli} wallet discover-funds -w w1 --max-gap 60"));
+    let found = &r.get("chains").unwrap()[0].get("used").unwrap()[1];
+    assert_eq!(get_str(found, "address"), far);
+
+    let err = sh_err(&format!("{cli} wallet discover-funds -w w1 --max-gap 0"));
+    assert!(err.contains("max_gap must be"));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_dust_report() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    fund(&server, &cli, "w1", 1_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+
+    let r = sh(&format!("{cli} wallet dust-report -w w1"));
+    assert_eq!(r.get("total").unwrap().as_u64().unwrap(), 0);
+
+    // At 100 sat/vb spending an output costs more than 1000 sats
+    let r = sh(&format!("{cli} wallet dust-report -w w1 --fee-rate 100000"));
+    assert_eq!(r.get("total").unwrap().as_u64().unwrap(), 1_000);
+    let dust = r.get("assets").unwrap().get(policy_asset).unwrap();
+    let utxos = dust.get("utxos").unwrap().as_array().unwrap();
+    assert_eq!(utxos.len(), 1);
+    assert_eq!(utxos[0].get("value").unwrap().as_u64().unwrap(), 1_000);
+    assert!(utxos[0].get("spend_cost").unwrap().as_u64().unwrap() > 1_000);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_wallet_default_fee_rate() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    let r = sh(&format!(
+        "{cli} signer singlesig-desc -s s1 --descriptor-blinding-key slip77 --kind wpkh"
+    ));
+    let desc = get_str(&r, "descriptor").to_string();
+    sh(&format!("{cli} wallet load -w w1 -d {desc}"));
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let node_address = server.node_getnewaddress().to_string();
+    let fee = |args: &str| {
+        let recipient = format!("--recipient {node_address}:1000:{policy_asset}");
+        let r = sh(&format!("{cli} wallet send -w w1 {recipient} {args}"));
+        let pset = get_str(&r, "pset");
+        let r = sh(&format!("{cli} wallet pset-details -w w1 -p {pset}"));
+        r.get("fee").unwrap().as_u64().unwrap()
+    };
+    let builder_default_fee = fee("");
+
+    sh(&format!("{cli} wallet unload -w w1"));
+    let r = sh(&format!(
+        "{cli} wallet load -w w1 -d {desc} --default-fee-rate 1000"
+    ));
+    assert_eq!(r.get("default_fee_rate").unwrap().as_f64().unwrap(), 1000.0);
+    sh(&format!("{cli} server scan"));
+    assert!(fee("") > builder_default_fee * 5);
+    // The fee rate of the request takes precedence
+    assert_eq!(fee("--fee-rate 100"), builder_default_fee);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_pending_balance() {
+    let (t, _tmp, cli, _params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    singlesig_wallet(&cli, "w2", "s1", "elip151", "wpkh");
+    fund(&server, &cli, "w1", 1_000_000);
+    let policy_asset = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+    let w2_addr = address(&cli, "w2");
+    let txid = send(&cli, "w1", &w2_addr, policy_asset, 1_000, &["s1"]);
+    wait_tx(&cli, "w2", &txid);
+
+    let pending = |wallet: &str| {
+        let r = sh(&format!("{cli} wallet balance -w {wallet} --with-pending"));
+        let b = r.get("pending").unwrap().get(policy_asset).unwrap();
+        let v = |k| b.get(k).unwrap().as_u64().unwrap();
+        (v("confirmed"), v("pending_incoming"), v("pending_outgoing"))
+    };
+    let (confirmed, incoming, outgoing) = pending("w1");
+    assert_eq!((confirmed, incoming), (1_000_000, 0));
+    assert!(outgoing > 1_000);
+    assert_eq!(get_balance(&cli, "w1", policy_asset), confirmed - outgoing);
+    assert_eq!(pending("w2"), (0, 1_000, 0));
+
+    server.generate(1);
+    std::thread::sleep(std::time::Duration::from_millis(2000));
+    sh(&format!("{cli} server scan"));
+    assert_eq!(pending("w1"), (1_000_000 - outgoing, 0, 0));
+    assert_eq!(pending("w2"), (1_000, 0, 0));
+
+    let r = sh(&format!("{cli} wallet balance -w w1"));
+    assert!(r.get("pending").is_none());
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
+#[test]
+fn test_blinding_factors() {
+    let (t, _tmp, cli, params, server, _) = setup_cli(false);
+
+    sw_signer(&cli, "s1");
+    singlesig_wallet(&cli, "w1", "s1", "slip77", "wpkh");
+    let addr = Address::from_str(&address(&cli, "w1")).unwrap();
+    let txid = server.node_sendtoaddress(&addr, 1_000, None).to_string();
+    wait_tx(&cli, "w1", &txid);
+
+    let err = sh_err(&format!(
+        "{cli} wallet blinding-factors -w w1 --txid {txid}"
+    ));
+    assert!(err.contains("returns secret data"));
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+    let t = {
+        let cli = cli.clone();
+        std::thread::spawn(move || {
+            sh(&format!(
+                "{cli} server start --scanning-interval 1 --allow-secret-export {params}"
+            ));
+        })
+    };
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+    wait_tx(&cli, "w1", &txid);
+
+    let r = sh(&format!(
+        "{cli} wallet blinding-factors -w w1 --txid {txid}"
+    ));
+    let outputs = r.get("outputs").unwrap().as_array().unwrap();
+    assert_eq!(outputs.len(), 1);
+    let output = &outputs[0];
+
+    // The blinding factors open the commitments of the output
+    let r = sh(&format!("{cli} wallet tx -w w1 -t {txid}"));
+    let tx: elements::Transaction =
+        elements::encode::deserialize(&Vec::<u8>::from_hex(get_str(&r, "tx")).unwrap()).unwrap();
+    let vout = output.get("vout").unwrap().as_u64().unwrap() as usize;
+    let secp = elements::secp256k1_zkp::Secp256k1::new();
+    let asset = elements::AssetId::from_str(get_str(output, "asset")).unwrap();
+    let abf = AssetBlindingFactor::from_str(get_str(output, "asset_blinder")).unwrap();
+    let vbf = ValueBlindingFactor::from_str(get_str(output, "value_blinder")).unwrap();
+    let satoshi = output.get("satoshi").unwrap().as_u64().unwrap();
+    assert_eq!(satoshi, 1_000);
+    let asset_comm = Asset::new_confidential(&secp, asset, abf);
+    assert_eq!(tx.output[vout].asset, asset_comm);
+    let asset_gen = asset_comm.commitment().unwrap();
+    let value_comm = confidential::Value::new_confidential(&secp, satoshi, asset_gen, vbf);
+    assert_eq!(tx.output[vout].value, value_comm);
+
+    sh(&format!("{cli} server stop"));
+    t.join().unwrap();
+}
+
 #[test]
 fn test_3of5() {
     let (t, _tmp, cli, _params, server, _) = setup_cli(false);
@@ -3,6 +3,8 @@ use std::str::FromStr;
 use elements::bitcoin::bip32::{DerivationPath, KeySource, Xpub};
 use elements::hex::ToHex;
 use elements_miniscript::descriptor::checksum::desc_checksum;
+use elements_miniscript::policy::concrete::{DescriptorCtx, Policy};
+use elements_miniscript::{DescriptorPublicKey, Segwitv0};
 use rand::{thread_rng, Rng};
 use thiserror::Error;
 
@@ -67,6 +69,11 @@ pub fn multisig_desc(
     } else if threshold as usize > xpubs.len() {
         return Err("Threshold cannot be greater than the number of xpubs".into());
     }
+    for (i, (_, xpub)) in xpubs.iter().enumerate() {
+        if xpubs[..i].iter().any(|(_, other)| other == xpub) {
+            return Err(format!("Duplicate xpub {xpub}"));
+        }
+    }
 
     let (prefix, suffix) = match script_variant {
         Multisig::Wsh => ("elwsh(multi", ")"),
@@ -103,7 +110,43 @@ pub fn multisig_desc(
     Ok(format!("{desc}#{checksum}"))
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Compile a miniscript policy, e.g. `thresh(2,pk(A),pk(B),older(144))`, into a segwit v0
+/// confidential descriptor
+///
+/// The policy must be safe and allow non-malleable satisfactions, the keys should be
+/// ranged, e.g. `[fingerprint/87h/1h/0h]xpub/<0;1>/*`.
+pub fn policy_desc(
+    policy: &str,
+    blinding_variant: DescriptorBlindingKey,
+) -> Result<String, String> {
+    let policy = Policy::<DescriptorPublicKey>::from_str(policy).map_err(|e| e.to_string())?;
+    let descriptor = policy
+        .compile_to_descriptor::<Segwitv0>(DescriptorCtx::Wsh)
+        .map_err(|e| format!("Cannot compile policy: {e}"))?;
+    descriptor.sanity_check().map_err(|e| e.to_string())?;
+
+    let blinding_key = match blinding_variant {
+        DescriptorBlindingKey::Slip77 => {
+            return Err(
+                "Deterministic slip77 key not supported in policy descriptor generation".into(),
+            )
+        }
+        DescriptorBlindingKey::Slip77Rand => {
+            let mut bytes = [0u8; 32];
+            thread_rng().fill(&mut bytes);
+            format!("slip77({})", bytes.to_hex())
+        }
+        DescriptorBlindingKey::Elip151 => "elip151".to_string(),
+    };
+
+    let descriptor = descriptor.to_string();
+    let descriptor = descriptor.split('#').next().unwrap_or_default();
+    let desc = format!("ct({blinding_key},{descriptor})");
+    let checksum = desc_checksum(&desc).map_err(|e| format!("{:?}", e))?;
+    Ok(format!("{desc}#{checksum}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Singlesig {
     /// as defined by bip84
     Wpkh,
@@ -116,15 +159,21 @@ pub enum Singlesig {
 #[error("Invalid singlesig variant '{0}' supported variant are: 'wpkh', 'shwpkh'")]
 pub struct InvalidSinglesigVariant(String);
 
+impl Singlesig {
+    /// The strings accepted when parsing a [`Singlesig`], with the corresponding variant
+    pub const VARIANTS: &'static [(&'static str, Singlesig)] =
+        &[("wpkh", Singlesig::Wpkh), ("shwpkh", Singlesig::ShWpkh)];
+}
+
 impl FromStr for Singlesig {
     type Err = InvalidSinglesigVariant;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "wpkh" => Singlesig::Wpkh,
-            "shwpkh" => Singlesig::ShWpkh,
-            v => return Err(InvalidSinglesigVariant(v.to_string())),
-        })
+        Self::VARIANTS
+            .iter()
+            .find(|(v, _)| *v == s)
+            .map(|(_, variant)| *variant)
+            .ok_or_else(|| InvalidSinglesigVariant(s.to_string()))
     }
 }
 
@@ -133,7 +182,7 @@ impl FromStr for Singlesig {
 /// Not all the possible cases described in
 /// [ELIP150](<https://github.com/ElementsProject/ELIPs/blob/main/elip-0150.mediawiki>)
 /// have a corresponding variant in this enum.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DescriptorBlindingKey {
     /// Deterministic [SLIP77](<https://github.com/satoshilabs/slips/blob/master/slip-0077.md>) master blinding key
     ///
@@ -159,19 +208,28 @@ pub enum DescriptorBlindingKey {
 #[error("Invalid blinding key variant '{0}' supported variant are: 'slip77', 'elip151'")]
 pub struct InvalidBlindingKeyVariant(String);
 
+impl DescriptorBlindingKey {
+    /// The strings accepted when parsing a [`DescriptorBlindingKey`], with the corresponding variant
+    pub const VARIANTS: &'static [(&'static str, DescriptorBlindingKey)] = &[
+        ("slip77", DescriptorBlindingKey::Slip77),
+        ("slip77-rand", DescriptorBlindingKey::Slip77Rand),
+        ("elip151", DescriptorBlindingKey::Elip151),
+    ];
+}
+
 impl FromStr for DescriptorBlindingKey {
     type Err = InvalidBlindingKeyVariant;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "slip77" => DescriptorBlindingKey::Slip77,
-            "slip77-rand" => DescriptorBlindingKey::Slip77Rand,
-            "elip151" => DescriptorBlindingKey::Elip151,
-            v => return Err(InvalidBlindingKeyVariant(v.to_string())),
-        })
+        Self::VARIANTS
+            .iter()
+            .find(|(v, _)| *v == s)
+            .map(|(_, variant)| *variant)
+            .ok_or_else(|| InvalidBlindingKeyVariant(s.to_string()))
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Bip {
     Bip84,
     Bip49,
@@ -182,19 +240,28 @@ pub enum Bip {
 #[error("Invalid bip  variant '{0}' supported variant are: 'bip84'")]
 pub struct InvalidBipVariant(String);
 
+impl Bip {
+    /// The strings accepted when parsing a [`Bip`], with the corresponding variant
+    pub const VARIANTS: &'static [(&'static str, Bip)] = &[
+        ("bip84", Bip::Bip84),
+        ("bip49", Bip::Bip49),
+        ("bip87", Bip::Bip87),
+    ];
+}
+
 impl FromStr for Bip {
     type Err = InvalidBipVariant;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "bip84" => Bip::Bip84,
-            "bip49" => Bip::Bip49,
-            "bip87" => Bip::Bip87,
-            v => return Err(InvalidBipVariant(v.to_string())),
-        })
+        Self::VARIANTS
+            .iter()
+            .find(|(v, _)| *v == s)
+            .map(|(_, variant)| *variant)
+            .ok_or_else(|| InvalidBipVariant(s.to_string()))
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Multisig {
     Wsh,
 }
@@ -203,13 +270,43 @@ pub enum Multisig {
 #[error("Invalid multisig variant '{0}' supported variant are: 'wsh'")]
 pub struct InvalidMultisigVariant(String);
 
+impl Multisig {
+    /// The strings accepted when parsing a [`Multisig`], with the corresponding variant
+    pub const VARIANTS: &'static [(&'static str, Multisig)] = &[("wsh", Multisig::Wsh)];
+}
+
 impl FromStr for Multisig {
     type Err = InvalidMultisigVariant;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "wsh" => Multisig::Wsh,
-            v => return Err(InvalidMultisigVariant(v.to_string())),
-        })
+        Self::VARIANTS
+            .iter()
+            .find(|(v, _)| *v == s)
+            .map(|(_, variant)| *variant)
+            .ok_or_else(|| InvalidMultisigVariant(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::{Bip, DescriptorBlindingKey, Multisig, Singlesig};
+
+    #[test]
+    fn test_variants_roundtrip() {
+        for (s, v) in Singlesig::VARIANTS {
+            assert_eq!(Singlesig::from_str(s).unwrap(), *v);
+        }
+        for (s, v) in DescriptorBlindingKey::VARIANTS {
+            assert_eq!(DescriptorBlindingKey::from_str(s).unwrap(), *v);
+        }
+        for (s, v) in Bip::VARIANTS {
+            assert_eq!(Bip::from_str(s).unwrap(), *v);
+        }
+        for (s, v) in Multisig::VARIANTS {
+            assert_eq!(Multisig::from_str(s).unwrap(), *v);
+        }
+        assert!(Singlesig::from_str("wsh").is_err());
     }
 }
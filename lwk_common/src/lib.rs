@@ -13,17 +13,19 @@ mod descriptor;
 mod error;
 mod keyorigin_xpub;
 mod model;
+mod payment_uri;
 mod qr;
 mod signer;
 
 pub use crate::descriptor::{
-    multisig_desc, singlesig_desc, Bip, DescriptorBlindingKey, InvalidBipVariant,
+    multisig_desc, policy_desc, singlesig_desc, Bip, DescriptorBlindingKey, InvalidBipVariant,
     InvalidBlindingKeyVariant, InvalidMultisigVariant, InvalidSinglesigVariant, Multisig,
     Singlesig,
 };
 pub use crate::error::Error;
 pub use crate::keyorigin_xpub::{keyorigin_xpub_from_str, InvalidKeyOriginXpub};
 pub use crate::model::*;
+pub use crate::payment_uri::{parse_payment_uri, InvalidPaymentUri, PaymentUri};
 pub use crate::qr::*;
 pub use crate::signer::Signer;
 
@@ -35,7 +37,7 @@ use elements_miniscript::elements::bitcoin::secp256k1::SecretKey;
 use elements_miniscript::elements::{
     bitcoin::{bip32::KeySource, key::PublicKey},
     opcodes::all::OP_RETURN,
-    pset::PartiallySignedTransaction,
+    pset::{Input, PartiallySignedTransaction},
     script::Builder,
     secp256k1_zkp::{All, Generator, PedersenCommitment, Secp256k1},
     AssetId, BlindAssetProofs, BlindValueProofs, OutPoint, Script, TxOutSecrets,
@@ -72,6 +74,32 @@ pub fn derive_blinding_key(
     }
 }
 
+/// Whether the blinded amounts of the issuance in `input` can be rewound with `sk`
+fn blinded_issuance_unblindable(secp: &Secp256k1<All>, input: &Input, sk: SecretKey) -> bool {
+    let (asset, token) = input.issuance_ids();
+    [
+        (
+            input.issuance_value_comm,
+            input.issuance_value_rangeproof.as_ref(),
+            asset,
+        ),
+        (
+            input.issuance_inflation_keys_comm,
+            input.issuance_keys_rangeproof.as_ref(),
+            token,
+        ),
+    ]
+    .into_iter()
+    .all(|(comm, rangeproof, asset)| match (comm, rangeproof) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(comm), Some(rangeproof)) => {
+            let generator = Generator::new_unblinded(secp, asset.into_tag());
+            rangeproof.rewind(secp, comm, sk, &[], generator).is_ok()
+        }
+    })
+}
+
 fn commitments(
     secp: &Secp256k1<All>,
     txout_secrets: &TxOutSecrets,
@@ -147,9 +175,13 @@ pub fn pset_balance(
                 }
                 if input.has_issuance() {
                     let issuance = input.asset_issuance();
-                    if issuance.amount.is_confidential()
-                        || issuance.inflation_keys.is_confidential()
-                    {
+                    let blinded = issuance.amount.is_confidential()
+                        || issuance.inflation_keys.is_confidential();
+                    // Blinded issuances are supported only if the wallet can unblind them
+                    let unblindable = derive_blinding_key(descriptor, &txout.script_pubkey)
+                        .map(|sk| blinded_issuance_unblindable(&secp, input, sk))
+                        .unwrap_or(false);
+                    if blinded && !unblindable {
                         return Err(Error::InputBlindedIssuance { idx });
                     }
                 }
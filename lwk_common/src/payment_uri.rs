@@ -0,0 +1,119 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Invalid payment uri \"{uri}\": {reason}")]
+pub struct InvalidPaymentUri {
+    uri: String,
+    reason: String,
+}
+
+/// The recipient described by a payment uri, like
+/// "liquidnetwork:<address>?amount=<amount>&assetid=<asset>"
+#[derive(Debug, PartialEq, Eq)]
+pub struct PaymentUri {
+    /// The address to send to
+    pub address: String,
+
+    /// The amount in satoshi, the uri `amount` is expressed with 8 decimals
+    pub satoshi: Option<u64>,
+
+    /// The asset to send, if missing the policy asset
+    pub asset: Option<String>,
+}
+
+/// Parse a payment uri, the address is not validated
+pub fn parse_payment_uri(uri: &str) -> Result<PaymentUri, InvalidPaymentUri> {
+    let err = |reason: &str| InvalidPaymentUri {
+        uri: uri.to_string(),
+        reason: reason.to_string(),
+    };
+    let (scheme, rest) = uri.split_once(':').ok_or_else(|| err("missing scheme"))?;
+    if !["liquidnetwork", "liquidtestnet"].contains(&scheme.to_ascii_lowercase().as_str()) {
+        return Err(err("unknown scheme"));
+    }
+    let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if address.is_empty() {
+        return Err(err("missing address"));
+    }
+
+    let mut satoshi = None;
+    let mut asset = None;
+    for param in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| err("parameter without value"))?;
+        match key {
+            "amount" => satoshi = Some(parse_amount(value).ok_or_else(|| err("invalid amount"))?),
+            "assetid" => asset = Some(value.to_string()),
+            _ => (),
+        }
+    }
+
+    // Addresses in QR codes may be uppercased to save space
+    let address = if address.chars().all(|c| !c.is_ascii_lowercase()) {
+        address.to_ascii_lowercase()
+    } else {
+        address.to_string()
+    };
+    Ok(PaymentUri {
+        address,
+        satoshi,
+        asset,
+    })
+}
+
+/// Convert a decimal amount with up to 8 decimals in satoshi
+fn parse_amount(amount: &str) -> Option<u64> {
+    let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    if int.is_empty() && frac.is_empty() || frac.len() > 8 {
+        return None;
+    }
+    let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(int) || !all_digits(frac) {
+        return None;
+    }
+    let int: u64 = if int.is_empty() { 0 } else { int.parse().ok()? };
+    let frac: u64 = format!("{frac:0<8}").parse().ok()?;
+    int.checked_mul(100_000_000)?.checked_add(frac)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_payment_uri() {
+        let addr = "lq1qqf8er278e6nyvuwtgf39e6ewvdcnjupn9a86rzpx655y5lhkt0walu3djf9cklkxd3ryld97hu8h3xepw7sh2rlu7q45dcew5";
+        let asset = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+        let r = parse_payment_uri(&format!("liquidnetwork:{addr}")).unwrap();
+        assert_eq!(r.address, addr);
+        assert_eq!(r.satoshi, None);
+        assert_eq!(r.asset, None);
+
+        let uri = format!("liquidnetwork:{addr}?amount=0.0001&assetid={asset}");
+        let r = parse_payment_uri(&uri).unwrap();
+        assert_eq!(r.satoshi, Some(10_000));
+        assert_eq!(r.asset.as_deref(), Some(asset));
+
+        let uri = format!("liquidnetwork:{}", addr.to_ascii_uppercase());
+        assert_eq!(parse_payment_uri(&uri).unwrap().address, addr);
+
+        assert!(parse_payment_uri(addr).is_err());
+        assert!(parse_payment_uri(&format!("bitcoin:{addr}")).is_err());
+        assert!(parse_payment_uri("liquidnetwork:").is_err());
+        let uri = format!("liquidnetwork:{addr}?amount=0.000000001");
+        assert!(parse_payment_uri(&uri).is_err());
+        let uri = format!("liquidnetwork:{addr}?amount=-1");
+        assert!(parse_payment_uri(&uri).is_err());
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("1"), Some(100_000_000));
+        assert_eq!(parse_amount("1.5"), Some(150_000_000));
+        assert_eq!(parse_amount(".00000001"), Some(1));
+        assert_eq!(parse_amount("."), None);
+        assert_eq!(parse_amount("1e3"), None);
+    }
+}
@@ -16,6 +16,11 @@ fn address_to_qr_text(address: &Address) -> String {
     format!("liquidnetwork:{}", address_string)
 }
 
+/// Convert the given address in a payment uri, like "liquidnetwork:<address>"
+pub fn address_to_uri(address: &Address) -> String {
+    format!("liquidnetwork:{address}")
+}
+
 /// Convert the given address in a string representing a QR code to be consumed from a terminal
 pub fn address_to_text_qr(address: &Address) -> Result<String, QrError> {
     let address = address_to_qr_text(address);
@@ -110,6 +115,12 @@ mod test {
         assert!(text_qr.contains(expected.trim()));
     }
 
+    #[test]
+    fn test_address_to_uri() {
+        let address = Address::from_str(ADDR).unwrap();
+        assert_eq!(address_to_uri(&address), format!("liquidnetwork:{ADDR}"));
+    }
+
     #[test]
     fn test_address_to_uri_qr() {
         let address = Address::from_str(ADDR).unwrap();
@@ -1,5 +1,6 @@
 use elements::{
     bitcoin::bip32::{ChildNumber, Fingerprint, Xpub},
+    hashes::{sha256, Hash, HashEngine},
     hex::ToHex,
     Script,
 };
@@ -146,6 +147,28 @@ impl JadeDescriptor {
             .explicit_script()
             .map_err(|_| Error::UnsupportedDescriptorType)
     }
+
+    /// A deterministic name for the multisig wallet, usable as `multisig_name` when registering
+    ///
+    /// The name is "lwk" followed by 12 hex chars of a hash committing to the descriptor, 15
+    /// chars in total, fitting Jade's limit. For `sortedmulti` descriptors the signers order
+    /// doesn't change the name.
+    pub fn multisig_name(&self) -> String {
+        let mut signers: Vec<String> = self.signers.iter().map(|s| s.keyorigin_xpub()).collect();
+        if self.sorted {
+            signers.sort();
+        }
+        let mut engine = sha256::Hash::engine();
+        engine.input(self.variant.as_bytes());
+        engine.input(&[self.sorted as u8]);
+        engine.input(&self.threshold.to_le_bytes());
+        engine.input(&self.master_blinding_key);
+        for signer in signers {
+            engine.input(signer.as_bytes());
+        }
+        let hash = sha256::Hash::from_engine(engine);
+        format!("lwk{}", &hash.to_hex()[..12])
+    }
 }
 
 impl TryFrom<&DescriptorPublicKey> for MultisigSigner {
@@ -284,6 +307,14 @@ mod test {
                 let desc2: ConfidentialDescriptor<DescriptorPublicKey> =
                     (&jade_desc).try_into().unwrap();
                 assert_eq!(desc, desc2);
+
+                let name = jade_desc.multisig_name();
+                assert_eq!(name.len(), 15);
+                assert!(name.starts_with("lwk"));
+                let swapped = format!("ct(slip77({slip77_key}),elwsh({k}({t},{b}/*,{a}/*)))");
+                let swapped: ConfidentialDescriptor<DescriptorPublicKey> = swapped.parse().unwrap();
+                let swapped: JadeDescriptor = (&swapped).try_into().unwrap();
+                assert_eq!(name == swapped.multisig_name(), k == "sortedmulti");
             }
         }
     }
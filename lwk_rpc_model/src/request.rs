@@ -41,6 +41,12 @@ pub struct WalletLoad {
 
     /// The name given to the wallet, will be needed for calls related to the wallet
     pub name: String,
+
+    /// Fee rate used by the transactions of this wallet when the request doesn't specify one, in
+    /// the same unit of the `fee_rate` of the methods creating transactions.
+    /// It takes precedence over the server defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_fee_rate: Option<f32>,
 }
 
 /// Unload the wallet identified by the given name
@@ -50,6 +56,13 @@ pub struct WalletUnload {
     pub name: String,
 }
 
+/// Check a mnemonic is valid without loading it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerValidateMnemonic {
+    /// The mnemonic (12 or 24 words)
+    pub mnemonic: String,
+}
+
 /// Load a signer in the server
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SignerLoadSoftware {
@@ -61,6 +74,10 @@ pub struct SignerLoadSoftware {
 
     /// Whether to persist the software signer
     pub persist: bool,
+
+    /// Replace a signer already loaded with the same name
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// Load a signer in the server
@@ -74,6 +91,10 @@ pub struct SignerLoadJade {
 
     /// If set, instead of looking for physical jade, try to connect to the emulator at the following port
     pub emulator: Option<SocketAddr>,
+
+    /// Replace a signer already loaded with the same name
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// Load a signer in the server
@@ -84,6 +105,38 @@ pub struct SignerLoadExternal {
 
     /// The fingerprint identifyng the external signer
     pub fingerprint: String,
+
+    /// Replace a signer already loaded with the same name
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// A signer to load as part of a batch
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SignerSpec {
+    /// A software signer, as in [`SignerLoadSoftware`]
+    Software(SignerLoadSoftware),
+
+    /// A Jade signer, as in [`SignerLoadJade`]
+    Jade(SignerLoadJade),
+
+    /// An external signer, as in [`SignerLoadExternal`]
+    External(SignerLoadExternal),
+}
+
+/// Load several signers in the server
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerLoadMany {
+    /// The signers to load, in the given order
+    pub signers: Vec<SignerSpec>,
+
+    /// Load either every signer or none of them
+    ///
+    /// If false, the signers that can be loaded are loaded and the others are reported as
+    /// failed.
+    #[serde(default)]
+    pub atomic: bool,
 }
 
 /// Get the signer details
@@ -100,13 +153,36 @@ pub struct SignerUnload {
     pub name: String,
 }
 
+/// Replace the external signers having the fingerprint of the given signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerReplace {
+    /// The name of the signer replacing the external ones, it can't be external
+    pub name: String,
+}
+
+/// Check whether a signer participates in a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerMatchesWallet {
+    /// The name of the signer
+    pub signer_name: String,
+
+    /// The wallet name
+    pub wallet_name: String,
+}
+
 /// Request a receiving address
+///
+/// Getting an address has no side effect on the wallet: repeated requests without index return
+/// the same address, until a scan finds a transaction on it and the first unused index moves.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletAddress {
     /// The wallet name
     pub name: String,
 
     /// The derivation index for the wildcard, if missing the first unused index is used
+    ///
+    /// The address is derived locally from the descriptor, no backend call is made in either
+    /// case, the first unused index is the one known after the last scan
     pub index: Option<u32>,
 
     /// The signer name
@@ -117,6 +193,36 @@ pub struct WalletAddress {
 
     /// Return a image QR code encoded as uri with the given pixel per module
     pub with_uri_qr: Option<u8>,
+
+    /// Whether to return the payment request with every format of the address
+    #[serde(default)]
+    pub with_payment_request: bool,
+}
+
+/// Request to display an address on a hardware signer to let the user confirm it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConfirmAddress {
+    /// The wallet name
+    pub name: String,
+
+    /// The derivation index of the address
+    pub index: u32,
+
+    /// The name of the hardware signer displaying the address
+    pub signer: String,
+}
+
+/// Request to compare the address derived by a wallet with the one computed by a hardware signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifyDeviceAddress {
+    /// The wallet name
+    pub wallet_name: String,
+
+    /// The name of the signer
+    pub signer_name: String,
+
+    /// The derivation index of the address
+    pub index: u32,
 }
 
 /// The balance of a wallet
@@ -127,6 +233,35 @@ pub struct WalletBalance {
 
     /// Replace asset ids with tickers when possible
     pub with_tickers: bool,
+
+    /// Return which assets in the balance are reissuance tokens and the asset they control
+    #[serde(default)]
+    pub with_reissuance_tokens: bool,
+
+    /// Return also the confirmed balance and the pending incoming and outgoing amounts
+    #[serde(default)]
+    pub with_pending: bool,
+}
+
+/// Request the balance of every loaded wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletBalanceAll {
+    /// Replace asset ids with tickers when possible
+    pub with_tickers: bool,
+
+    /// Wait for a scan of every wallet before computing the balances
+    pub sync: bool,
+}
+
+/// Request the balance of a wallet split between confirmed and pending outputs
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConfirmedBalance {
+    /// The wallet name
+    pub name: String,
+
+    /// Confirmations an output needs to be counted as confirmed, default 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_confirmations: Option<u32>,
 }
 
 /// Send a transaction from a wallet
@@ -140,23 +275,218 @@ pub struct WalletSendMany {
 
     /// Optional fee rate in sat/vb
     pub fee_rate: Option<f32>,
+
+    /// Optional exact fee in satoshi, can't be set with `fee_rate`
+    ///
+    /// Fails if it's below the minimum relay fee for the size of the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+
+    /// Optional timelocked branch of the descriptor to satisfy, like "older(144)" or "after(1000)"
+    ///
+    /// Sequence and locktime of the transaction are set accordingly
+    pub spend_path: Option<String>,
+
+    /// Randomize the order of the outputs and split the L-BTC change in two outputs if it's large
+    /// enough, the fee output is always last
+    ///
+    /// The additional change output makes the transaction bigger, so it pays a higher fee for the
+    /// same fee rate.
+    #[serde(default)]
+    pub privacy: bool,
+}
+
+/// Create a transaction, sign it with local software signers and finalize it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletLocalMultisigSend {
+    /// The transaction to create, as in `wallet_send_many`
+    #[serde(flatten)]
+    pub send: WalletSendMany,
+
+    /// Names of the loaded software signers signing the transaction
+    pub signers: Vec<String>,
+}
+
+/// Sweep the smallest L-BTC utxos of a wallet into a single output
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConsolidate {
+    /// The wallet name
+    pub name: String,
+
+    /// Optional fee rate in sat/vb
+    pub fee_rate: Option<f32>,
+
+    /// Maximum number of utxos to spend, the smallest are chosen first, defaults to 100
+    pub max_inputs: Option<u32>,
+
+    /// The address receiving the consolidated output, defaults to a change address of the wallet
+    pub target_address: Option<String>,
+}
+
+/// Request the maximum amount of an asset the wallet can send
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletMaxSpendable {
+    /// The wallet name
+    pub name: String,
+
+    /// The asset to send, defaults to the policy asset
+    pub asset: Option<String>,
+
+    /// Optional fee rate in sat/vb
+    pub fee_rate: Option<f32>,
+
+    /// The destination address, defaults to an address of the wallet
+    pub address: Option<String>,
+}
+
+/// Request to sweep the funds controlled by a private key not in the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SweepKey {
+    /// The name of the wallet receiving the funds
+    pub name: String,
+
+    /// The private key in WIF format
+    pub wif: String,
+
+    /// The descriptor blinding key used to unblind the funds of the private key, like
+    /// `slip77(...)` or a view key
+    ///
+    /// There is no blinding key that can be derived from the private key alone, and `elip151` is
+    /// not supported for descriptors without wildcard.
+    pub blinding_key: String,
+
+    /// The script type of the private key address, "wpkh" or "shwpkh"
+    ///
+    /// If missing, the addresses of both types are scanned and the sweep fails if both have
+    /// funds.
+    #[serde(default)]
+    pub script_type: Option<String>,
+
+    /// Optional fee rate in sat/vb
+    pub fee_rate: Option<f32>,
+}
+
+/// Validate addressees without building a transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletValidateAddressees {
+    /// The addressees to validate, in the same form accepted by [`WalletSendMany`]
+    pub addressees: Vec<UnvalidatedAddressee>,
+}
+
+/// Add inputs and outputs of a wallet to a PSET shared with other parties
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletAddToPset {
+    /// The wallet name contributing to the PSET
+    pub name: String,
+
+    /// The PSET in base64 to add to
+    pub pset: String,
+
+    /// The wallet unspent outputs to add as inputs, in the form "txid:vout"
+    ///
+    /// Inputs cannot be added once some output of the PSET is blinded
+    #[serde(default)]
+    pub add_inputs: Vec<String>,
+
+    /// The addressees to add as outputs, the value of the added inputs exceeding them is sent to
+    /// wallet change addresses
+    #[serde(default)]
+    pub add_outputs: Vec<UnvalidatedAddressee>,
+
+    /// The satoshi of the policy asset the wallet contributes to the fee
+    #[serde(default)]
+    pub fee: u64,
+
+    /// Blind the outputs to be blinded with the wallet inputs
+    ///
+    /// Set it once every party has added its inputs and outputs, the last one blinding balances
+    /// the blinding factors
+    #[serde(default)]
+    pub blind: bool,
+}
+
+/// Unload every wallet or signer at once
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UnloadAll {
+    /// Unload every wallet, interrupting their scan
+    pub wallets: bool,
+
+    /// Unload every signer
+    pub signers: bool,
+}
+
+/// Set the defaults used by the methods creating transactions when the caller omits them
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetDefaults {
+    /// Default fee rate, in the same unit of the `fee_rate` of the methods creating transactions
+    #[serde(default)]
+    pub fee_rate: Option<f32>,
+
+    /// Default number of blocks within which transactions should confirm, the fee rate is
+    /// estimated by the Electrum server
+    ///
+    /// Cannot be set together with `fee_rate`
+    #[serde(default)]
+    pub confirmation_target: Option<u32>,
+}
+
+/// Propose to swap an asset of a wallet for another asset
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSwapPropose {
+    /// The wallet name proposing the swap
+    pub name: String,
+
+    /// The asset offered by the wallet
+    pub send_asset: String,
+
+    /// The satoshi of `send_asset` offered
+    pub send_amount: u64,
+
+    /// The asset asked in exchange
+    pub receive_asset: String,
+
+    /// The satoshi of `receive_asset` asked
+    pub receive_amount: u64,
+}
+
+/// Accept a swap proposal, completing it with the inputs and outputs of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSwapAccept {
+    /// The wallet name accepting the swap
+    pub name: String,
+
+    /// The swap proposal PSET in base64
+    pub pset: String,
+
+    /// Optional fee rate in sat/vb, the fee is paid by the accepting wallet
+    #[serde(default)]
+    pub fee_rate: Option<f32>,
 }
 
 ///  An addressee which has yet to be validated
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UnvalidatedAddressee {
     /// The amount to send in satoshi
+    #[serde(default)]
     pub satoshi: u64,
 
     /// The address to send to
     ///
     /// If "burn", the output will be burned
+    #[serde(default)]
     pub address: String,
 
     /// The asset to send
     ///
     /// If empty, the policy asset
+    #[serde(default)]
     pub asset: String,
+
+    /// A payment uri like "liquidnetwork:<address>?amount=<amount>&assetid=<asset>"
+    ///
+    /// Alternative to `satoshi`, `address` and `asset`, which must be unset if this is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
 }
 
 /// A request containing information to create a single signature descriptor wallet
@@ -172,6 +502,13 @@ pub struct SignerSinglesigDescriptor {
     pub singlesig_kind: String,
 }
 
+/// A request for every standard single signature descriptor of a signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerSinglesigDescriptorSet {
+    /// The signer name
+    pub name: String,
+}
+
 /// A request containing information to create a multi signature descriptor wallet
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletMultisigDescriptor {
@@ -186,6 +523,86 @@ pub struct WalletMultisigDescriptor {
 
     /// The partecipants in the multisig wallet xpubs with key origin
     pub keyorigin_xpubs: Vec<String>,
+
+    /// Whether to return a deterministic name to use when registering the wallet on Jade
+    #[serde(default)]
+    pub with_jade_name: bool,
+}
+
+/// A request to compile a miniscript policy into a descriptor
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCompilePolicy {
+    /// The policy, e.g. "thresh(2,pk(A),pk(B),older(144))" with ranged keys like
+    /// "[fingerprint/87h/1h/0h]xpub/<0;1>/*"
+    pub policy: String,
+
+    /// The descriptor blinding key
+    pub descriptor_blinding_key: String,
+}
+
+/// Compute the checksum of a descriptor, without loading it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDescriptorChecksum {
+    /// The descriptor, an existing checksum is ignored and recomputed
+    pub descriptor: String,
+}
+
+/// Check the keys of a descriptor are for the server network, without loading a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCheckNetwork {
+    /// The descriptor, confidential or not
+    pub descriptor: String,
+}
+
+/// Get the fee of a PSET, without loading a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPsetFee {
+    /// The PSET in base64
+    pub pset: String,
+}
+
+/// Check which spending branches of the wallet descriptor the inputs of a PSET satisfy
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCheckSpendPath {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET in base64
+    pub pset: String,
+}
+
+/// Re-serialize a PSET with the requested version, without loading a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConvertPsetVersion {
+    /// The PSET in base64
+    pub pset: String,
+
+    /// The requested PSET version, currently only 2 is supported
+    pub version: u32,
+}
+
+/// Remove the signatures of a PSET, without loading a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletStripSignatures {
+    /// The PSET in base64
+    pub pset: String,
+}
+
+/// Convert a descriptor blinding key to another representation, without loading a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConvertBlindingKey {
+    /// The blinding key: "slip77(<hex>)", a view key (hex or WIF) or "elip151"
+    pub input: String,
+
+    /// The requested representation: "slip77", "view" or "elip151"
+    pub to: String,
+
+    /// A descriptor the key must be compatible with, required for "elip151"
+    ///
+    /// It can be either a confidential descriptor, whose blinding key must match the input, or
+    /// a non-confidential one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptor: Option<String>,
 }
 
 /// Request to register a multisig wallet on a signer
@@ -216,6 +633,32 @@ pub struct SignerSign {
 
     /// The PSET in base64
     pub pset: String,
+
+    /// Indexes of the inputs to sign, if missing every input the signer can sign is signed.
+    ///
+    /// The other inputs are left untouched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<Vec<u32>>,
+}
+
+/// Check whether a signer can sign a PSET of a wallet, without signing
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerCanSign {
+    /// The signer name
+    pub name: String,
+
+    /// The wallet name
+    pub wallet: String,
+
+    /// The PSET in base64
+    pub pset: String,
+}
+
+/// Request the signing capabilities of a signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerCapabilities {
+    /// The signer name
+    pub name: String,
 }
 
 /// Request to broadcast a transaction
@@ -231,6 +674,23 @@ pub struct WalletBroadcast {
     pub pset: String,
 }
 
+/// Request to finalize and broadcast several independent PSETs
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletBroadcastBatch {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSETs in base64, broadcast in the given order
+    pub psets: Vec<String>,
+}
+
+/// Broadcast again the unconfirmed transactions of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletRebroadcastPending {
+    /// The wallet name
+    pub name: String,
+}
+
 /// Request details for a wallet
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletDetails {
@@ -238,6 +698,53 @@ pub struct WalletDetails {
     pub name: String,
 }
 
+/// Request the descriptor of a loaded wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDescriptor {
+    /// The wallet name
+    pub name: String,
+
+    /// Whether to return also the descriptor of the internal (change) chain
+    pub include_internal: bool,
+}
+
+/// Request the sync progress of a loaded wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSyncProgress {
+    /// The wallet name
+    pub name: String,
+}
+
+/// Request to stop the scan of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCancelSync {
+    /// The wallet name
+    pub name: String,
+}
+
+/// Request to scan a wallet with a different Electrum server than the configured one
+///
+/// The server is used for the current session only: it is not persisted, after a restart or
+/// once the wallet is unloaded the configured server is used again.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSetBackend {
+    /// The wallet name
+    pub name: String,
+
+    /// The Electrum server url, like "blockstream.info:995"
+    pub electrum_url: String,
+
+    /// Whether to connect to the Electrum server with TLS
+    pub tls: bool,
+
+    /// Accept only the server certificate with this SHA-256 fingerprint, in hex with optional
+    /// `:` separators, instead of validating the domain. Used only with `tls`.
+    ///
+    /// The pinned certificate of the server configuration does not apply to this server.
+    #[serde(default)]
+    pub cert_fingerprint: Option<String>,
+}
+
 /// Request to do an issuance
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletIssue {
@@ -261,6 +768,13 @@ pub struct WalletIssue {
 
     /// The optional fee rate
     pub fee_rate: Option<f32>,
+
+    /// Whether the issued asset and token amounts are confidential, false if missing.
+    ///
+    /// Explicit amounts let anyone, such as registries and exchanges, verify the supply of the
+    /// asset, blinded amounts can be seen only by the issuing wallet.
+    #[serde(default)]
+    pub blinded_issuance: bool,
 }
 
 /// Request to do a reissuance
@@ -320,6 +834,13 @@ pub struct AssetContract {
     pub version: u8,
 }
 
+/// Merge the signatures of PSETs describing the same transaction, without loading a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletMergeSignatures {
+    /// The PSETs in base64, they must describe the same transaction
+    pub psets: Vec<String>,
+}
+
 /// Request to combine PSETs
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletCombine {
@@ -328,6 +849,70 @@ pub struct WalletCombine {
 
     /// A list of PSET to combine
     pub pset: Vec<String>,
+
+    /// Paths of files containing PSETs to combine, in base64 or binary, combined after `pset`
+    ///
+    /// Relative paths are resolved from the pset directory of the server, files outside of it
+    /// cannot be read
+    #[serde(default)]
+    pub pset_file: Vec<String>,
+}
+
+/// Request to start a signing session for a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSessionCreate {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET in base64 to be signed
+    pub pset: String,
+}
+
+/// Request to add the signatures contained in a PSET to a signing session
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSessionAdd {
+    /// The session identifier
+    pub session_id: String,
+
+    /// The PSET in base64 containing the new signatures
+    pub pset: String,
+}
+
+/// Request to get the current state of a signing session
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSessionGet {
+    /// The session identifier
+    pub session_id: String,
+}
+
+/// Request to look for privacy issues of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPrivacyAnalysis {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET in base64 to analyze
+    pub pset: String,
+}
+
+/// Request to check the inputs of a PSET are still unspent outputs of the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCheckPsetSpendable {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET in base64 to check
+    pub pset: String,
+}
+
+/// Request an estimate of the fee of a PSET once every input is signed
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletMultisigFeeEstimate {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET in base64, at any signing stage
+    pub pset: String,
 }
 
 /// Request to see details of a PSET
@@ -343,11 +928,90 @@ pub struct WalletPsetDetails {
     pub with_tickers: bool,
 }
 
+/// Request a self-contained description of a PSET to sign, for offline signers
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSigningRequest {
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET in base64 to sign
+    pub pset: String,
+}
+
+/// Request the addresses of the wallet that received funds
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletUsedAddresses {
+    /// The wallet name
+    pub name: String,
+}
+
+/// Request the outputs of the wallet worth less than the fee needed to spend them
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDustReport {
+    /// The wallet name
+    pub name: String,
+
+    /// Optional fee rate, in the same unit of the `fee_rate` of the methods creating
+    /// transactions, defaults to the one used by the wallet
+    pub fee_rate: Option<f32>,
+}
+
+/// Request a deep scan of the wallet looking for the derivation indexes that received funds
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDiscoverFunds {
+    /// The wallet name
+    pub name: String,
+
+    /// Number of consecutive unused addresses after which the scan of a chain stops,
+    /// defaults to 100
+    pub max_gap: Option<u32>,
+}
+
+/// Request to reserve an output so that the transactions created afterwards don't spend it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletLockUtxo {
+    /// The wallet name
+    pub name: String,
+
+    /// The output to lock, as "txid:vout". It may be not in the wallet yet, for instance the
+    /// change of a transaction not broadcast
+    pub outpoint: String,
+
+    /// The unix time in seconds when the lock expires, if missing in one hour
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+/// Request to release an output locked with [`WalletLockUtxo`]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletUnlockUtxo {
+    /// The wallet name
+    pub name: String,
+
+    /// The output to unlock, as "txid:vout"
+    pub outpoint: String,
+}
+
+/// Request the number of confirmations of a wallet transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConfirmations {
+    /// The wallet name
+    pub name: String,
+
+    /// The transaction id
+    pub txid: String,
+}
+
 /// Request to get the wallet unspet transaction Outputs
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletUtxos {
     /// The wallet name
     pub name: String,
+
+    /// The format of the outputs, "native" (default) or "core" to also have them as returned by
+    /// the Elements Core `listunspent` call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
 }
 
 /// Request to get the wallet transactions
@@ -373,6 +1037,53 @@ pub struct WalletTx {
     pub from_explorer: bool,
 }
 
+/// Request to decode the data (OP_RETURN) outputs of a wallet transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDecodeDataOutputs {
+    /// The wallet name
+    pub name: String,
+
+    /// Transaction ID
+    pub txid: String,
+}
+
+/// Request what is needed to load the wallet as watch-only elsewhere
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletExportWatchOnly {
+    /// The wallet name
+    pub name: String,
+}
+
+/// Request the wallet in the format another wallet software imports
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletExportTo {
+    /// The wallet name
+    pub name: String,
+
+    /// The wallet software: "green", "core" or "generic"
+    pub target: String,
+}
+
+/// Request the transaction issuing an asset of the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletIssuanceTx {
+    /// The wallet name
+    pub name: String,
+
+    /// The asset identifier
+    pub asset_id: String,
+}
+
+/// Request the blinding factors of the wallet outputs of a transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletBlindingFactors {
+    /// The wallet name
+    pub name: String,
+
+    /// Transaction ID
+    pub txid: String,
+}
+
 /// Request to have details of an asset
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AssetDetails {
@@ -380,6 +1091,13 @@ pub struct AssetDetails {
     pub asset_id: String,
 }
 
+/// Request to retrieve the contract metadata committed to an asset
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AssetMetadata {
+    /// The asset identifier
+    pub asset_id: String,
+}
+
 /// Request to insert an asset
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AssetInsert {
@@ -457,8 +1175,29 @@ mod test {
     fn test_json_schema() {
         let schema = schema_for!(WalletLoad);
         assert_eq!(
-            r#"{"$schema":"http://json-schema.org/draft-07/schema#","title":"WalletLoad","description":"Request to load a wallet in the server, returning [`response::Wallet`]","type":"object","required":["descriptor","name"],"properties":{"descriptor":{"description":"The read-only descriptor describing the wallet outputs","type":"string"},"name":{"description":"The name given to the wallet, will be needed for calls related to the wallet","type":"string"}}}"#,
+            r#"{"$schema":"http://json-schema.org/draft-07/schema#","title":"WalletLoad","description":"Request to load a wallet in the server, returning [`response::Wallet`]","type":"object","required":["descriptor","name"],"properties":{"default_fee_rate":{"description":"Fee rate used by the transactions of this wallet when the request doesn't specify one, in the same unit of the `fee_rate` of the methods creating transactions. It takes precedence over the server defaults.","type":["number","null"],"format":"float"},"descriptor":{"description":"The read-only descriptor describing the wallet outputs","type":"string"},"name":{"description":"The name given to the wallet, will be needed for calls related to the wallet","type":"string"}}}"#,
             serde_json::to_string(&schema).unwrap()
         );
     }
+
+    #[test]
+    fn test_optional_flags() {
+        // Flags added after the first version of a request can be omitted by existing clients
+        let r: WalletBalance =
+            serde_json::from_str(r#"{"name":"w1","with_tickers":false}"#).unwrap();
+        assert!(!r.with_reissuance_tokens);
+        assert!(!r.with_pending);
+
+        let r: WalletMultisigDescriptor = serde_json::from_str(
+            r#"{"descriptor_blinding_key":"slip77","multisig_kind":"wsh","threshold":1,"keyorigin_xpubs":[]}"#,
+        )
+        .unwrap();
+        assert!(!r.with_jade_name);
+
+        let r: WalletAddress = serde_json::from_str(
+            r#"{"name":"w1","index":null,"signer":null,"with_text_qr":false,"with_uri_qr":null}"#,
+        )
+        .unwrap();
+        assert!(!r.with_payment_request);
+    }
 }
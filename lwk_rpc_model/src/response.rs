@@ -5,7 +5,7 @@ use crate::request;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// An empty response.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -21,6 +21,187 @@ pub struct Version {
     pub network: String,
 }
 
+/// Server health, distinguishing liveness from readiness
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Health {
+    /// Always true if the server is able to reply
+    pub alive: bool,
+
+    /// True if the backend was reachable in the last scan and every wallet has been scanned
+    pub ready: bool,
+
+    /// Details about the readiness
+    pub details: HealthDetails,
+}
+
+/// The defaults used by the methods creating transactions when the caller omits them
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Defaults {
+    /// Default fee rate
+    pub fee_rate: Option<f32>,
+
+    /// Default number of blocks within which transactions should confirm
+    pub confirmation_target: Option<u32>,
+}
+
+/// The variants accepted by the methods creating descriptors and xpubs
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListVariants {
+    /// The singlesig descriptor kinds, like "wpkh"
+    pub singlesig: Vec<String>,
+
+    /// The multisig descriptor kinds, like "wsh"
+    pub multisig: Vec<String>,
+
+    /// The descriptor blinding key variants, like "slip77"
+    pub blinding_keys: Vec<String>,
+
+    /// The BIP kinds of the signer xpubs, like "bip84"
+    pub xpub_kinds: Vec<String>,
+}
+
+/// The configuration the server is running with, secrets are omitted
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GetConfig {
+    /// The server network
+    pub network: String,
+
+    /// The address where the RPC server is listening
+    pub addr: String,
+
+    /// The data directory
+    pub datadir: String,
+
+    /// The data directory of the network, where the state and the wallets data are stored
+    pub network_datadir: String,
+
+    /// True if nothing is persisted because the data directory is not writable
+    pub in_memory: bool,
+
+    /// Whether the server keeps the data in memory when the data directory is not writable
+    pub in_memory_fallback: bool,
+
+    /// The Electrum server, credentials are redacted
+    pub electrum_url: String,
+
+    /// Electrum servers tried in order when the main one is unreachable, credentials are redacted
+    pub electrum_fallback_urls: Vec<String>,
+
+    /// Whether the connection to the Electrum server uses TLS
+    pub tls: bool,
+
+    /// Whether the domain of the Electrum server certificate is validated
+    pub validate_domain: bool,
+
+    /// The SHA-256 fingerprint the Electrum server certificate is pinned to
+    pub electrum_cert_fingerprint: Option<String>,
+
+    /// The block explorer, credentials are redacted
+    pub explorer_url: String,
+
+    /// The Esplora API, credentials are redacted
+    pub esplora_api_url: String,
+
+    /// The asset registry, credentials are redacted
+    pub registry_url: String,
+
+    /// Seconds contracts fetched from the registry are cached
+    pub registry_cache_secs: u64,
+
+    /// Timeout in seconds of the requests to the backend
+    pub timeout_secs: u64,
+
+    /// Seconds between wallet scans
+    pub scanning_interval_secs: u64,
+
+    /// Seconds PSET signing sessions are kept
+    pub session_ttl_secs: u64,
+
+    /// If true, broadcasting a transaction already in mempool or in chain is not an error
+    pub already_known_is_success: bool,
+
+    /// Maximum number of wallets that can be loaded at the same time
+    pub max_wallets: usize,
+
+    /// Maximum number of signers that can be loaded at the same time
+    pub max_signers: usize,
+
+    /// Maximum number of connections open at the same time to the Electrum servers
+    pub max_backend_connections: usize,
+
+    /// PSET details warn if the fee is higher than this fraction of the policy asset sent
+    pub fee_warning_fraction: f64,
+
+    /// Whether methods returning secret data are allowed
+    pub allow_secret_export: bool,
+
+    /// The directory from which PSET files can be read
+    pub pset_dir: Option<String>,
+
+    /// Maximum number of requests per second for each auth token, by method name
+    pub rate_limits: BTreeMap<String, u32>,
+
+    /// Number of the configured auth tokens, the tokens themselves are not reported
+    pub auth_tokens: usize,
+
+    /// Number of the auth tokens restricted to some wallets
+    pub auth_token_wallets: usize,
+
+    /// The default fee rate in sat/kvB, set with set-defaults
+    pub default_fee_rate: Option<f32>,
+
+    /// The default confirmation target in blocks, set with set-defaults
+    pub default_confirmation_target: Option<u32>,
+
+    /// The fee rate in sat/kvB used when there is no default and no estimation
+    pub fallback_fee_rate: f32,
+
+    /// True if signers are generated from a seed, insecure and meant only for tests
+    pub insecure_signer_seed: bool,
+}
+
+/// Result of a diagnostic request to the blockchain backend
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PingBackend {
+    /// The url of the backend, a fallback if the configured one is unreachable
+    pub url: String,
+
+    /// Milliseconds needed to open a new connection to the backend
+    pub connect_ms: u64,
+
+    /// Milliseconds of the round-trip of a trivial request to the backend
+    pub latency_ms: u64,
+
+    /// The software version reported by the backend
+    pub server_version: String,
+
+    /// The height of the blockchain tip known by the backend
+    pub tip_height: u32,
+}
+
+/// Details of the server readiness
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HealthDetails {
+    /// True if the backend was reachable in the last scan loop
+    pub backend_reachable: bool,
+
+    /// Number of scan loops completed
+    pub scan_loops_completed: u32,
+
+    /// Sync status of the loaded wallets
+    pub wallets: Vec<WalletHealth>,
+}
+
+/// Sync status of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletHealth {
+    /// The wallet name
+    pub name: String,
+
+    /// True if the last scan of the wallet succeeded
+    pub synced: bool,
+}
+
 /// Response for generate signer
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SignerGenerate {
@@ -28,6 +209,24 @@ pub struct SignerGenerate {
     pub mnemonic: String,
 }
 
+/// Result of the validation of a mnemonic
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerValidateMnemonic {
+    /// Whether the mnemonic is a valid BIP39 phrase
+    pub valid: bool,
+
+    /// The fingerprint of the signer the mnemonic would load, if valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+
+    /// The number of words of the given mnemonic
+    pub word_count: usize,
+
+    /// Why the mnemonic is not valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Response for list signers call
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SignerList {
@@ -43,6 +242,14 @@ pub struct Wallet {
 
     /// The wallet name
     pub name: String,
+
+    /// Whether the descriptor uses the multipath notation, like `<0;1>`, defining both the receive
+    /// and the change chains
+    pub multipath: bool,
+
+    /// The fee rate used by the transactions of the wallet when the request doesn't specify one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_fee_rate: Option<f32>,
 }
 
 /// Response for list wallets call
@@ -59,6 +266,16 @@ pub struct WalletUnload {
     pub unloaded: Wallet,
 }
 
+/// The wallets and signers unloaded
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UnloadAll {
+    /// The names of the wallets unloaded
+    pub wallets: Vec<String>,
+
+    /// The names of the signers unloaded
+    pub signers: Vec<String>,
+}
+
 /// Response for unload signer call
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SignerUnload {
@@ -66,6 +283,26 @@ pub struct SignerUnload {
     pub unloaded: Signer,
 }
 
+/// Response for replace signer call
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerReplace {
+    /// Details of the signer now used for its fingerprint
+    pub signer: Signer,
+
+    /// Names of the external signers removed from the server
+    pub replaced: Vec<String>,
+}
+
+/// Response for the signer matches wallet call
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerMatchesWallet {
+    /// The fingerprint of the signer
+    pub fingerprint: String,
+
+    /// Whether the signer fingerprint appears in the wallet descriptor
+    pub matches: bool,
+}
+
 /// Response of a signer
 #[derive(Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Signer {
@@ -95,6 +332,26 @@ pub struct WalletAddress {
     /// QR code image encoded as uri
     #[serde(skip_serializing_if = "Option::is_none")]
     pub uri_qr: Option<String>,
+
+    /// Every format of the address needed to request a payment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_request: Option<PaymentRequest>,
+}
+
+/// The formats of a receiving address
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PaymentRequest {
+    /// The confidential address
+    pub confidential_address: String,
+
+    /// The explicit address, without the blinding key
+    pub explicit_address: String,
+
+    /// The script pubkey of the address as hex
+    pub script_pubkey: String,
+
+    /// The payment uri of the address
+    pub uri: String,
 }
 
 /// Balance respone
@@ -102,193 +359,909 @@ pub struct WalletAddress {
 pub struct WalletBalance {
     /// A map of the balance of every asset in the wallet
     pub balance: HashMap<String, i64>,
-}
 
-/// PSET response
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct Pset {
-    /// The PSET in base64 format
-    pub pset: String,
-}
+    /// A map from the reissuance tokens in the balance to the asset ids they can reissue.
+    ///
+    /// Present only if requested, it contains only the tokens whose issuance has been seen by the
+    /// wallet or that have been inserted in the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reissuance_tokens: Option<HashMap<String, String>>,
 
-/// Response containing a single signature descriptor
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SignerSinglesigDescriptor {
-    /// The singlesig descriptor
-    pub descriptor: String,
+    /// A map of the balance of every asset split by confirmation status, present only if
+    /// requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending: Option<HashMap<String, BalanceBuckets>>,
 }
 
-/// Response containing a multi signature descriptor
+/// The balance of one of the loaded wallets
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct WalletMultisigDescriptor {
-    /// The multisig descriptor
-    pub descriptor: String,
-}
+pub struct WalletBalanceEntry {
+    /// A map of the balance of every asset in the wallet, missing if it couldn't be computed
+    pub balance: Option<HashMap<String, i64>>,
 
-/// A response containing an xpub with keyorigin
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SignerXpub {
-    /// The xpub with keyorigin prepended (fingerprint+derivation path)
-    pub keyorigin_xpub: String,
+    /// True if the last scan of the wallet succeeded
+    pub synced: bool,
+
+    /// Why the balance couldn't be computed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
-/// The response of a broadcast
+/// The balances of every loaded wallet
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct WalletBroadcast {
-    /// The txid of the transaction just broadacasted
-    pub txid: String,
+pub struct WalletBalanceAll {
+    /// A map from the wallet names to their balance
+    pub wallets: BTreeMap<String, WalletBalanceEntry>,
 }
 
-/// A response of a JSON contract containing asset metadata and validated according to the contract rules
+/// The balance of an asset split by confirmation status
+///
+/// The balance of a wallet split between confirmed and pending outputs
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct AssetContract {
-    /// Entity emitting the asset
-    pub entity: Entity,
-
-    /// Pubkey of the asset issuer, in the 33 bytes format expressed 66 hex chars
-    pub issuer_pubkey: String,
+pub struct WalletConfirmedBalance {
+    /// The height of the tip the wallet is synced to
+    pub tip_height: u32,
 
-    /// Name of the asset
-    pub name: String,
+    /// Confirmations an output needs to be counted as confirmed
+    pub min_confirmations: u32,
 
-    /// Precision of the asset
-    pub precision: u8,
+    /// A map of the balance of every asset in the wallet
+    pub balance: HashMap<String, ConfirmedBalance>,
+}
 
-    /// Ticker of the asset
-    pub ticker: String,
+/// The balance of an asset split between confirmed and pending outputs
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ConfirmedBalance {
+    /// The amount in outputs with at least the requested confirmations
+    pub confirmed: u64,
 
-    /// Version of the contract
-    pub version: u8,
+    /// The amount in unconfirmed outputs or with fewer than the requested confirmations
+    pub pending: u64,
 }
 
-/// Entity issuing the asset
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct Entity {
-    /// Domain of the entity issuing the asset
-    domain: String,
-}
+/// The balance is `confirmed + pending_incoming - pending_outgoing`
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct BalanceBuckets {
+    /// The balance considering only confirmed transactions
+    pub confirmed: u64,
 
-/// Details of a signer (short version)
-///
-/// Intended for use in complex responses,
-/// eg when showing PSET details
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SignerShortDetails {
-    /// The name of the signer
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
+    /// The amount received by unconfirmed transactions
+    pub pending_incoming: u64,
 
-    /// The fingerprint of the signer
-    pub fingerprint: String,
+    /// The amount sent by unconfirmed transactions, fee included
+    pub pending_outgoing: u64,
 }
 
-/// Details of a loaded signer
+/// Whether a signer can sign a PSET of a wallet
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct SignerDetails {
-    /// Signer name
-    pub name: String,
-
-    /// Fingerprint
+pub struct SignerCanSign {
+    /// The signer fingerprint
     pub fingerprint: String,
 
-    /// Full identifier of the signer, of which the fingerprint is a subset
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    /// The signer type, as in signer details
+    #[serde(rename = "type")]
+    pub type_: String,
 
-    /// Master xpub
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub xpub: Option<String>,
+    /// Whether the signer can sign without connecting to it first
+    ///
+    /// External signers are placeholders that never sign, Jade signers known only by their id
+    /// need to be connected.
+    pub available: bool,
 
-    /// Mnemonic
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub mnemonic: Option<String>,
+    /// Whether the signer is one of the signers of the wallet descriptor
+    pub is_wallet_signer: bool,
 
-    /// Signer type
-    #[serde(rename = "type")]
-    pub type_: String,
+    /// Indexes of the inputs with a key of the signer not signed yet
+    pub inputs_to_sign: Vec<u32>,
+
+    /// Indexes of the inputs already signed with a key of the signer
+    pub inputs_signed: Vec<u32>,
+
+    /// True if the signer is available and has inputs to sign
+    pub can_sign: bool,
 }
 
-/// Details of a wallet
+/// What a signer can do, to show the right flow before signing
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct WalletDetails {
-    /// Wallet descriptor
-    pub descriptor: String,
+pub struct SignerCapabilities {
+    /// The signer type, as in signer details
+    pub kind: String,
 
-    /// Type of the wallet // TODO make enum
-    #[serde(rename = "type")]
-    pub type_: String,
+    /// Whether the signer can sign, false for external signers
+    pub can_sign: bool,
 
-    /// Signers of this wallet
-    pub signers: Vec<SignerShortDetails>,
+    /// Whether signing needs a confirmation on a hardware device
+    pub requires_device: bool,
 
-    /// Warnings on this wallet
-    pub warnings: String,
+    /// Whether the signer can sign messages through the server, always false for now since there
+    /// is no method to sign messages
+    pub supports_message_signing: bool,
 }
 
-/// Response to wallet combine
+/// PSET response
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct WalletCombine {
-    /// PSET in base64 format
+pub struct Pset {
+    /// The PSET in base64 format
     pub pset: String,
 }
 
-/// Response containing detail of a PSET
+/// A change output returned to the wallet
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct Issuance {
-    /// Asset id
+pub struct ChangeOutput {
+    /// The output index in the PSET
+    pub vout: u32,
+
+    /// The asset of the change
     pub asset: String,
 
-    /// Token id
-    pub token: String,
+    /// The change amount
+    pub satoshi: u64,
 
-    /// Wheter the issuance is confidential
-    pub is_confidential: bool,
+    /// The change address
+    pub address: String,
 
-    /// Index of the input containing the issuance
-    pub vin: u32,
+    /// The derivation index of the change address on the internal chain
+    pub index: u32,
+}
 
-    /// Number of units of the asset
-    pub asset_satoshi: u64,
+/// Send response
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSendMany {
+    /// The PSET in base64 format
+    pub pset: String,
 
-    /// Number of reissuance token
-    pub token_satoshi: u64,
+    /// The change outputs, one for each asset with a remainder
+    pub change: Vec<ChangeOutput>,
 
-    /// Previous output txid corresponding to the issuance input
-    pub prev_txid: String,
+    /// The fee of the transaction
+    pub fee: u64,
 
-    /// Previous output vout corresponding to the issuance input
-    pub prev_vout: u32,
+    /// The fee rate of the transaction once signed, in the same unit of the `fee_rate` of the
+    /// request
+    pub fee_rate: f32,
 }
 
-/// Details about a reissuance
+/// Consolidation response
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct Reissuance {
-    /// The asset id
-    pub asset: String,
+pub struct WalletConsolidate {
+    /// The PSET in base64 format
+    pub pset: String,
 
-    /// The token id,
-    pub token: String,
+    /// The number of utxos spent
+    pub consolidated: u32,
 
-    /// Wheter the reissuance is confidential
-    pub is_confidential: bool,
+    /// The number of L-BTC utxos left untouched because of `max_inputs` or because locked
+    pub remaining: u32,
 
-    /// Index of the input containing the reissuance
-    pub vin: u32,
+    /// The output receiving the consolidated amount
+    pub output: ConsolidatedOutput,
 
-    /// Number of units of the asset reissued
-    pub asset_satoshi: u64,
+    /// The fee of the transaction
+    pub fee: u64,
 }
 
-/// Details of a PSET
+/// The maximum amount of an asset the wallet can send
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-pub struct WalletPsetDetails {
-    /// Signatures contained in the PSET
-    pub has_signatures_from: Vec<SignerShortDetails>,
+pub struct WalletMaxSpendable {
+    /// The asset id
+    pub asset: String,
 
-    /// Signature required to spend but missing in the PSET
-    pub missing_signatures_from: Vec<SignerShortDetails>,
+    /// The amount that can be sent, for the policy asset the fee is already subtracted
+    pub satoshi: u64,
 
-    /// Net balance of the assets for the point of view of the given wallet
+    /// The fee of the transaction sending the amount, always paid in the policy asset
+    pub fee: u64,
+}
+
+/// A transaction spending all the funds of a private key to a wallet, signed and finalized, not
+/// broadcast
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SweepKey {
+    /// The finalized transaction in hex
+    pub tx: String,
+
+    /// The txid of the transaction
+    pub txid: String,
+
+    /// The address of the private key where the funds were found
+    pub swept_address: String,
+
+    /// The script type of the swept address, "wpkh" or "shwpkh"
+    pub script_type: String,
+
+    /// The wallet address receiving the funds
+    pub address: String,
+
+    /// The number of utxos spent
+    pub utxos: u32,
+
+    /// A map of the amount received by the wallet for every asset, for the policy asset the fee
+    /// is already subtracted
+    pub satoshi: HashMap<String, u64>,
+
+    /// The fee of the transaction
+    pub fee: u64,
+}
+
+/// The single output of a consolidation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConsolidatedOutput {
+    /// The output index in the PSET
+    pub vout: u32,
+
+    /// The receiving address
+    pub address: String,
+
+    /// The sum of the spent utxos minus the fee
+    pub satoshi: u64,
+}
+
+/// The validation result of an addressee
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddresseeValidation {
+    /// The position of the addressee in the request
+    pub index: u32,
+
+    /// Whether the addressee can be used to send
+    pub valid: bool,
+
+    /// Why the addressee is not valid
+    pub error: Option<String>,
+}
+
+/// Response containing the validation of each addressee
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletValidateAddressees {
+    /// Whether all the addressees are valid
+    pub valid: bool,
+
+    /// The validation result for each addressee, in the request order
+    pub addressees: Vec<AddresseeValidation>,
+}
+
+/// Issuance response
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletIssue {
+    /// The PSET in base64 format
+    pub pset: String,
+
+    /// Whether the issued asset and token amounts are confidential
+    pub blinded_issuance: bool,
+
+    /// The hash of the contract committed in the asset id, if a contract was given
+    pub contract_hash: Option<String>,
+}
+
+/// Response containing a single signature descriptor
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerSinglesigDescriptor {
+    /// The singlesig descriptor
+    pub descriptor: String,
+}
+
+/// A single signature descriptor with the variants used to create it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SinglesigDescriptorEntry {
+    /// The singlesig kind, "wpkh" or "shwpkh"
+    pub singlesig_kind: String,
+
+    /// The descriptor blinding key, "slip77" or "elip151"
+    pub descriptor_blinding_key: String,
+
+    /// The singlesig descriptor
+    pub descriptor: String,
+}
+
+/// Response containing every standard single signature descriptor of a signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerSinglesigDescriptorSet {
+    /// A descriptor for every combination of singlesig kind and deterministic descriptor
+    /// blinding key
+    pub descriptors: Vec<SinglesigDescriptorEntry>,
+}
+
+/// Response containing a multi signature descriptor
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletMultisigDescriptor {
+    /// The multisig descriptor
+    pub descriptor: String,
+
+    /// A deterministic name derived from the descriptor, compatible with Jade naming rules
+    ///
+    /// Load the wallet with this name so that registering it on Jade uses the same identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jade_multisig_name: Option<String>,
+
+    /// Possible mistakes in the keys, which don't prevent creating the descriptor
+    pub warnings: Vec<String>,
+}
+
+/// Response containing the descriptor compiled from a policy
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCompilePolicy {
+    /// The checksummed descriptor, ready to be loaded
+    pub descriptor: String,
+}
+
+/// Response containing the checksum of a descriptor
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDescriptorChecksum {
+    /// The checksum of the descriptor
+    pub checksum: String,
+
+    /// The descriptor followed by "#" and its checksum
+    pub descriptor: String,
+}
+
+/// The keys of a descriptor are for the server network
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCheckNetwork {
+    /// The server network
+    pub network: String,
+
+    /// Number of extended keys checked, single keys have no network
+    pub xpubs: u32,
+}
+
+/// The fee of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPsetFee {
+    /// The amount of the explicit fee output
+    pub fee: u64,
+
+    /// The virtual size of the transaction as currently in the PSET.
+    ///
+    /// If the PSET is not finalized the signatures are not accounted for
+    pub vsize: usize,
+
+    /// The fee rate in sats per kvb resulting from the fee and the virtual size
+    pub fee_rate: f32,
+
+    /// Whether every input of the PSET is finalized, so that the virtual size is the final one
+    pub finalized: bool,
+
+    /// Issues found in the fee output
+    pub warnings: Vec<String>,
+}
+
+/// Response containing a PSET re-serialized with the requested version
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConvertPsetVersion {
+    /// The PSET in base64
+    pub pset: String,
+
+    /// The version of the given PSET
+    pub from_version: u32,
+
+    /// The version of the returned PSET
+    pub version: u32,
+}
+
+/// A PSET without signatures
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletStripSignatures {
+    /// The PSET in base64, with the same inputs and outputs and no signatures
+    pub pset: String,
+
+    /// The number of partial signatures removed
+    pub removed: u32,
+
+    /// The number of inputs that were finalized, their final scripts are removed too
+    pub finalized_cleared: u32,
+}
+
+/// Response containing a descriptor blinding key converted to another representation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConvertBlindingKey {
+    /// The blinding key in the requested representation
+    pub blinding_key: String,
+
+    /// The confidential descriptor using the converted key, if a descriptor was supplied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptor: Option<String>,
+}
+
+/// A response containing an xpub with keyorigin
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerXpub {
+    /// The xpub with keyorigin prepended (fingerprint+derivation path)
+    pub keyorigin_xpub: String,
+}
+
+/// An address displayed on, and matching the one of, a hardware signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConfirmAddress {
+    /// The confirmed address
+    pub address: String,
+
+    /// The index of the derivation of the given address
+    pub index: u32,
+}
+
+/// The address derived by the wallet compared with the one computed by a hardware signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifyDeviceAddress {
+    /// The confidential address derived by the wallet
+    pub address: String,
+
+    /// The explicit (unconfidential) form of the address
+    pub explicit_address: String,
+
+    /// The index of the derivation of the address
+    pub index: u32,
+
+    /// The address computed and displayed by the device, none if the signer has no screen
+    pub device_address: Option<String>,
+
+    /// Whether the device address matches the wallet one, none if the signer has no screen
+    pub matches: Option<bool>,
+}
+
+/// The response of a broadcast
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletBroadcast {
+    /// The txid of the transaction just broadacasted
+    pub txid: String,
+
+    /// True if the backend already knew the transaction, so it was already in mempool or in chain
+    pub already_known: bool,
+
+    /// The fee of the transaction, detected from its fee output
+    pub fee: Option<u64>,
+
+    /// The virtual size of the finalized transaction
+    pub vsize: usize,
+
+    /// The fee rate in sats per kvb resulting from the fee and the virtual size
+    pub fee_rate: Option<f32>,
+
+    /// Issues with the fee output of the transaction
+    pub warnings: Vec<String>,
+
+    /// The height of the block confirming the transaction, if the client waited for
+    /// confirmations after the broadcast, polling `wallet_confirmations`
+    ///
+    /// The server returns immediately after the broadcast, so it never sets it.
+    pub confirmation_height: Option<u32>,
+
+    /// True if the client waited for confirmations and they were not reached before the timeout
+    pub wait_timed_out: bool,
+}
+
+/// The outcome of the load of one of the signers of a batch
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerLoadEntry {
+    /// The index of the signer in the request
+    pub index: u32,
+
+    /// The signer name
+    pub name: String,
+
+    /// The loaded signer, missing if it couldn't be loaded
+    pub signer: Option<Signer>,
+
+    /// Why the signer couldn't be loaded, missing on success
+    pub error: Option<String>,
+
+    /// The error code, as the one returned by the single signer load, missing on success
+    pub error_code: Option<i64>,
+}
+
+/// The outcome of the load of a batch of signers
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerLoadMany {
+    /// The result of every signer, in the order of the request
+    pub results: Vec<SignerLoadEntry>,
+
+    /// The number of signers loaded
+    pub loaded: u32,
+
+    /// The number of signers that couldn't be loaded
+    pub failed: u32,
+}
+
+/// The outcome of the broadcast of one of the PSETs of a batch
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchBroadcastEntry {
+    /// The index of the PSET in the request
+    pub index: u32,
+
+    /// The txid of the transaction, missing if the PSET couldn't be finalized
+    pub txid: Option<String>,
+
+    /// True if the backend already knew the transaction, so it was already in mempool or in chain
+    pub already_known: bool,
+
+    /// Why the PSET couldn't be finalized or broadcast, missing on success
+    pub error: Option<String>,
+}
+
+/// The outcome of the broadcast of a batch of PSETs
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletBroadcastBatch {
+    /// The result of every PSET, in the order of the request
+    pub results: Vec<BatchBroadcastEntry>,
+
+    /// The number of transactions broadcast, or already known
+    pub broadcast: u32,
+
+    /// The number of PSETs that failed
+    pub failed: u32,
+}
+
+/// A transaction signed by local signers and finalized, not broadcast
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletLocalMultisigSend {
+    /// The finalized transaction in hex
+    pub tx: String,
+
+    /// The txid of the transaction
+    pub txid: String,
+
+    /// The fee paid by the transaction in satoshi
+    pub fee: u64,
+}
+
+/// The outcome of broadcasting again the unconfirmed transactions of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletRebroadcastPending {
+    /// The txids of the transactions accepted by the backend, or already known by it
+    pub rebroadcast: Vec<String>,
+
+    /// The transactions rejected by the backend
+    pub failed: Vec<RebroadcastFailure>,
+}
+
+/// An unconfirmed transaction rejected by the backend
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RebroadcastFailure {
+    /// The txid of the transaction
+    pub txid: String,
+
+    /// Why the backend rejected it, e.g. because an input was spent by another transaction
+    pub error: String,
+}
+
+/// A response of a JSON contract containing asset metadata and validated according to the contract rules
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AssetContract {
+    /// Entity emitting the asset
+    pub entity: Entity,
+
+    /// Pubkey of the asset issuer, in the 33 bytes format expressed 66 hex chars
+    pub issuer_pubkey: String,
+
+    /// Name of the asset
+    pub name: String,
+
+    /// Precision of the asset
+    pub precision: u8,
+
+    /// Ticker of the asset
+    pub ticker: String,
+
+    /// Version of the contract
+    pub version: u8,
+}
+
+/// Entity issuing the asset
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Entity {
+    /// Domain of the entity issuing the asset
+    domain: String,
+}
+
+/// Details of a signer (short version)
+///
+/// Intended for use in complex responses,
+/// eg when showing PSET details
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerShortDetails {
+    /// The name of the signer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The fingerprint of the signer
+    pub fingerprint: String,
+
+    /// The extended public key of the signer in the descriptor
+    ///
+    /// Present only in wallet details and if the descriptor key is an xpub
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xpub: Option<String>,
+
+    /// The extended public key of the signer with its key origin, in the form accepted by
+    /// multisig descriptor generation
+    ///
+    /// Present only in wallet details and if the descriptor key is an xpub with key origin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyorigin_xpub: Option<String>,
+}
+
+/// Details of a loaded signer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerDetails {
+    /// Signer name
+    pub name: String,
+
+    /// Fingerprint
+    pub fingerprint: String,
+
+    /// Full identifier of the signer, of which the fingerprint is a subset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Master xpub
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xpub: Option<String>,
+
+    /// Mnemonic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+
+    /// Signer type
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Derivation standards of which the signer can provide the xpub, like "bip84"
+    pub derivation_standards: Vec<String>,
+
+    /// For Jade, whether it answers at the moment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connected: Option<bool>,
+
+    /// For a connected Jade, the firmware version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jade_version: Option<String>,
+
+    /// For a connected Jade, its state, like "READY" or "LOCKED"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jade_state: Option<String>,
+}
+
+/// Descriptors of a loaded wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDescriptor {
+    /// The descriptor of the wallet as loaded
+    pub descriptor: String,
+
+    /// The descriptor of the external chain, used for receiving
+    pub external: String,
+
+    /// The descriptor of the internal chain, used for change
+    ///
+    /// Returned if requested and if the wallet descriptor is multipath
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal: Option<String>,
+}
+
+/// How far a wallet has been synced compared to the blockchain tip
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSyncProgress {
+    /// The height up to which the wallet has been synced, 0 if never synced
+    ///
+    /// While the wallet is being scanned, the height of the wallet transactions downloaded so
+    /// far, which grows up to the tip during the scan
+    pub scanned_height: u32,
+
+    /// The height of the blockchain tip seen in the last scan loop, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tip_height: Option<u32>,
+
+    /// The percentage of the blockchain synced, from 0 to 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f32>,
+}
+
+/// Result of a request to stop the scan of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCancelSync {
+    /// Whether the wallet was being scanned and its scan has been signaled to stop
+    ///
+    /// A stopped scan is discarded, the wallet keeps the data of the previous scans
+    pub cancelled: bool,
+}
+
+/// The Electrum server a wallet is now scanned with
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSetBackend {
+    /// The wallet name
+    pub name: String,
+
+    /// The Electrum server url
+    pub electrum_url: String,
+
+    /// The height of the blockchain tip according to the Electrum server
+    pub tip_height: u32,
+}
+
+/// Details of a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDetails {
+    /// Wallet descriptor
+    pub descriptor: String,
+
+    /// Type of the wallet // TODO make enum
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Whether the descriptor has a wildcard, if false the wallet has a single address
+    pub ranged: bool,
+
+    /// Number of distinct assets with a positive balance
+    pub assets_held: usize,
+
+    /// Whether the wallet history contains an issuance of the wallet
+    pub has_issued: bool,
+
+    /// Whether the wallet holds reissuance tokens of assets issued in its history
+    pub holds_reissuance_tokens: bool,
+
+    /// Signers of this wallet
+    pub signers: Vec<SignerShortDetails>,
+
+    /// Warnings on this wallet
+    pub warnings: String,
+}
+
+/// Response to wallet combine
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCombine {
+    /// PSET in base64 format
+    pub pset: String,
+}
+
+/// Response containing detail of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Issuance {
+    /// Asset id
+    pub asset: String,
+
+    /// Token id
+    pub token: String,
+
+    /// Wheter the issuance is confidential
+    pub is_confidential: bool,
+
+    /// Index of the input containing the issuance
+    pub vin: u32,
+
+    /// Number of units of the asset
+    pub asset_satoshi: u64,
+
+    /// Number of reissuance token
+    pub token_satoshi: u64,
+
+    /// Previous output txid corresponding to the issuance input
+    pub prev_txid: String,
+
+    /// Previous output vout corresponding to the issuance input
+    pub prev_vout: u32,
+
+    /// The contract of the issued asset, if embedded in the PSET and committing to the asset id
+    pub contract: Option<AssetContract>,
+
+    /// The asset name from the embedded contract
+    pub name: Option<String>,
+
+    /// The asset ticker from the embedded contract
+    pub ticker: Option<String>,
+}
+
+/// Details about a reissuance
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Reissuance {
+    /// The asset id
+    pub asset: String,
+
+    /// The token id,
+    pub token: String,
+
+    /// Wheter the reissuance is confidential
+    pub is_confidential: bool,
+
+    /// Index of the input containing the reissuance
+    pub vin: u32,
+
+    /// Number of units of the asset reissued
+    pub asset_satoshi: u64,
+}
+
+/// A privacy issue of a transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyFinding {
+    /// How much the issue hurts privacy: "low", "medium" or "high"
+    pub severity: String,
+
+    /// The kind of issue, like "address_reuse"
+    pub kind: String,
+
+    /// Description of the issue
+    pub message: String,
+
+    /// The input concerned, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<usize>,
+
+    /// The output concerned, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<usize>,
+}
+
+/// Privacy issues of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPrivacyAnalysis {
+    /// The issues found, empty if none
+    pub findings: Vec<PrivacyFinding>,
+}
+
+/// The state of a PSET input in the current view of the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PsetInputStatus {
+    /// The index of the input in the PSET
+    pub index: u32,
+
+    /// The transaction id of the output spent by the input
+    pub txid: String,
+
+    /// The output index of the output spent by the input
+    pub vout: u32,
+
+    /// "unspent" if the output can still be spent, "spent" if already spent by another
+    /// transaction, "unknown" if it's not an output of the wallet
+    pub status: String,
+}
+
+/// Whether a PSET can still be broadcast as far as its inputs are concerned
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCheckPsetSpendable {
+    /// True if every input is an unspent output of the wallet
+    pub spendable: bool,
+
+    /// The status of every input
+    pub inputs: Vec<PsetInputStatus>,
+
+    /// The indexes of the inputs which are not unspent outputs of the wallet
+    pub stale: Vec<u32>,
+}
+
+/// The projected size and fee of a PSET once every input is signed
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletMultisigFeeEstimate {
+    /// The expected virtual size of the finalized transaction, assuming the inputs not finalized
+    /// yet are satisfied with the largest witness of the descriptor
+    pub vsize: usize,
+
+    /// The fee paid by the PSET
+    pub fee: u64,
+
+    /// The fee rate the PSET pays once finalized, in the same unit of the `fee_rate` of the
+    /// methods creating transactions
+    pub fee_rate: f32,
+
+    /// The fee rate the wallet uses when creating transactions
+    pub wallet_fee_rate: f32,
+
+    /// The fee needed for the finalized transaction at the fee rate of the wallet
+    pub projected_fee: u64,
+
+    /// True if the fee paid is at least the projected fee
+    pub adequate: bool,
+}
+
+/// Details of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPsetDetails {
+    /// Signatures contained in the PSET
+    pub has_signatures_from: Vec<SignerShortDetails>,
+
+    /// Signature required to spend but missing in the PSET
+    pub missing_signatures_from: Vec<SignerShortDetails>,
+
+    /// Signatures present compared to the ones required by the wallet policy
+    pub signing_progress: SigningProgress,
+
+    /// Net balance of the assets for the point of view of the given wallet
     pub balance: HashMap<String, i64>,
 
     /// Fee of the transaction
@@ -304,6 +1277,151 @@ pub struct WalletPsetDetails {
     pub warnings: String,
 }
 
+/// Spending branches satisfied by the wallet inputs of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletCheckSpendPath {
+    /// Whether every wallet input satisfies at least one branch
+    pub is_satisfied: bool,
+
+    /// The spending branches of each input of the wallet
+    pub inputs: Vec<InputSpendPath>,
+}
+
+/// Spending branches of an input of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InputSpendPath {
+    /// The input index in the PSET
+    pub vin: u32,
+
+    /// The alternative branches of the descriptor policy, a single one if there are no
+    /// alternatives
+    pub branches: Vec<SpendBranch>,
+
+    /// Index in `branches` of the first satisfied branch, missing if none is satisfied
+    pub satisfied_branch: Option<u32>,
+}
+
+/// An alternative branch of a descriptor policy
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SpendBranch {
+    /// The semantic policy of the branch, keys are shown with their fingerprint,
+    /// e.g. "and(pk(73c5da0a),older(144))"
+    pub policy: String,
+
+    /// Whether the signatures, sequence and locktime of the input satisfy the branch
+    pub satisfied: bool,
+}
+
+/// Signing progress of the inputs of a PSET spending from a wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SigningProgress {
+    /// Signatures required for each input, derived from the wallet descriptor.
+    ///
+    /// Missing if the descriptor doesn't have a single threshold, e.g. with complex policies
+    pub signatures_required: Option<u32>,
+
+    /// Whether every wallet input has the required signatures
+    pub is_complete: bool,
+
+    /// Percentage of required signatures present across the wallet inputs, from 0 to 100
+    pub percentage: Option<u32>,
+
+    /// Progress of each input of the wallet
+    pub inputs: Vec<InputSigningProgress>,
+}
+
+/// Signing progress of an input of a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InputSigningProgress {
+    /// The input index in the PSET
+    pub vin: u32,
+
+    /// Signatures required to spend the input
+    pub signatures_required: Option<u32>,
+
+    /// Signatures present in the input
+    pub signatures_present: u32,
+
+    /// Whether the input has the required signatures
+    pub is_complete: bool,
+}
+
+/// An output of a PSET in a signing request
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SigningRequestOutput {
+    /// The output index in the PSET
+    pub vout: u32,
+
+    /// The address receiving the output, missing for the fee and for outputs without an address
+    pub address: Option<String>,
+
+    /// The script pubkey of the output in hex
+    pub script_pubkey: String,
+
+    /// The asset of the output, if known by the wallet
+    pub asset: Option<String>,
+
+    /// The amount of the output, if known by the wallet
+    pub satoshi: Option<u64>,
+
+    /// Whether the output is the fee
+    pub is_fee: bool,
+
+    /// Whether the output belongs to the wallet
+    pub is_mine: bool,
+}
+
+/// A self-contained description of a PSET to sign, which an offline signer can verify without
+/// network access
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSigningRequest {
+    /// The version of the signing request format
+    pub version: u32,
+
+    /// The network of the wallet
+    pub network: String,
+
+    /// The descriptor of the wallet spending in the PSET
+    pub descriptor: String,
+
+    /// The PSET in base64 to sign
+    pub pset: String,
+
+    /// The outputs of the PSET, with the amounts and assets the wallet can compute
+    pub outputs: Vec<SigningRequestOutput>,
+
+    /// Net balance of the assets for the point of view of the wallet
+    pub balance: BTreeMap<String, i64>,
+
+    /// Fee of the transaction
+    pub fee: u64,
+
+    /// Fingerprints of the signers of the wallet
+    pub fingerprints: Vec<String>,
+
+    /// Fingerprints of the signers whose signature is required but missing in the PSET
+    pub missing_signatures_from: Vec<String>,
+}
+
+/// A signing session, tracking the signatures collected for a PSET
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSession {
+    /// The session identifier
+    pub session_id: String,
+
+    /// The wallet name
+    pub name: String,
+
+    /// The PSET containing all the signatures collected so far
+    pub pset: String,
+
+    /// Signatures contained in the PSET
+    pub has_signatures_from: Vec<SignerShortDetails>,
+
+    /// Signature required to spend but missing in the PSET
+    pub missing_signatures_from: Vec<SignerShortDetails>,
+}
+
 /// Unspent Transaction Output
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Utxo {
@@ -326,11 +1444,196 @@ pub struct Utxo {
     pub value: u64,
 }
 
+/// Unspent Transaction Output in the format of the Elements Core `listunspent` call
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CoreUtxo {
+    /// Transction ID
+    pub txid: String,
+
+    /// Output index
+    pub vout: u32,
+
+    /// The wallet address of the output
+    pub address: String,
+
+    /// Output script pubkey
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: String,
+
+    /// Output value in BTC
+    pub amount: f64,
+
+    /// Output asset
+    pub asset: String,
+
+    /// Value blinding factor
+    pub amountblinder: String,
+
+    /// Asset blinding factor
+    pub assetblinder: String,
+
+    /// Number of confirmations, 0 if unconfirmed
+    pub confirmations: u32,
+
+    /// Whether the output can be spent by the wallet
+    pub spendable: bool,
+
+    /// Whether the wallet knows how to spend the output
+    pub solvable: bool,
+
+    /// Whether the output is considered safe to spend, that is confirmed
+    pub safe: bool,
+}
+
+/// An address of the wallet that received funds
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UsedAddress {
+    /// The address
+    pub address: String,
+
+    /// The index of the derivation of the address
+    pub index: u32,
+
+    /// True for change addresses
+    pub internal: bool,
+
+    /// The balance per asset of the unspent outputs of the address confirmed in a block
+    pub confirmed: HashMap<String, u64>,
+
+    /// The balance per asset of the unspent outputs of the address still in mempool
+    pub unconfirmed: HashMap<String, u64>,
+
+    /// The number of transactions paying to the address
+    pub tx_count: usize,
+
+    /// Memo
+    pub memo: String,
+}
+
+/// The addresses of the wallet that received funds
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletUsedAddresses {
+    /// The used addresses, external ones first, ordered by derivation index
+    pub addresses: Vec<UsedAddress>,
+}
+
+/// An output of the wallet worth less than the fee needed to spend it
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DustUtxo {
+    /// Transction ID
+    pub txid: String,
+
+    /// Output index
+    pub vout: u32,
+
+    /// Output value in satoshi
+    pub value: u64,
+
+    /// The fee needed to spend the output
+    pub spend_cost: u64,
+}
+
+/// The dust outputs of an asset
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DustAsset {
+    /// The dust outputs, smallest first
+    pub utxos: Vec<DustUtxo>,
+
+    /// The sum of the values of the dust outputs
+    pub total: u64,
+}
+
+/// The outputs of the wallet worth less than the fee needed to spend them
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDustReport {
+    /// The fee rate used to compute the cost of spending the outputs
+    pub fee_rate: f32,
+
+    /// The dust outputs by asset
+    ///
+    /// Only outputs of the policy asset are compared with the fee, which is paid in the policy
+    /// asset
+    pub assets: BTreeMap<String, DustAsset>,
+
+    /// The sum of the values of all the dust outputs
+    pub total: u64,
+}
+
+/// A derivation index that received funds
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DiscoveredIndex {
+    /// The derivation index
+    pub index: u32,
+
+    /// The address at the derivation index
+    pub address: String,
+
+    /// The number of transactions involving the address
+    pub txs: usize,
+}
+
+/// The derivation indexes that received funds of a chain of the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DiscoveredChain {
+    /// True for the change chain
+    pub internal: bool,
+
+    /// The used derivation indexes, in increasing order
+    pub used: Vec<DiscoveredIndex>,
+
+    /// The number of derivation indexes checked
+    pub scanned: u32,
+
+    /// True if the scan stopped at the maximum derivation index before finding a gap of unused
+    /// addresses, so further indexes may still have been used
+    pub max_index_reached: bool,
+}
+
+/// The result of a deep scan of the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDiscoverFunds {
+    /// The gap limit used for the scan
+    pub max_gap: u32,
+
+    /// The chains of the wallet, external first
+    pub chains: Vec<DiscoveredChain>,
+}
+
+/// An output locked
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletLockUtxo {
+    /// The unix time in seconds when the lock expires
+    pub expires_at: u64,
+}
+
+/// An output unlocked
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletUnlockUtxo {
+    /// False if the output was not locked or the lock was expired
+    pub unlocked: bool,
+}
+
+/// The number of confirmations of a wallet transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletConfirmations {
+    /// The number of confirmations, 0 if the transaction is unconfirmed, none if the transaction
+    /// is not in the wallet
+    pub confirmations: Option<u32>,
+
+    /// The height of the block confirming the transaction, none if it's unconfirmed or not in
+    /// the wallet
+    pub height: Option<u32>,
+}
+
 /// Wallet unspent transaction outputs
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WalletUtxos {
     /// UTXOs
     pub utxos: Vec<Utxo>,
+
+    /// UTXOs in the Elements Core `listunspent` format, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub core: Option<Vec<CoreUtxo>>,
 }
 
 /// Transaction
@@ -376,6 +1679,141 @@ pub struct WalletTx {
     pub tx: String,
 }
 
+/// A data (OP_RETURN) output of a transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DataOutput {
+    /// The output index
+    pub vout: u32,
+
+    /// The output script in hex
+    pub script: String,
+
+    /// The data pushed after the OP_RETURN in hex, concatenated
+    pub data: String,
+
+    /// The interpretation of the output: "burn" if it has no data, "text" if the data is
+    /// printable text, "data" otherwise
+    pub kind: String,
+
+    /// The data decoded as text, if printable
+    pub text: Option<String>,
+
+    /// The asset of the output, if explicit
+    pub asset: Option<String>,
+
+    /// The amount of the output, if explicit
+    pub satoshi: Option<u64>,
+}
+
+/// The data outputs of a transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletDecodeDataOutputs {
+    /// The data outputs, the OP_RETURN ones
+    pub outputs: Vec<DataOutput>,
+}
+
+/// The secrets of a transaction output known by the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct OutputBlindingFactors {
+    /// The output index in the transaction
+    pub vout: u32,
+
+    /// The asset of the output
+    pub asset: String,
+
+    /// The amount of the output
+    pub satoshi: u64,
+
+    /// The asset blinding factor, all zeros if the asset is explicit
+    pub asset_blinder: String,
+
+    /// The value blinding factor, all zeros if the amount is explicit
+    pub value_blinder: String,
+}
+
+/// What is needed to load the wallet as watch-only elsewhere
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletExportWatchOnly {
+    /// The server network
+    pub network: String,
+
+    /// The descriptor without the blinding key
+    pub descriptor: String,
+
+    /// The descriptor blinding key
+    pub blinding_key: String,
+
+    /// The confidential descriptor, which can be loaded as is
+    pub confidential_descriptor: String,
+
+    /// The height of the first confirmed wallet transaction, a scan can start from there.
+    ///
+    /// Missing if the wallet has no confirmed transactions
+    pub birthday_height: Option<u32>,
+}
+
+/// The wallet in the format another wallet software imports
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletExportTo {
+    /// The wallet software the content is for
+    pub target: String,
+
+    /// The format of the content: "json" or "text"
+    pub format: String,
+
+    /// What to import in the wallet software, as is or saved to a file
+    ///
+    /// - "green": the confidential descriptors, one per line
+    /// - "core": the argument of `importdescriptors`
+    /// - "generic": the network, the confidential descriptor, the gap limit and the birthday
+    ///   height
+    pub content: String,
+}
+
+/// The transaction issuing an asset of the wallet
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletIssuanceTx {
+    /// The asset id
+    pub asset: String,
+
+    /// The reissuance token id
+    pub token: String,
+
+    /// The id of the issuance transaction
+    pub txid: String,
+
+    /// Index of the input containing the issuance
+    pub vin: u32,
+
+    /// The height of the issuance transaction, missing if unconfirmed
+    pub height: Option<u32>,
+
+    /// Number of units of the asset issued, missing if the issuance is confidential
+    pub asset_satoshi: Option<u64>,
+
+    /// Number of reissuance tokens issued, missing if the issuance is confidential
+    pub token_satoshi: Option<u64>,
+
+    /// Number of reissuance tokens held by the wallet
+    pub token_balance: u64,
+
+    /// Whether the wallet can reissue the asset, that is it holds a reissuance token
+    pub holds_token: bool,
+
+    /// The ids of the wallet transactions reissuing the asset
+    pub reissuance_txids: Vec<String>,
+}
+
+/// Response containing the blinding factors of the wallet outputs of a transaction
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WalletBlindingFactors {
+    /// Transaction ID
+    pub txid: String,
+
+    /// The outputs of the transaction belonging to the wallet
+    pub outputs: Vec<OutputBlindingFactors>,
+}
+
 /// Details of an asset
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AssetDetails {
@@ -384,6 +1822,32 @@ pub struct AssetDetails {
 
     /// Ticker of the asset
     pub ticker: String,
+
+    /// True if the asset registry couldn't be reached, in this case name and ticker are the
+    /// asset id
+    pub metadata_unavailable: bool,
+}
+
+/// Metadata of an asset as committed in its contract at issuance
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AssetMetadata {
+    /// The asset identifier (32 bytes as 64 hex chars)
+    pub asset_id: String,
+
+    /// Name of the asset
+    pub name: String,
+
+    /// Ticker of the asset
+    pub ticker: String,
+
+    /// Precision of the asset
+    pub precision: u8,
+
+    /// Domain of the issuer
+    pub domain: String,
+
+    /// Pubkey of the asset issuer, in the 33 bytes format expressed 66 hex chars
+    pub issuer_pubkey: String,
 }
 
 /// Asset details
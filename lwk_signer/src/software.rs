@@ -57,6 +57,12 @@ pub struct SwSigner {
     pub(crate) mnemonic: Option<Mnemonic>,
 }
 
+impl Drop for SwSigner {
+    fn drop(&mut self) {
+        self.xprv.private_key.non_secure_erase();
+    }
+}
+
 impl core::fmt::Debug for SwSigner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Signer({})", self.fingerprint())
@@ -170,10 +170,12 @@ impl JsonRpcServer {
                         }
                         tiny_http::Method::Post => {
                             // validate/parse the jsonrpc POST request
+                            let mut is_notification = false;
                             let response = match validate_jsonrpc_request(&mut http_request) {
                                 Ok(request) => {
                                     // handle the request
                                     let id = request.id.clone();
+                                    is_notification = id.is_none();
                                     match handle_jsonrpc_request(
                                         request,
                                         state.clone(),
@@ -193,10 +195,21 @@ impl JsonRpcServer {
                                 }
                             };
 
-                            // send the response
-                            if let Err(err) =
+                            if is_notification {
+                                // the server must not reply to a notification (a request without
+                                // id), errors included
+                                if response.is_error() {
+                                    tracing::debug!("notification error: {:?}", response.error);
+                                }
+                                let mut http_response = HttpResponse::empty(204);
+                                for header in config.headers.clone().into_iter() {
+                                    http_response.add_header(header);
+                                }
+                                send_http_response(http_request, http_response, "notification");
+                            } else if let Err(err) =
                                 send_jsonrpc_response(http_request, response, &config.headers)
                             {
+                                // send the response
                                 tracing::error!("send_response error: {}", err);
                             }
                         }
@@ -286,7 +299,15 @@ fn validate_jsonrpc_request(http_request: &mut tiny_http::Request) -> Result<Req
     let mut s = String::new(); // todo: performance
     http_request.as_reader().read_to_string(&mut s)?;
 
-    let request: Request = serde_json::from_str(&s)?;
+    let mut request: Request = serde_json::from_str(&s)?;
+
+    // the token is optional, it's up to the method handler to refuse requests without it
+    request.auth_token = http_request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().trim().strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string());
 
     Ok(request)
 }
@@ -341,9 +362,21 @@ fn send_jsonrpc_response(
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Request {
     pub jsonrpc: String,
+
+    /// `None` if the id is missing, making the request a notification, an explicit `null` id is
+    /// [`Id::Null`] and the request is replied
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub id: Option<Id>,
     pub method: String,
     pub params: Option<Value>,
+
+    /// The token given in the `Authorization: Bearer <token>` HTTP header, if any
+    #[serde(skip)]
+    pub auth_token: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -420,6 +453,16 @@ impl Display for RpcError {
 pub enum Id {
     Number(u64),
     String(String),
+    Null,
+}
+
+/// Called only if the `id` field is present, so that `null` is not confused with a missing id
+fn deserialize_present_id<'de, D>(deserializer: D) -> Result<Option<Id>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let id: Option<Id> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(Some(id.unwrap_or(Id::Null)))
 }
 
 #[cfg(test)]
@@ -565,6 +608,53 @@ mod test {
         assert!(resp.bytes().unwrap().is_empty());
     }
 
+    #[test]
+    fn notification() {
+        let addr = "127.0.0.1:0";
+        let server = Server::http(addr).unwrap();
+        let state = Arc::new(Mutex::new(()));
+        let rpc = JsonRpcServer::new(server, Config::default(), state, process);
+        let port = rpc.port().unwrap();
+        let url = format!("http://127.0.0.1:{}", port);
+
+        let client = reqwest::blocking::Client::builder().build().unwrap();
+        let request = json!({"jsonrpc": "2.0", "method": "echo", "params": "hello"});
+        let resp = client.post(&url).json(&request).send().unwrap();
+        assert_eq!(resp.status(), 204);
+        assert!(resp.bytes().unwrap().is_empty());
+
+        // notifications are not replied even if they fail
+        let request = json!({"jsonrpc": "2.0", "method": "rpc.reserved"});
+        let resp = client.post(&url).json(&request).send().unwrap();
+        assert_eq!(resp.status(), 204);
+        assert!(resp.bytes().unwrap().is_empty());
+
+        let request = json!({"jsonrpc": "2.0", "id": 1, "method": "echo", "params": "hello"});
+        let resp = client.post(&url).json(&request).send().unwrap();
+        assert_eq!(resp.status(), 200);
+        let response: Response = resp.json().unwrap();
+        assert_eq!(response.result, Some(json!("hello")));
+
+        // a null id is not a notification
+        let request = json!({"jsonrpc": "2.0", "id": null, "method": "echo", "params": "hello"});
+        let resp = client.post(&url).json(&request).send().unwrap();
+        assert_eq!(resp.status(), 200);
+        let response: Value = resp.json().unwrap();
+        assert_eq!(
+            response,
+            json!({"jsonrpc": "2.0", "id": null, "result": "hello"})
+        );
+
+        let request: Request =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "id": null, "method": "echo"}))
+                .unwrap();
+        assert!(matches!(request.id, Some(Id::Null)));
+        let request: Request =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "method": "echo"})).unwrap();
+        assert!(request.id.is_none());
+        assert!(!serde_json::to_string(&request).unwrap().contains("id"));
+    }
+
     fn make_file(dir_path: PathBuf, file_name: String, data: &[u8]) -> File {
         let mut path = dir_path;
         path.push(file_name);
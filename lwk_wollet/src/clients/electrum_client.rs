@@ -1,28 +1,57 @@
+use crate::hashes::{sha256, Hash};
 use crate::store::Height;
 use crate::Error;
+use electrum_client::raw_client::{ElectrumSslStream, RawClient};
 use electrum_client::ScriptStatus;
 use electrum_client::{Client, ConfigBuilder, ElectrumApi, GetHistoryRes};
 use elements::encode::deserialize as elements_deserialize;
 use elements::encode::serialize as elements_serialize;
+use elements::hex::ToHex;
 use elements::Address;
 use elements::{bitcoin, BlockHash, BlockHeader, Script, Transaction, Txid};
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, ClientConnection, ServerName, StreamOwned};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use super::History;
 
 /// A client to issue TCP requests to an electrum server.
 pub struct ElectrumClient {
-    client: Client,
+    client: InnerClient,
 
     tip: BlockHeader,
 
     script_status: HashMap<Script, ScriptStatus>,
 }
 
+/// Connections with a pinned certificate are built by hand, thus they use a [`RawClient`]
+/// which, unlike [`Client`], doesn't reconnect if the connection drops
+enum InnerClient {
+    Client(Client),
+    Pinned(RawClient<ElectrumSslStream>),
+}
+
+macro_rules! call {
+    ($self:expr, $method:ident $(, $args:expr)*) => {
+        match &$self.client {
+            InnerClient::Client(c) => c.$method($($args),*),
+            InnerClient::Pinned(c) => c.$method($($args),*),
+        }
+    };
+}
+
 #[derive(Debug, Clone)]
 pub enum ElectrumUrl {
     Tls(String, bool), // the bool value indicates if the domain name should be validated
+
+    /// TLS connection accepting only the certificate with the given SHA-256 fingerprint, useful
+    /// for servers with self-signed certificates
+    TlsPinned(String, [u8; 32]),
+
     Plaintext(String),
 }
 
@@ -30,6 +59,7 @@ impl std::fmt::Display for ElectrumUrl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ElectrumUrl::Tls(s, _) => write!(f, "{}", s),
+            ElectrumUrl::TlsPinned(s, _) => write!(f, "{}", s),
             ElectrumUrl::Plaintext(s) => write!(f, "{}", s),
         }
     }
@@ -42,18 +72,77 @@ impl ElectrumUrl {
             false => ElectrumUrl::Plaintext(electrum_url.into()),
         }
     }
+
+    /// A TLS url accepting only the certificate with the given SHA-256 fingerprint
+    pub fn new_pinned(electrum_url: &str, cert_sha256: [u8; 32]) -> Self {
+        ElectrumUrl::TlsPinned(electrum_url.into(), cert_sha256)
+    }
+
     pub fn build_client(&self) -> Result<Client, Error> {
         let builder = ConfigBuilder::new();
         let (url, builder) = match self {
             ElectrumUrl::Tls(url, validate) => {
                 (format!("ssl://{}", url), builder.validate_domain(*validate))
             }
+            ElectrumUrl::TlsPinned(..) => {
+                return Err(Error::Generic(
+                    "urls with a pinned certificate cannot build a reconnecting client".into(),
+                ))
+            }
             ElectrumUrl::Plaintext(url) => (format!("tcp://{}", url), builder),
         };
         Ok(Client::from_config(&url, builder.build())?)
     }
 }
 
+/// Accept only the server certificate with the expected fingerprint, remembering the one
+/// received otherwise, so that the mismatch can be reported
+struct PinnedCertificate {
+    expected: [u8; 32],
+    found: Mutex<Option<[u8; 32]>>,
+}
+
+impl ServerCertVerifier for PinnedCertificate {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let found = sha256::Hash::hash(&end_entity.0).to_byte_array();
+        if found == self.expected {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            if let Ok(mut f) = self.found.lock() {
+                *f = Some(found);
+            }
+            Err(rustls::Error::General(
+                "certificate fingerprint mismatch".into(),
+            ))
+        }
+    }
+}
+
+fn pinned_client(
+    url: &str,
+    verifier: Arc<PinnedCertificate>,
+) -> Result<RawClient<ElectrumSslStream>, Error> {
+    let host = url.rsplit_once(':').map_or(url, |(host, _)| host);
+    let server_name = ServerName::try_from(host)
+        .map_err(|_| Error::Generic(format!("invalid electrum server name '{host}'")))?;
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier)
+        .with_no_client_auth();
+    let connection = ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|e| Error::Generic(e.to_string()))?;
+    let stream = TcpStream::connect(url)?;
+    Ok(StreamOwned::new(connection, stream).into())
+}
+
 impl Debug for ElectrumClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ElectrumClient")
@@ -64,8 +153,31 @@ impl Debug for ElectrumClient {
 
 impl ElectrumClient {
     pub fn new(url: &ElectrumUrl) -> Result<Self, Error> {
-        let client = url.build_client()?;
-        let header = client.block_headers_subscribe_raw()?;
+        let (client, header) = match url {
+            ElectrumUrl::TlsPinned(url, expected) => {
+                let verifier = Arc::new(PinnedCertificate {
+                    expected: *expected,
+                    found: Mutex::new(None),
+                });
+                let client = pinned_client(url, verifier.clone())?;
+                // The TLS handshake, thus the certificate check, happens on the first request
+                let header = client.block_headers_subscribe_raw().map_err(|e| {
+                    match verifier.found.lock().ok().and_then(|f| *f) {
+                        Some(found) => Error::ElectrumCertificateMismatch {
+                            expected: expected.to_hex(),
+                            found: found.to_hex(),
+                        },
+                        None => e.into(),
+                    }
+                })?;
+                (InnerClient::Pinned(client), header)
+            }
+            _ => {
+                let client = url.build_client()?;
+                let header = client.block_headers_subscribe_raw()?;
+                (InnerClient::Client(client), header)
+            }
+        };
         let tip: BlockHeader = elements_deserialize(&header.header)?;
 
         Ok(Self {
@@ -75,6 +187,18 @@ impl ElectrumClient {
         })
     }
 
+    /// Return the software version reported by the electrum server
+    pub fn server_version(&self) -> Result<String, Error> {
+        Ok(call!(self, server_features)?.server_version)
+    }
+
+    /// Return the fee rate in BTC/kvB estimated to confirm within `blocks`
+    ///
+    /// The value is negative if the server doesn't have enough data to estimate.
+    pub fn estimate_fee(&self, blocks: usize) -> Result<f64, Error> {
+        Ok(call!(self, estimate_fee, blocks)?)
+    }
+
     /// Return the status of an address as defined by the electrum protocol
     ///
     /// The status is function of the transaction ids where this address appears and the height of
@@ -84,11 +208,11 @@ impl ElectrumClient {
         let elements_script = address.script_pubkey();
         let bitcoin_script = bitcoin::ScriptBuf::from(elements_script.to_bytes());
 
-        let val = match self.client.script_subscribe(&bitcoin_script) {
+        let val = match call!(self, script_subscribe, &bitcoin_script) {
             Ok(val) => val,
             Err(electrum_client::Error::AlreadySubscribed(_)) => {
-                self.client.script_get_history(&bitcoin_script)?; // it seems it must be called, otherwise the server don't update the status
-                self.client.script_pop(&bitcoin_script)?
+                call!(self, script_get_history, &bitcoin_script)?; // it seems it must be called, otherwise the server don't update the status
+                call!(self, script_pop, &bitcoin_script)?
             }
             Err(e) => return Err(e.into()),
         };
@@ -102,7 +226,7 @@ impl ElectrumClient {
 impl super::BlockchainBackend for ElectrumClient {
     fn tip(&mut self) -> Result<BlockHeader, Error> {
         let mut popped_header = None;
-        while let Some(header) = self.client.block_headers_pop_raw()? {
+        while let Some(header) = call!(self, block_headers_pop_raw)? {
             popped_header = Some(header)
         }
 
@@ -115,9 +239,7 @@ impl super::BlockchainBackend for ElectrumClient {
     }
 
     fn broadcast(&self, tx: &Transaction) -> Result<Txid, Error> {
-        let txid = self
-            .client
-            .transaction_broadcast_raw(&elements_serialize(tx))?;
+        let txid = call!(self, transaction_broadcast_raw, &elements_serialize(tx))?;
         Ok(Txid::from_raw_hash(txid.to_raw_hash()))
     }
 
@@ -128,7 +250,7 @@ impl super::BlockchainBackend for ElectrumClient {
             .collect();
 
         let mut result = vec![];
-        for tx in self.client.batch_transaction_get_raw(&txids)? {
+        for tx in call!(self, batch_transaction_get_raw, &txids)? {
             let tx: Transaction = elements::encode::deserialize(&tx)?;
             result.push(tx);
         }
@@ -141,7 +263,7 @@ impl super::BlockchainBackend for ElectrumClient {
         _: &HashMap<Height, BlockHash>,
     ) -> Result<Vec<BlockHeader>, Error> {
         let mut result = vec![];
-        for header in self.client.batch_block_header_raw(heights)? {
+        for header in call!(self, batch_block_header_raw, heights)? {
             let header: BlockHeader = elements::encode::deserialize(&header)?;
             result.push(header);
         }
@@ -154,9 +276,7 @@ impl super::BlockchainBackend for ElectrumClient {
             .map(|t| bitcoin::Script::from_bytes(t.as_bytes()))
             .collect();
 
-        Ok(self
-            .client
-            .batch_script_get_history(&scripts)?
+        Ok(call!(self, batch_script_get_history, &scripts)?
             .into_iter()
             .map(|e| e.into_iter().map(Into::into).collect())
             .collect())
@@ -172,3 +292,39 @@ impl From<GetHistoryRes> for History {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_certificate() {
+        let cert = Certificate(vec![1, 2, 3]);
+        let expected = sha256::Hash::hash(&cert.0).to_byte_array();
+        let verifier = PinnedCertificate {
+            expected,
+            found: Mutex::new(None),
+        };
+        let name = ServerName::try_from("example.com").unwrap();
+        let verify = |cert: &Certificate| {
+            verifier.verify_server_cert(
+                cert,
+                &[],
+                &name,
+                &mut std::iter::empty(),
+                &[],
+                SystemTime::now(),
+            )
+        };
+
+        assert!(verify(&cert).is_ok());
+        assert!(verifier.found.lock().unwrap().is_none());
+
+        let other = Certificate(vec![4, 5, 6]);
+        assert!(verify(&other).is_err());
+        assert_eq!(
+            *verifier.found.lock().unwrap(),
+            Some(sha256::Hash::hash(&other.0).to_byte_array())
+        );
+    }
+}
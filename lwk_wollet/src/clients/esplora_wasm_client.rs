@@ -140,6 +140,9 @@ impl EsploraWasmClient {
             let chain: Chain = (&descriptor).try_into().unwrap_or(Chain::External);
             loop {
                 let batch = store.get_script_batch(batch_count, &descriptor)?;
+                if batch.value.is_empty() {
+                    break;
+                }
 
                 let s: Vec<_> = batch.value.iter().map(|e| &e.0).collect();
                 let result: Vec<Vec<History>> = self.get_scripts_history(&s).await?;
@@ -1,7 +1,7 @@
 use crate::{
     store::{Height, Store, Timestamp, BATCH_SIZE},
     update::{DownloadTxResult, Update},
-    Chain, Error, Wollet, WolletDescriptor, EC,
+    Chain, Error, UsedChain, Wollet, WolletDescriptor, EC,
 };
 use elements::{
     bitcoin::bip32::ChildNumber,
@@ -13,6 +13,36 @@ use lwk_common::derive_blinding_key;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic;
 
+/// The number of transactions downloaded at once by the scans
+const TX_BATCH_SIZE: usize = 100;
+
+/// How far a scan has gone, updated by the scan while it runs so that it can be read from other
+/// threads
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    scanned_height: atomic::AtomicU32,
+}
+
+impl ScanProgress {
+    /// The height up to which the wallet transactions have been downloaded.
+    ///
+    /// It starts from the wallet tip and it reaches the blockchain tip when the scan completes,
+    /// it never decreases during a scan.
+    pub fn scanned_height(&self) -> Height {
+        self.scanned_height.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Restart the progress from `height`, the scans do it from the wallet tip
+    pub fn start(&self, height: Height) {
+        self.scanned_height.store(height, atomic::Ordering::Relaxed);
+    }
+
+    fn advance(&self, height: Height) {
+        self.scanned_height
+            .fetch_max(height, atomic::Ordering::Relaxed);
+    }
+}
+
 #[cfg(feature = "esplora")]
 pub(crate) mod esplora_client;
 
@@ -37,8 +67,32 @@ pub trait BlockchainBackend {
     fn get_scripts_history(&self, scripts: &[&Script]) -> Result<Vec<Vec<History>>, Error>;
 
     fn full_scan(&mut self, wollet: &Wollet) -> Result<Option<Update>, Error> {
+        self.full_scan_cancellable(
+            wollet,
+            &atomic::AtomicBool::new(false),
+            &ScanProgress::default(),
+        )
+    }
+
+    /// Like [`BlockchainBackend::full_scan()`] but checks `cancel` before every scripts history
+    /// fetch and every transactions download, if it's set the scan stops with
+    /// [`Error::ScanCancelled`] and no update is returned.
+    ///
+    /// The transactions are downloaded from the lowest height, `progress` is advanced to the
+    /// height of the transactions downloaded.
+    ///
+    /// A partial update is not returned because an [`Update`] replaces the wallet history: the
+    /// transactions of the scripts not fetched yet would look as if they were no longer in the
+    /// blockchain and they would be removed from the wallet. The next scan starts over.
+    fn full_scan_cancellable(
+        &mut self,
+        wollet: &Wollet,
+        cancel: &atomic::AtomicBool,
+        progress: &ScanProgress,
+    ) -> Result<Option<Update>, Error> {
         let descriptor = wollet.wollet_descriptor();
         let store = &wollet.store;
+        progress.start(store.cache.tip.0);
         let mut txid_height = HashMap::new();
         let mut scripts = HashMap::new();
 
@@ -50,7 +104,13 @@ pub trait BlockchainBackend {
             let mut batch_count = 0;
             let chain: Chain = (&descriptor).try_into().unwrap_or(Chain::External);
             loop {
+                if cancel.load(atomic::Ordering::Relaxed) {
+                    return Err(Error::ScanCancelled);
+                }
                 let batch = store.get_script_batch(batch_count, &descriptor)?;
+                if batch.value.is_empty() {
+                    break;
+                }
 
                 let s: Vec<_> = batch.value.iter().map(|e| &e.0).collect();
                 let result: Vec<Vec<History>> = self.get_scripts_history(&s)?;
@@ -100,8 +160,26 @@ pub trait BlockchainBackend {
             }
         }
 
-        let history_txs_id: HashSet<Txid> = txid_height.keys().cloned().collect();
-        let new_txs = self.download_txs(&history_txs_id, &scripts, store, &descriptor)?;
+        // Unconfirmed transactions last
+        let mut txs_to_download: Vec<_> = txid_height
+            .iter()
+            .filter(|(txid, _)| !store.cache.all_txs.contains_key(*txid))
+            .map(|(txid, height)| (height.unwrap_or(Height::MAX), *txid))
+            .collect();
+        txs_to_download.sort_unstable();
+        let mut new_txs = DownloadTxResult::default();
+        for batch in txs_to_download.chunks(TX_BATCH_SIZE) {
+            if cancel.load(atomic::Ordering::Relaxed) {
+                return Err(Error::ScanCancelled);
+            }
+            let txids: HashSet<Txid> = batch.iter().map(|(_, txid)| *txid).collect();
+            let downloaded = self.download_txs(&txids, &scripts, store, &descriptor)?;
+            new_txs.txs.extend(downloaded.txs);
+            new_txs.unblinds.extend(downloaded.unblinds);
+            if let Some((height, _)) = batch.last().filter(|(h, _)| *h != Height::MAX) {
+                progress.advance(*height);
+            }
+        }
         let history_txs_heights: HashSet<Height> =
             txid_height.values().filter_map(|e| *e).collect();
         let timestamps = self.download_headers(&history_txs_heights, &height_blockhash, store)?;
@@ -116,6 +194,7 @@ pub trait BlockchainBackend {
             .load(atomic::Ordering::Relaxed);
 
         let tip = self.tip()?;
+        progress.advance(tip.height);
 
         let last_unused_changed = store_last_unused_external != last_unused_external
             || store_last_unused_internal != last_unused_internal;
@@ -159,6 +238,58 @@ pub trait BlockchainBackend {
         }
     }
 
+    /// Look for the used scripts of the wallet with the gap limit `max_gap`, instead of the
+    /// [`BATCH_SIZE`] used by the scans, without changing the wallet
+    ///
+    /// Every chain is checked up to `max_index` at most.
+    fn discover_used(
+        &mut self,
+        descriptor: &WolletDescriptor,
+        max_gap: u32,
+        max_index: u32,
+    ) -> Result<Vec<UsedChain>, Error> {
+        let mut result = vec![];
+        for descriptor in descriptor.descriptor().clone().into_single_descriptors()? {
+            let chain: Chain = (&descriptor).try_into().unwrap_or(Chain::External);
+            // A descriptor without wildcard has a single script
+            let max_index = if descriptor.has_wildcard() {
+                max_index
+            } else {
+                max_index.min(1)
+            };
+            let mut used = vec![];
+            let mut next_unused = 0;
+            let mut index = 0;
+            let max_index_reached = loop {
+                if index - next_unused >= max_gap {
+                    break false;
+                }
+                if index >= max_index {
+                    break descriptor.has_wildcard();
+                }
+                let end = (index + BATCH_SIZE).min(max_index);
+                let scripts = (index..end)
+                    .map(|i| Ok(descriptor.at_derivation_index(i)?.script_pubkey()))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let scripts: Vec<_> = scripts.iter().collect();
+                for (i, history) in (index..end).zip(self.get_scripts_history(&scripts)?) {
+                    if !history.is_empty() {
+                        used.push((i, history.len()));
+                        next_unused = i + 1;
+                    }
+                }
+                index = end;
+            };
+            result.push(UsedChain {
+                chain,
+                used,
+                scanned: index,
+                max_index_reached,
+            });
+        }
+        Ok(result)
+    }
+
     fn download_txs(
         &self,
         history_txs_id: &HashSet<Txid>,
@@ -341,4 +472,129 @@ mod tests {
             );
         }
     }
+
+    struct Unreachable;
+
+    impl BlockchainBackend for Unreachable {
+        fn tip(&mut self) -> Result<elements::BlockHeader, crate::Error> {
+            unreachable!()
+        }
+        fn broadcast(&self, _: &elements::Transaction) -> Result<elements::Txid, crate::Error> {
+            unreachable!()
+        }
+        fn get_transactions(
+            &self,
+            _: &[elements::Txid],
+        ) -> Result<Vec<elements::Transaction>, crate::Error> {
+            unreachable!()
+        }
+        fn get_headers(
+            &self,
+            _: &[crate::store::Height],
+            _: &std::collections::HashMap<crate::store::Height, elements::BlockHash>,
+        ) -> Result<Vec<elements::BlockHeader>, crate::Error> {
+            unreachable!()
+        }
+        fn get_scripts_history(
+            &self,
+            _: &[&elements::Script],
+        ) -> Result<Vec<Vec<super::History>>, crate::Error> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn full_scan_cancelled() {
+        let desc = lwk_test_util::TEST_DESCRIPTOR.parse().unwrap();
+        let wollet = crate::Wollet::without_persist(ElementsNetwork::LiquidTestnet, desc).unwrap();
+        let cancel = std::sync::atomic::AtomicBool::new(true);
+        let err = Unreachable
+            .full_scan_cancellable(&wollet, &cancel, &super::ScanProgress::default())
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::ScanCancelled));
+    }
+
+    #[test]
+    fn scan_progress() {
+        let progress = super::ScanProgress::default();
+        assert_eq!(progress.scanned_height(), 0);
+        progress.start(10);
+        progress.advance(20);
+        progress.advance(15);
+        assert_eq!(progress.scanned_height(), 20);
+        progress.start(5);
+        assert_eq!(progress.scanned_height(), 5);
+    }
+
+    struct UsedScripts(Vec<elements::Script>);
+
+    impl BlockchainBackend for UsedScripts {
+        fn tip(&mut self) -> Result<elements::BlockHeader, crate::Error> {
+            unreachable!()
+        }
+        fn broadcast(&self, _: &elements::Transaction) -> Result<elements::Txid, crate::Error> {
+            unreachable!()
+        }
+        fn get_transactions(
+            &self,
+            _: &[elements::Txid],
+        ) -> Result<Vec<elements::Transaction>, crate::Error> {
+            unreachable!()
+        }
+        fn get_headers(
+            &self,
+            _: &[crate::store::Height],
+            _: &std::collections::HashMap<crate::store::Height, elements::BlockHash>,
+        ) -> Result<Vec<elements::BlockHeader>, crate::Error> {
+            unreachable!()
+        }
+        fn get_scripts_history(
+            &self,
+            scripts: &[&elements::Script],
+        ) -> Result<Vec<Vec<super::History>>, crate::Error> {
+            use elements::hashes::Hash;
+            Ok(scripts
+                .iter()
+                .map(|s| {
+                    self.0
+                        .iter()
+                        .filter(|u| u == s)
+                        .map(|_| super::History {
+                            txid: elements::Txid::all_zeros(),
+                            height: 1,
+                            block_hash: None,
+                        })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn discover_used() {
+        let desc: crate::WolletDescriptor = lwk_test_util::TEST_DESCRIPTOR.parse().unwrap();
+        let wollet =
+            crate::Wollet::without_persist(ElementsNetwork::LiquidTestnet, desc.clone()).unwrap();
+        let script = |i| wollet.address(Some(i)).unwrap().address().script_pubkey();
+        let mut backend = UsedScripts(vec![script(5), script(45)]);
+
+        let chains = backend.discover_used(&desc, 20, 1_000).unwrap();
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].chain, crate::Chain::External);
+        assert_eq!(chains[0].used, vec![(5, 1)]);
+        assert_eq!(chains[0].scanned, 40);
+        assert!(!chains[0].max_index_reached);
+        assert_eq!(chains[1].chain, crate::Chain::Internal);
+        assert!(chains[1].used.is_empty());
+        assert_eq!(chains[1].scanned, 20);
+
+        let chains = backend.discover_used(&desc, 50, 1_000).unwrap();
+        assert_eq!(chains[0].used, vec![(5, 1), (45, 1)]);
+        assert_eq!(chains[0].scanned, 100);
+
+        let chains = backend.discover_used(&desc, 50, 30).unwrap();
+        assert_eq!(chains[0].used, vec![(5, 1)]);
+        assert_eq!(chains[0].scanned, 30);
+        assert!(chains[0].max_index_reached);
+    }
 }
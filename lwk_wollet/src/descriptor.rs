@@ -8,9 +8,11 @@ use elements::hashes::{sha256t_hash_newtype, Hash};
 use elements::{Address, AddressParams};
 use elements_miniscript::{
     confidential::Key,
-    descriptor::{DescriptorSecretKey, Wildcard},
-    ConfidentialDescriptor, Descriptor, DescriptorPublicKey, ForEachKey,
+    descriptor::{DescriptorSecretKey, ShInner, Wildcard, WshInner},
+    ConfidentialDescriptor, Descriptor, DescriptorPublicKey, ForEachKey, Terminal,
 };
+
+use crate::SpendPath;
 use serde::{Deserialize, Serialize};
 
 sha256t_hash_newtype! {
@@ -54,9 +56,6 @@ impl TryFrom<ConfidentialDescriptor<DescriptorPublicKey>> for WolletDescriptor {
             }
         }
 
-        if !desc.descriptor.has_wildcard() {
-            return Err(Self::Error::UnsupportedDescriptorWithoutWildcard);
-        }
         if desc.descriptor.is_multipath() {
             let descriptors = desc.descriptor.clone().into_single_descriptors()?;
 
@@ -138,6 +137,13 @@ impl WolletDescriptor {
         &self.0.descriptor
     }
 
+    /// Whether the descriptor has a wildcard, so it derives a different script for every index
+    ///
+    /// A descriptor without wildcard has a single script, at index 0.
+    pub fn is_ranged(&self) -> bool {
+        self.0.descriptor.has_wildcard()
+    }
+
     /// return the single descriptor if not multipath, if multipath returns the internal or the
     /// external descriptor accordint to `int_or_ext`
     fn inner_descriptor_if_available(&self, ext_int: Chain) -> WolletDescriptor {
@@ -162,6 +168,11 @@ impl WolletDescriptor {
         })
     }
 
+    /// The descriptor of the given chain, a descriptor that is not multipath is returned as is
+    pub fn chain_descriptor(&self, ext_int: Chain) -> WolletDescriptor {
+        self.inner_descriptor_if_available(ext_int)
+    }
+
     pub fn change(
         &self,
         index: u32,
@@ -206,6 +217,27 @@ impl WolletDescriptor {
         let desc = self.inner_descriptor_if_available(ext_int);
         Ok(desc.descriptor().at_derivation_index(index)?)
     }
+
+    /// Whether the descriptor has a branch with the timelock of the given spend path
+    pub fn has_spend_path(&self, spend_path: &SpendPath) -> bool {
+        let wsh = match &self.0.descriptor {
+            Descriptor::Wsh(wsh) => wsh,
+            Descriptor::Sh(sh) => match sh.as_inner() {
+                ShInner::Wsh(wsh) => wsh,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        let ms = match wsh.as_inner() {
+            WshInner::Ms(ms) => ms,
+            _ => return false,
+        };
+        ms.iter().any(|node| match (&node.node, spend_path) {
+            (Terminal::Older(s), SpendPath::Older(n)) => s.to_consensus_u32() == *n,
+            (Terminal::After(t), SpendPath::After(n)) => t.to_consensus_u32() == *n,
+            _ => false,
+        })
+    }
 }
 
 impl AsRef<ConfidentialDescriptor<DescriptorPublicKey>> for WolletDescriptor {
@@ -221,7 +253,7 @@ mod test {
         hash::{Hash, Hasher},
     };
 
-    use crate::WolletDescriptor;
+    use crate::{SpendPath, WolletDescriptor};
 
     #[test]
     fn test_wollet_hash() {
@@ -232,4 +264,17 @@ mod test {
         desc.hash(&mut hasher);
         assert_eq!(12055616352728229988, hasher.finish());
     }
+
+    #[test]
+    fn test_has_spend_path() {
+        let a = "tpubDDCNstnPhbdd4vwbw5UWK3vRQSF1WXQkvBHpNXpKJAkwFYjwu735EH3GVf53qwbWimzewDUv68MUmRDgYtQ1AU8FRCPkazfuaBp7LaEaohG";
+        let b = "tpubDDExQpZg2tziZ7ACSBCYsY3rYxAZtTRBgWwioRLYqgNBguH6rMHN1D8epTxUQUB5kM5nxkEtr2SNic6PJLPubcGMR6S2fmDZTzL9dHpU7ka";
+        let slip77 = "9c8e4f05c7711a98c838be228bcb84924d4570ca53f35fa1c793e58841d47023";
+        let desc_str =
+            format!("ct(slip77({slip77}),elwsh(or_d(pk({a}/*),and_v(v:pk({b}/*),older(144)))))");
+        let desc: WolletDescriptor = desc_str.parse().unwrap();
+        assert!(desc.has_spend_path(&SpendPath::Older(144)));
+        assert!(!desc.has_spend_path(&SpendPath::Older(10)));
+        assert!(!desc.has_spend_path(&SpendPath::After(144)));
+    }
 }
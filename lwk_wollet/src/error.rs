@@ -28,6 +28,9 @@ pub enum Error {
     #[error(transparent)]
     ClientError(#[from] electrum_client::Error),
 
+    #[error("Electrum server certificate SHA-256 fingerprint {found} does not match the pinned {expected}")]
+    ElectrumCertificateMismatch { expected: String, found: String },
+
     #[error(transparent)]
     ElementsEncode(#[from] crate::elements::encode::Error),
 
@@ -40,6 +43,9 @@ pub enum Error {
     #[error(transparent)]
     PsetBlindError(#[from] crate::elements::pset::PsetBlindError),
 
+    #[error(transparent)]
+    ConfidentialTxOutError(#[from] crate::elements::ConfidentialTxOutError),
+
     #[error(transparent)]
     Secp256k1(#[from] crate::secp256k1::Error),
 
@@ -128,9 +134,6 @@ pub enum Error {
     #[error("Invalid issuer pubkey")]
     InvalidIssuerPubkey,
 
-    #[error("Descriptor without wildcard not supported")]
-    UnsupportedDescriptorWithoutWildcard,
-
     #[error(
         "Multipath descriptor must have only the external/internal multipath (eg '.../<0;1>/*')"
     )]
@@ -159,6 +162,49 @@ pub enum Error {
 
     #[error("An issuance has already being set on this tx builder")]
     IssuanceAlreadySet,
+
+    #[error("Invalid spend path '{0}', expected 'older(n)' or 'after(n)' with n in blocks")]
+    InvalidSpendPath(String),
+
+    #[error("The descriptor does not contain the spend path {0}")]
+    SpendPathNotInDescriptor(String),
+
+    #[error("The spend path {0} is not satisfiable yet")]
+    SpendPathNotSatisfiable(String),
+
+    #[error("The scan has been cancelled")]
+    ScanCancelled,
+
+    #[error("At least an input must be added to a PSET to blind the added outputs")]
+    AddToPsetWithoutInputs,
+
+    #[error("Output {0} is not an unspent output of this wallet")]
+    UtxoNotMine(crate::elements::OutPoint),
+
+    #[error("Output {0} is already spent by the PSET")]
+    UtxoAlreadyInPset(crate::elements::OutPoint),
+
+    #[error("The PSET has outputs already blinded, their contributions cannot be changed")]
+    PsetAlreadyBlinded,
+
+    #[error("No output of the PSET is to be blinded by this wallet")]
+    NothingToBlind,
+
+    #[error("Cannot fund the swap: {needed} of asset {asset} needed, {available} available")]
+    SwapInsufficientFunds {
+        asset: crate::elements::AssetId,
+        needed: u64,
+        available: u64,
+    },
+
+    #[error("The PSET is not a swap proposal")]
+    NotASwapProposal,
+
+    #[error("Invalid swap: {0}")]
+    InvalidSwap(String),
+
+    #[error("Fee {fee} is below the minimum {min} for the size of the transaction")]
+    FeeTooLow { fee: u64, min: u64 },
 }
 
 // cannot derive automatically with this error because of trait bound
@@ -18,27 +18,31 @@ mod persister;
 mod pset_create;
 mod registry;
 mod store;
+mod swap;
 mod tx_builder;
 mod update;
 mod util;
 mod wollet;
 
-pub use crate::clients::BlockchainBackend;
+pub use crate::clients::{BlockchainBackend, ScanProgress};
 pub use crate::config::ElementsNetwork;
 pub use crate::descriptor::{Chain, WolletDescriptor};
 pub use crate::error::Error;
 pub use crate::model::{
-    AddressResult, IssuanceDetails, Recipient, UnvalidatedRecipient, WalletTx, WalletTxOut,
+    AddressResult, IssuanceDetails, Recipient, SpendPath, UnvalidatedRecipient, UsedChain,
+    WalletTx, WalletTxOut,
 };
 pub use crate::persister::{FsPersister, NoPersist, PersistError, Persister};
 pub use crate::registry::{asset_ids, issuance_ids, Contract, Entity};
 pub use crate::tx_builder::{TxBuilder, WolletTxBuilder};
-pub use crate::update::Update;
+pub use crate::update::{Update, UtxoChanges};
 pub use crate::util::EC;
 pub use crate::wollet::Wollet;
 
 #[cfg(feature = "electrum")]
-pub use crate::wollet::full_scan_with_electrum_client;
+pub use crate::wollet::{
+    full_scan_with_electrum_client, full_scan_with_electrum_client_cancellable,
+};
 #[cfg(feature = "electrum")]
 pub use clients::electrum_client::{ElectrumClient, ElectrumUrl};
 
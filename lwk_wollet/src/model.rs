@@ -59,6 +59,47 @@ impl Recipient {
     }
 }
 
+/// A timelocked branch of the wallet descriptor to satisfy when spending
+///
+/// Parsed from the miniscript fragment of the branch, like "older(144)" or "after(1000)".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpendPath {
+    /// The branch with `older(n)`: inputs must be confirmed since `n` blocks
+    Older(u32),
+
+    /// The branch with `after(n)`: the transaction can't be included before block height `n`
+    After(u32),
+}
+
+impl FromStr for SpendPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || Error::InvalidSpendPath(s.to_string());
+        let (kind, rest) = s.split_once('(').ok_or_else(err)?;
+        let value: u32 = rest
+            .strip_suffix(')')
+            .ok_or_else(err)?
+            .parse()
+            .map_err(|_| err())?;
+        match kind {
+            // only block based timelocks are supported
+            "older" if value > 0 && value <= u16::MAX as u32 => Ok(SpendPath::Older(value)),
+            "after" if value > 0 && value < 500_000_000 => Ok(SpendPath::After(value)),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl std::fmt::Display for SpendPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpendPath::Older(n) => write!(f, "older({n})"),
+            SpendPath::After(n) => write!(f, "after({n})"),
+        }
+    }
+}
+
 /// A not-yet validated recipient of a transaction.
 ///
 /// By calling [`UnvalidatedRecipient::validate()`] can be transformed in a validated [`Recipient`]
@@ -241,6 +282,27 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn test_spend_path() {
+        for s in ["older(144)", "after(1000)"] {
+            let spend_path = SpendPath::from_str(s).unwrap();
+            assert_eq!(spend_path.to_string(), s);
+        }
+        assert_eq!(
+            SpendPath::from_str("older(10)").unwrap(),
+            SpendPath::Older(10)
+        );
+        for s in [
+            "older(0)",
+            "older(65536)",
+            "after(500000000)",
+            "pk(1)",
+            "older(1",
+        ] {
+            assert!(SpendPath::from_str(s).is_err());
+        }
+    }
+
     #[test]
     fn test_asset_roundtrip() {
         let hex = "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
@@ -258,3 +320,19 @@ mod tests {
         );
     }
 }
+
+/// The scripts of a wallet chain found used by [`crate::BlockchainBackend::discover_used()`]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsedChain {
+    pub chain: Chain,
+
+    /// The derivation indexes having a transaction history, with the number of transactions
+    pub used: Vec<(u32, usize)>,
+
+    /// How many indexes have been checked, starting from 0
+    pub scanned: u32,
+
+    /// True if the scan stopped because of the maximum index instead of the gap limit, so there
+    /// might be other used indexes
+    pub max_index_reached: bool,
+}
@@ -1,17 +1,23 @@
 use crate::bitcoin::PublicKey as BitcoinPublicKey;
-use crate::elements::confidential::AssetBlindingFactor;
+use crate::elements::confidential::{AssetBlindingFactor, Value, ValueBlindingFactor};
 use crate::elements::issuance::ContractHash;
 use crate::elements::pset::{Input, Output, PartiallySignedTransaction};
-use crate::elements::{Address, AssetId, OutPoint, Transaction, TxOut, TxOutSecrets, Txid};
+use crate::elements::{
+    Address, AssetId, LockTime, OutPoint, Script, Sequence, Transaction, TxOut, TxOutSecrets, Txid,
+};
 use crate::error::Error;
 use crate::hashes::Hash;
-use crate::model::{Recipient, WalletTxOut};
+use crate::model::{Recipient, SpendPath, WalletTxOut};
 use crate::registry::Contract;
 use crate::wollet::Wollet;
-use crate::ElementsNetwork;
+use crate::{ElementsNetwork, EC};
 use elements::pset::elip100::AssetMetadata;
+use elements::secp256k1_zkp::SecretKey;
+use elements::RangeProofMessage;
+use lwk_common::derive_blinding_key;
+use rand::thread_rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Serialize, Deserialize)]
 // We make issuance and reissuance are mutually exclusive for simplicity
@@ -93,13 +99,222 @@ impl Wollet {
         Ok(idx)
     }
 
+    /// Add the given wallet UTXOs as inputs and the given recipients as outputs to a PSET shared
+    /// with other parties, for collaborative transactions
+    ///
+    /// For every asset the value of the added inputs exceeding the added outputs and `fee` is sent
+    /// to a wallet change address, `fee` is added to the explicit fee output.
+    /// Existing inputs and outputs are left untouched, the added outputs are not blinded yet: once
+    /// every party has added its contribution each one calls [`Wollet::blind_pset()`].
+    pub fn add_to_pset(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+        utxos: &[OutPoint],
+        recipients: &[Recipient],
+        fee: u64,
+    ) -> Result<(), Error> {
+        self.add_contribution(pset, utxos, recipients, fee, &HashMap::new())
+    }
+
+    /// Like [`Wollet::add_to_pset()`], the value in `given` is not sent to change but left to the
+    /// outputs of other parties
+    pub(crate) fn add_contribution(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+        utxos: &[OutPoint],
+        recipients: &[Recipient],
+        fee: u64,
+        given: &HashMap<AssetId, u64>,
+    ) -> Result<(), Error> {
+        if utxos.is_empty() {
+            if recipients.is_empty() && fee == 0 {
+                return Ok(());
+            }
+            return Err(Error::AddToPsetWithoutInputs);
+        }
+        // Surjection proofs commit to all the inputs
+        if pset.outputs().iter().any(|o| o.asset_comm.is_some()) {
+            return Err(Error::PsetAlreadyBlinded);
+        }
+        let wallet_utxos: HashMap<OutPoint, WalletTxOut> =
+            self.utxos()?.into_iter().map(|u| (u.outpoint, u)).collect();
+        let spent: HashSet<OutPoint> = pset
+            .inputs()
+            .iter()
+            .map(|i| OutPoint::new(i.previous_txid, i.previous_output_index))
+            .collect();
+
+        let blinder_index = pset.n_inputs() as u32;
+        let mut inp_txout_sec = HashMap::new();
+        let mut inp_weight = 0;
+        let mut satoshi_in: HashMap<AssetId, u64> = HashMap::new();
+        for outpoint in utxos {
+            if spent.contains(outpoint) {
+                return Err(Error::UtxoAlreadyInPset(*outpoint));
+            }
+            let utxo = wallet_utxos
+                .get(outpoint)
+                .ok_or_else(|| Error::UtxoNotMine(*outpoint))?;
+            self.add_input(pset, &mut inp_txout_sec, &mut inp_weight, utxo)?;
+            *satoshi_in.entry(utxo.unblinded.asset).or_default() += utxo.unblinded.value;
+        }
+
+        // The added outputs may exceed the added inputs, paid by other parties' inputs
+        let mut outputs = recipients.to_vec();
+        let mut last_unused_internal = self.change(None)?.index();
+        let policy_asset = self.policy_asset();
+        for (asset, satoshi_in) in satoshi_in {
+            let mut satoshi_out: u64 = recipients
+                .iter()
+                .filter(|r| r.asset == asset)
+                .map(|r| r.satoshi)
+                .sum();
+            if asset == policy_asset {
+                satoshi_out += fee;
+            }
+            satoshi_out += given.get(&asset).copied().unwrap_or(0);
+            if satoshi_in > satoshi_out {
+                outputs.push(self.addressee_change(
+                    satoshi_in - satoshi_out,
+                    asset,
+                    &mut last_unused_internal,
+                )?);
+            }
+        }
+        for addressee in outputs.iter() {
+            self.add_output(pset, addressee)?;
+            if let Some(output) = pset.outputs_mut().last_mut() {
+                output.blinder_index = Some(blinder_index);
+            }
+        }
+
+        if fee > 0 {
+            let fee_output = pset.outputs_mut().iter_mut().find(|o| {
+                o.script_pubkey.is_empty() && o.asset == Some(policy_asset) && o.amount.is_some()
+            });
+            match fee_output {
+                Some(o) => o.amount = o.amount.map(|a| a + fee),
+                None => pset.add_output(Output::new_explicit(
+                    Script::default(),
+                    fee,
+                    policy_asset,
+                    None,
+                )),
+            }
+        }
+
+        self.add_details(pset)?;
+        Ok(())
+    }
+
+    /// Blind the outputs of the PSET to be blinded with the inputs of this wallet
+    ///
+    /// If the outputs of other parties are still to be blinded, the blinding factors are left
+    /// unbalanced for the last one blinding.
+    pub fn blind_pset(&self, pset: &mut PartiallySignedTransaction) -> Result<(), Error> {
+        let wallet_utxos: HashMap<OutPoint, WalletTxOut> =
+            self.utxos()?.into_iter().map(|u| (u.outpoint, u)).collect();
+        let inp_txout_sec: HashMap<usize, TxOutSecrets> = pset
+            .inputs()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, input)| {
+                let outpoint = OutPoint::new(input.previous_txid, input.previous_output_index);
+                wallet_utxos.get(&outpoint).map(|u| (i, u.unblinded))
+            })
+            .collect();
+
+        let mut to_blind = false;
+        let mut others_to_blind = false;
+        for output in pset.outputs() {
+            let Some(blinder_index) = output.blinder_index else {
+                continue;
+            };
+            if output.blinding_key.is_none() {
+                continue;
+            }
+            let mine = inp_txout_sec.contains_key(&(blinder_index as usize));
+            match (mine, output.asset_comm.is_some()) {
+                // Blinding again would count the inputs of this wallet twice
+                (true, true) => return Err(Error::PsetAlreadyBlinded),
+                (true, false) => to_blind = true,
+                (false, false) => others_to_blind = true,
+                (false, true) => (),
+            }
+        }
+        if !to_blind {
+            return Err(Error::NothingToBlind);
+        }
+
+        let mut rng = thread_rng();
+        if others_to_blind {
+            pset.blind_non_last(&mut rng, &EC, &inp_txout_sec)?;
+        } else {
+            pset.blind_last(&mut rng, &EC, &inp_txout_sec)?;
+        }
+        Ok(())
+    }
+
+    /// Set the sequence and locktime needed to satisfy the given spend path on all the inputs
+    pub(crate) fn set_spend_path(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+        spend_path: SpendPath,
+    ) -> Result<(), Error> {
+        if !self.wollet_descriptor().has_spend_path(&spend_path) {
+            return Err(Error::SpendPathNotInDescriptor(spend_path.to_string()));
+        }
+        let not_satisfiable = || Error::SpendPathNotSatisfiable(spend_path.to_string());
+        let (tip_height, _) = self.tip()?;
+        // The transaction could be included at most in the next block
+        let next_height = tip_height + 1;
+        match spend_path {
+            SpendPath::Older(n) => {
+                let heights: HashMap<OutPoint, Option<u32>> = self
+                    .utxos()?
+                    .into_iter()
+                    .map(|u| (u.outpoint, u.height))
+                    .collect();
+                for input in pset.inputs_mut() {
+                    let outpoint = OutPoint::new(input.previous_txid, input.previous_output_index);
+                    let height = heights
+                        .get(&outpoint)
+                        .copied()
+                        .flatten()
+                        .ok_or_else(not_satisfiable)?;
+                    if next_height < height + n {
+                        return Err(not_satisfiable());
+                    }
+                    input.sequence = Some(Sequence::from_consensus(n));
+                }
+            }
+            SpendPath::After(n) => {
+                if next_height <= n {
+                    return Err(not_satisfiable());
+                }
+                pset.global.tx_data.fallback_locktime = Some(LockTime::from_consensus(n));
+                for input in pset.inputs_mut() {
+                    input.sequence = Some(Sequence::ENABLE_LOCKTIME_NO_RBF);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the issuance data in the input `idx`
+    ///
+    /// If `blinded`, the issued amounts are replaced by commitments, the value blinding factors
+    /// are added to `inp_txout_sec` so that the last blinder can balance them.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn set_issuance(
         &self,
         pset: &mut PartiallySignedTransaction,
+        inp_txout_sec: &mut HashMap<usize, TxOutSecrets>,
         idx: usize,
         satoshi_asset: u64,
         satoshi_token: u64,
         contract: Option<Contract>,
+        blinded: bool,
     ) -> Result<(AssetId, AssetId), Error> {
         let input = pset
             .inputs_mut()
@@ -115,6 +330,9 @@ impl Wollet {
         };
         input.issuance_asset_entropy = Some(contract_hash.to_byte_array());
 
+        if blinded {
+            self.blind_issuance(input, inp_txout_sec)?;
+        }
         let (asset, token) = input.issuance_ids();
 
         if let Some(contract) = contract.as_ref() {
@@ -126,6 +344,73 @@ impl Wollet {
         Ok((asset, token))
     }
 
+    /// Replace the explicit issuance amounts of `input` with commitments
+    ///
+    /// The rangeproofs nonce is the blinding key of the spent output, so the wallet can rewind
+    /// the amounts it issued, if the descriptor has no private blinding key a random one is used.
+    fn blind_issuance(
+        &self,
+        input: &mut Input,
+        inp_txout_sec: &mut HashMap<usize, TxOutSecrets>,
+    ) -> Result<(), Error> {
+        let mut rng = thread_rng();
+        let script_pubkey = &input
+            .witness_utxo
+            .as_ref()
+            .ok_or_else(|| Error::MissingVin)?
+            .script_pubkey;
+        let nonce = derive_blinding_key(self.descriptor(), script_pubkey)
+            .unwrap_or_else(|| SecretKey::new(&mut rng));
+
+        // The token id depends on whether the issuance is blinded, so it must be computed with
+        // the commitments set
+        let outpoint = OutPoint::new(input.previous_txid, input.previous_output_index);
+        let contract_hash =
+            ContractHash::from_byte_array(input.issuance_asset_entropy.unwrap_or_default());
+        let entropy = AssetId::generate_asset_entropy(outpoint, contract_hash);
+        let asset = AssetId::from_entropy(entropy);
+        let token = AssetId::reissuance_token_from_entropy(entropy, true);
+
+        for (satoshi, asset, is_token) in [
+            (input.issuance_value_amount, asset, false),
+            (input.issuance_inflation_keys, token, true),
+        ] {
+            let Some(satoshi) = satoshi else {
+                continue;
+            };
+            let vbf = ValueBlindingFactor::new(&mut rng);
+            let msg = RangeProofMessage {
+                asset,
+                bf: AssetBlindingFactor::zero(),
+            };
+            let (comm, rangeproof) = Value::Explicit(satoshi).blind_with_shared_secret(
+                &EC,
+                vbf,
+                nonce,
+                &Script::new(),
+                &msg,
+            )?;
+            let comm = comm.commitment().expect("confidential value");
+            if is_token {
+                input.issuance_inflation_keys = None;
+                input.issuance_inflation_keys_comm = Some(comm);
+                input.issuance_keys_rangeproof = Some(Box::new(rangeproof));
+            } else {
+                input.issuance_value_amount = None;
+                input.issuance_value_comm = Some(comm);
+                input.issuance_value_rangeproof = Some(Box::new(rangeproof));
+            }
+
+            // Issuances are pseudo-inputs, use indexes that can't clash with the inputs
+            let key = usize::MAX - usize::from(is_token);
+            inp_txout_sec.insert(
+                key,
+                TxOutSecrets::new(asset, AssetBlindingFactor::zero(), satoshi, vbf),
+            );
+        }
+        Ok(())
+    }
+
     pub(crate) fn set_reissuance(
         &self,
         pset: &mut PartiallySignedTransaction,
@@ -121,7 +121,12 @@ impl Store {
         };
 
         let start = batch * BATCH_SIZE;
-        let end = start + BATCH_SIZE;
+        // A descriptor without wildcard has a single script, in the first batch
+        let end = if descriptor.has_wildcard() {
+            start + BATCH_SIZE
+        } else {
+            start.max(1)
+        };
         let ext_int: Chain = descriptor.try_into().unwrap_or(Chain::External);
         for j in start..end {
             let child = ChildNumber::from_normal_idx(j)?;
@@ -187,6 +192,27 @@ mod tests {
         assert_ne!(x.value[0], x.value[1]);
     }
 
+    #[test]
+    fn test_script_batch_without_wildcard() {
+        let xpub = "tpubDD7tXK8KeQ3YY83yWq755fHY2JW8Ha8Q765tknUM5rSvjPcGWfUppDFMpQ1ScziKfW3ZNtZvAD7M3u7bSs7HofjTD3KP3YxPK7X6hwV8Rk2";
+        let master_blinding_key =
+            "9c8e4f05c7711a98c838be228bcb84924d4570ca53f35fa1c793e58841d47023";
+        let desc_str = format!("ct(slip77({}),elwpkh({}/0/5))", master_blinding_key, xpub);
+        let desc = ConfidentialDescriptor::<_>::from_str(&desc_str).unwrap();
+        let desc: WolletDescriptor = desc.try_into().unwrap();
+        assert!(!desc.is_ranged());
+
+        let store = Store::default();
+        let x = store
+            .get_script_batch(0, &desc.as_ref().descriptor)
+            .unwrap();
+        assert_eq!(x.value.len(), 1);
+        let x = store
+            .get_script_batch(1, &desc.as_ref().descriptor)
+            .unwrap();
+        assert!(x.value.is_empty());
+    }
+
     #[test]
     fn test_store_hash() {
         let mut store = Store::default();
@@ -0,0 +1,284 @@
+use crate::elements::encode::{deserialize_partial, serialize};
+use crate::elements::pset::raw::ProprietaryKey;
+use crate::elements::pset::PartiallySignedTransaction;
+use crate::elements::{AssetId, OutPoint};
+use crate::error::Error;
+use crate::model::{Recipient, WalletTxOut};
+use crate::wollet::Wollet;
+use std::collections::HashMap;
+
+/// Fee rate in sat/kvB used by [`Wollet::swap_accept()`] if none is given
+const DEFAULT_SWAP_FEE_RATE: f32 = 100.0;
+
+/// The terms of a swap proposal, stored in a proprietary global field of the PSET so that the
+/// counterparty knows what it receives without unblinding the inputs of the proposer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SwapOffer {
+    /// The asset and amount given by the proposer
+    pub send_asset: AssetId,
+    pub send_amount: u64,
+
+    /// The asset and amount the proposer wants in exchange
+    pub receive_asset: AssetId,
+    pub receive_amount: u64,
+}
+
+impl SwapOffer {
+    fn key() -> ProprietaryKey {
+        ProprietaryKey {
+            prefix: b"lwk".to_vec(),
+            subtype: 0,
+            key: b"swap".to_vec(),
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut value = serialize(&self.send_asset);
+        value.extend(serialize(&self.send_amount));
+        value.extend(serialize(&self.receive_asset));
+        value.extend(serialize(&self.receive_amount));
+        value
+    }
+
+    fn decode(value: &[u8]) -> Result<Self, Error> {
+        let err = |_| Error::InvalidSwap("invalid swap terms in the PSET".to_string());
+        let (send_asset, n1) = deserialize_partial(value).map_err(err)?;
+        let (send_amount, n2) = deserialize_partial(&value[n1..]).map_err(err)?;
+        let (receive_asset, n3) = deserialize_partial(&value[n1 + n2..]).map_err(err)?;
+        let (receive_amount, _) = deserialize_partial(&value[n1 + n2 + n3..]).map_err(err)?;
+        Ok(Self {
+            send_asset,
+            send_amount,
+            receive_asset,
+            receive_amount,
+        })
+    }
+
+    pub(crate) fn from_pset(pset: &PartiallySignedTransaction) -> Result<Option<Self>, Error> {
+        pset.global
+            .proprietary
+            .get(&Self::key())
+            .map(|v| Self::decode(v))
+            .transpose()
+    }
+}
+
+/// Select the largest UTXOs of `asset` until `satoshi` is reached
+fn select_coins(
+    utxos: &[WalletTxOut],
+    asset: AssetId,
+    satoshi: u64,
+) -> Result<Vec<OutPoint>, Error> {
+    let mut candidates: Vec<_> = utxos
+        .iter()
+        .filter(|u| u.unblinded.asset == asset)
+        .collect();
+    candidates.sort_by(|a, b| b.unblinded.value.cmp(&a.unblinded.value));
+    let mut selected = vec![];
+    let mut available = 0;
+    for utxo in candidates {
+        if available >= satoshi {
+            break;
+        }
+        available += utxo.unblinded.value;
+        selected.push(utxo.outpoint);
+    }
+    if available < satoshi {
+        return Err(Error::SwapInsufficientFunds {
+            asset,
+            needed: satoshi,
+            available,
+        });
+    }
+    Ok(selected)
+}
+
+impl Wollet {
+    /// Create a PSET proposing to swap `send_amount` of `send_asset` of this wallet for
+    /// `receive_amount` of `receive_asset`
+    ///
+    /// The PSET contains the wallet inputs, the output receiving `receive_asset` and the change,
+    /// all unblinded: since the signatures commit to the whole transaction it can be signed only
+    /// once the counterparty has completed it with [`Wollet::swap_accept()`] and the proposer has
+    /// blinded its outputs with [`Wollet::blind_pset()`].
+    ///
+    /// The outputs in `excluded_utxos` are not spent.
+    pub fn swap_propose(
+        &self,
+        send_asset: AssetId,
+        send_amount: u64,
+        receive_asset: AssetId,
+        receive_amount: u64,
+        excluded_utxos: &[OutPoint],
+    ) -> Result<PartiallySignedTransaction, Error> {
+        if send_asset == receive_asset {
+            return Err(Error::InvalidSwap(
+                "the assets to send and receive must differ".to_string(),
+            ));
+        }
+        if send_amount == 0 || receive_amount == 0 {
+            return Err(Error::InvalidSwap(
+                "the amounts must be greater than zero".to_string(),
+            ));
+        }
+        let utxos = self.spendable_utxos(excluded_utxos)?;
+        let inputs = select_coins(&utxos, send_asset, send_amount)?;
+        let address = self.address(None)?;
+        let recipient = Recipient::from_address(receive_amount, address.address(), receive_asset);
+
+        let mut pset = PartiallySignedTransaction::new_v2();
+        let given = HashMap::from([(send_asset, send_amount)]);
+        self.add_contribution(&mut pset, &inputs, &[recipient], 0, &given)?;
+        let offer = SwapOffer {
+            send_asset,
+            send_amount,
+            receive_asset,
+            receive_amount,
+        };
+        pset.global
+            .proprietary
+            .insert(SwapOffer::key(), offer.encode());
+        Ok(pset)
+    }
+
+    /// Complete a swap proposed with [`Wollet::swap_propose()`]
+    ///
+    /// Add the wallet inputs paying the amount asked by the proposer and the fee, which is paid by
+    /// this wallet, the output receiving the offered amount and the change, then blind the outputs
+    /// of the assets of this wallet. The proposer completes the blinding with
+    /// [`Wollet::blind_pset()`], then both parties sign.
+    ///
+    /// The outputs in `excluded_utxos` are not spent.
+    pub fn swap_accept(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+        fee_rate: Option<f32>,
+        excluded_utxos: &[OutPoint],
+    ) -> Result<(), Error> {
+        let offer = SwapOffer::from_pset(pset)?.ok_or(Error::NotASwapProposal)?;
+        let asked: u64 = pset
+            .outputs()
+            .iter()
+            .filter(|o| o.asset == Some(offer.receive_asset) && !o.script_pubkey.is_empty())
+            .filter_map(|o| o.amount)
+            .sum();
+        if asked != offer.receive_amount {
+            return Err(Error::InvalidSwap(format!(
+                "the proposer asks {asked} of asset {} but the terms are {}",
+                offer.receive_asset, offer.receive_amount
+            )));
+        }
+
+        let fee_rate = fee_rate.unwrap_or(DEFAULT_SWAP_FEE_RATE);
+        let utxos = self.spendable_utxos(excluded_utxos)?;
+        let address = self.address(None)?;
+        let recipient =
+            Recipient::from_address(offer.send_amount, address.address(), offer.send_asset);
+        let given = HashMap::from([(offer.receive_asset, offer.receive_amount)]);
+        let policy_asset = self.policy_asset();
+
+        // Raise the fee until it matches the fee rate of the transaction it's paying for
+        let mut fee = 0;
+        loop {
+            let mut needed = given.clone();
+            *needed.entry(policy_asset).or_default() += fee;
+            let mut inputs = vec![];
+            for (asset, satoshi) in needed {
+                inputs.extend(select_coins(&utxos, asset, satoshi)?);
+            }
+
+            let mut completed = pset.clone();
+            self.add_contribution(&mut completed, &inputs, &[recipient.clone()], fee, &given)?;
+            self.swap_blinders(&mut completed, pset, &offer, &utxos);
+            let estimated = self.estimate_swap_fee(&completed, fee_rate)?;
+            if estimated <= fee {
+                self.blind_pset(&mut completed)?;
+                *pset = completed;
+                return Ok(());
+            }
+            fee = estimated;
+        }
+    }
+
+    fn spendable_utxos(&self, excluded_utxos: &[OutPoint]) -> Result<Vec<WalletTxOut>, Error> {
+        let mut utxos = self.utxos()?;
+        utxos.retain(|u| !excluded_utxos.contains(&u.outpoint));
+        Ok(utxos)
+    }
+
+    /// Assign each output receiving the swapped assets to a blinder having inputs of that asset
+    ///
+    /// Surjection proofs require the blinding factors of an input with the same asset, so the
+    /// proposer blinds the output receiving its asset and this wallet the output paying the
+    /// proposer.
+    fn swap_blinders(
+        &self,
+        completed: &mut PartiallySignedTransaction,
+        proposal: &PartiallySignedTransaction,
+        offer: &SwapOffer,
+        utxos: &[WalletTxOut],
+    ) {
+        let mine: HashMap<OutPoint, AssetId> = utxos
+            .iter()
+            .map(|u| (u.outpoint, u.unblinded.asset))
+            .collect();
+        let paying_input = completed.inputs().iter().position(|i| {
+            let outpoint = OutPoint::new(i.previous_txid, i.previous_output_index);
+            mine.get(&outpoint) == Some(&offer.receive_asset)
+        });
+        let n_outputs = proposal.n_outputs();
+        for (i, output) in completed.outputs_mut().iter_mut().enumerate() {
+            if i < n_outputs && output.asset == Some(offer.receive_asset) {
+                output.blinder_index = paying_input.map(|i| i as u32);
+            } else if i >= n_outputs && output.asset == Some(offer.send_asset) {
+                output.blinder_index = Some(0);
+            }
+        }
+    }
+
+    /// Estimate the fee of the transaction once every output is blinded and every input signed
+    ///
+    /// The outputs still to be blinded by other parties are assumed as large as the ones blinded
+    /// by this wallet and their inputs as expensive to satisfy as the ones of this wallet.
+    fn estimate_swap_fee(
+        &self,
+        pset: &PartiallySignedTransaction,
+        fee_rate: f32,
+    ) -> Result<u64, Error> {
+        let unblinded_weight = pset.extract_tx()?.weight();
+        let mut blinded = pset.clone();
+        self.blind_pset(&mut blinded)?;
+        let blinded_weight = blinded.extract_tx()?.weight();
+
+        let to_blind = |p: &PartiallySignedTransaction| {
+            p.outputs()
+                .iter()
+                .filter(|o| o.blinding_key.is_some() && o.asset_comm.is_none())
+                .count()
+        };
+        let blinded_by_me = to_blind(pset) - to_blind(&blinded);
+        let blinded_by_others = to_blind(&blinded);
+        let output_weight = (blinded_weight - unblinded_weight) / blinded_by_me.max(1);
+
+        let mut my_inputs_weight = 0;
+        let mut my_inputs = 0;
+        for input in pset.inputs() {
+            let Some(txout) = input.witness_utxo.as_ref() else {
+                continue;
+            };
+            if let Ok(desc) = self.definite_descriptor(&txout.script_pubkey) {
+                my_inputs_weight += desc.max_weight_to_satisfy()?;
+                my_inputs += 1;
+            }
+        }
+        let other_inputs = pset.n_inputs() - my_inputs;
+        let input_weight = my_inputs_weight / my_inputs.max(1);
+
+        let weight = blinded_weight
+            + blinded_by_others * output_weight
+            + my_inputs_weight
+            + other_inputs * input_weight;
+        let vsize = (weight + 4 - 1) / 4;
+        Ok((vsize as f32 * fee_rate / 1000.0).ceil() as u64)
+    }
+}
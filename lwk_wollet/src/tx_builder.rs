@@ -5,17 +5,20 @@ use elements::{
     issuance::ContractHash,
     pset::{Output, PartiallySignedTransaction},
     secp256k1_zkp::ZERO_TWEAK,
-    Address, AssetId, Script, Transaction,
+    Address, AssetId, OutPoint, Script, Transaction,
 };
-use rand::thread_rng;
+use rand::{seq::SliceRandom, thread_rng, Rng};
 
 use crate::{
     hashes::Hash,
-    model::{IssuanceDetails, Recipient},
+    model::{IssuanceDetails, Recipient, SpendPath},
     pset_create::{validate_address, IssuanceRequest},
     Contract, ElementsNetwork, Error, UnvalidatedRecipient, Wollet, EC,
 };
 
+/// The minimum relay fee rate of the network, in sat/kvb
+const MIN_FEE_RATE: f32 = 100.0;
+
 pub fn extract_issuances(tx: &Transaction) -> Vec<IssuanceDetails> {
     let mut r = vec![];
     for (vin, txin) in tx.input.iter().enumerate() {
@@ -65,9 +68,18 @@ pub struct TxBuilder {
     network: ElementsNetwork,
     recipients: Vec<Recipient>,
     fee_rate: f32,
+    fee: Option<u64>,
     issuance_request: IssuanceRequest,
+    blinded_issuance: bool,
+    spend_path: Option<SpendPath>,
+    excluded_utxos: HashSet<OutPoint>,
+    drain_lbtc_to: Option<Address>,
+    privacy: bool,
 }
 
+/// Minimum L-BTC change, in satoshi, to split it in a decoy output when [`TxBuilder::privacy()`] is set
+pub(crate) const DECOY_MIN_CHANGE: u64 = 10_000;
+
 impl TxBuilder {
     /// Creates a transaction builder for bindings code. From rust use [`WolletTxBuilder`]
     pub fn new(network: ElementsNetwork) -> Self {
@@ -75,7 +87,13 @@ impl TxBuilder {
             network,
             recipients: vec![],
             fee_rate: 100.0,
+            fee: None,
             issuance_request: IssuanceRequest::None,
+            blinded_issuance: false,
+            spend_path: None,
+            excluded_utxos: HashSet::new(),
+            drain_lbtc_to: None,
+            privacy: false,
         }
     }
 
@@ -140,6 +158,49 @@ impl TxBuilder {
         self
     }
 
+    /// Pay exactly `fee` satoshi, instead of computing the fee from the fee rate
+    ///
+    /// `finish` fails if `fee` is below the minimum relay fee for the size of the transaction.
+    pub fn fee(mut self, fee: Option<u64>) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Satisfy the timelocked branch of the descriptor given by `spend_path`
+    ///
+    /// Sequence and locktime are set accordingly, `finish` fails if the descriptor doesn't have
+    /// the branch or if the timelock is not expired yet.
+    pub fn spend_path(mut self, spend_path: Option<SpendPath>) -> Self {
+        self.spend_path = spend_path;
+        self
+    }
+
+    /// Never spend the given outputs, for instance because they are reserved by another
+    /// transaction waiting to be signed
+    pub fn exclude_utxos(mut self, outpoints: &[OutPoint]) -> Self {
+        self.excluded_utxos.extend(outpoints);
+        self
+    }
+
+    /// Send the L-BTC change to `address` instead of a change address of the wallet
+    ///
+    /// Since all the L-BTC inputs are spent, without recipients this sweeps the L-BTC of the
+    /// wallet (minus the excluded utxos and the fee) to `address`.
+    pub fn drain_lbtc_to(mut self, address: Address) -> Self {
+        self.drain_lbtc_to = Some(address);
+        self
+    }
+
+    /// Randomize the order of the outputs and, if the L-BTC change is large enough, split it in
+    /// two change outputs so it's not obvious which one is the change
+    ///
+    /// The fee output is always kept last. The decoy output is an additional blinded output,
+    /// so the transaction is bigger and pays a higher fee for the same fee rate.
+    pub fn privacy(mut self, privacy: bool) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
     /// Issue an asset
     ///
     /// There will be `asset_sats` units of this asset that will be received by
@@ -182,6 +243,16 @@ impl TxBuilder {
         Ok(self)
     }
 
+    /// Hide the amounts of the asset and of the reissuance token created by [`Self::issue_asset()`]
+    ///
+    /// By default the issued amounts are explicit: anyone can verify the supply of the asset,
+    /// which registries and exchanges usually require. Blinded amounts can be unblinded only by
+    /// the issuing wallet, with its private blinding key, and give a different reissuance token.
+    pub fn blinded_issuance(mut self, blinded: bool) -> Self {
+        self.blinded_issuance = blinded;
+        self
+    }
+
     /// Reissue an asset
     ///
     /// reissue the asset defined by `asset_to_reissue`, provided the reissuance token is owned
@@ -219,6 +290,12 @@ impl TxBuilder {
     }
 
     pub fn finish(self, wollet: &Wollet) -> Result<PartiallySignedTransaction, Error> {
+        let asset_utxos = |asset: &AssetId| -> Result<Vec<_>, Error> {
+            let mut utxos = wollet.asset_utxos(asset)?;
+            utxos.retain(|u| !self.excluded_utxos.contains(&u.outpoint));
+            Ok(utxos)
+        };
+
         // Init PSET
         let mut pset = PartiallySignedTransaction::new_v2();
         let mut inp_txout_sec = HashMap::new();
@@ -242,7 +319,7 @@ impl TxBuilder {
                 wollet.add_output(&mut pset, addressee)?;
                 satoshi_out += addressee.satoshi;
             }
-            for utxo in wollet.asset_utxos(&asset)? {
+            for utxo in asset_utxos(&asset)? {
                 wollet.add_input(&mut pset, &mut inp_txout_sec, &mut inp_weight, &utxo)?;
                 satoshi_in += utxo.unblinded.value;
                 if satoshi_in >= satoshi_out {
@@ -273,7 +350,7 @@ impl TxBuilder {
         }
 
         // FIXME: For implementation simplicity now we always add all L-BTC inputs
-        for utxo in wollet.asset_utxos(&wollet.policy_asset())? {
+        for utxo in asset_utxos(&wollet.policy_asset())? {
             wollet.add_input(&mut pset, &mut inp_txout_sec, &mut inp_weight, &utxo)?;
             satoshi_in += utxo.unblinded.value;
         }
@@ -290,8 +367,15 @@ impl TxBuilder {
             ) => {
                 // At least a L-BTC input for the fee was added.
                 let idx = 0;
-                let (asset, token) =
-                    wollet.set_issuance(&mut pset, idx, satoshi_asset, satoshi_token, contract)?;
+                let (asset, token) = wollet.set_issuance(
+                    &mut pset,
+                    &mut inp_txout_sec,
+                    idx,
+                    satoshi_asset,
+                    satoshi_token,
+                    contract,
+                    self.blinded_issuance,
+                )?;
 
                 let addressee = match address_asset {
                     Some(address) => Recipient::from_address(satoshi_asset, &address, asset),
@@ -332,7 +416,7 @@ impl TxBuilder {
                         Some((idx, u)) => (*idx, u.asset_bf),
                         None => {
                             // Add an input sending the token,
-                            let utxos_token = wollet.asset_utxos(&token)?;
+                            let utxos_token = asset_utxos(&token)?;
                             let utxo_token = utxos_token
                                 .first()
                                 .ok_or_else(|| Error::InsufficientFunds)?;
@@ -377,6 +461,10 @@ impl TxBuilder {
             }
         }
 
+        if let Some(spend_path) = self.spend_path {
+            wollet.set_spend_path(&mut pset, spend_path)?;
+        }
+
         // Add a temporary fee, and always add a change output,
         // then we'll tweak those values to match the given fee rate.
         let temp_fee = 1000;
@@ -384,12 +472,20 @@ impl TxBuilder {
             return Err(Error::InsufficientFunds);
         }
         let satoshi_change = satoshi_in - satoshi_out - temp_fee;
-        let addressee = wollet.addressee_change(
-            satoshi_change,
-            wollet.policy_asset(),
-            &mut last_unused_internal,
-        )?;
-        wollet.add_output(&mut pset, &addressee)?;
+        let decoy =
+            self.privacy && self.drain_lbtc_to.is_none() && satoshi_change >= DECOY_MIN_CHANGE;
+        let n_change = if decoy { 2 } else { 1 };
+        for _ in 0..n_change {
+            let addressee = match &self.drain_lbtc_to {
+                Some(address) => Recipient::from_address(satoshi_change, address, policy_asset),
+                None => wollet.addressee_change(
+                    satoshi_change / n_change,
+                    wollet.policy_asset(),
+                    &mut last_unused_internal,
+                )?,
+            };
+            wollet.add_output(&mut pset, &addressee)?;
+        }
         let fee_output =
             Output::new_explicit(Script::default(), temp_fee, wollet.policy_asset(), None);
         pset.add_output(fee_output);
@@ -402,23 +498,51 @@ impl TxBuilder {
         };
 
         let vsize = (weight + 4 - 1) / 4;
-        let fee = (vsize as f32 * self.fee_rate / 1000.0).ceil() as u64;
-        if satoshi_in < (satoshi_out + temp_fee) {
+        let fee = match self.fee {
+            Some(fee) => {
+                let min = (vsize as f32 * MIN_FEE_RATE / 1000.0).ceil() as u64;
+                if fee < min {
+                    return Err(Error::FeeTooLow { fee, min });
+                }
+                fee
+            }
+            None => (vsize as f32 * self.fee_rate / 1000.0).ceil() as u64,
+        };
+        if satoshi_in < (satoshi_out + fee) {
             return Err(Error::InsufficientFunds);
         }
         let satoshi_change = satoshi_in - satoshi_out - fee;
+        // The decoy was decided with the temporary fee, with the actual fee the change could be
+        // too small to split, in that case keep a single change output.
+        // The fee computed accounting the decoy output is kept, it's slightly higher than needed.
+        let decoy = decoy && satoshi_change >= DECOY_MIN_CHANGE;
+        if n_change == 2 && !decoy {
+            let n_outputs = pset.n_outputs();
+            pset.remove_output(n_outputs - 2);
+        }
         // Replace change and fee outputs
+        let mut rng = thread_rng();
         let n_outputs = pset.n_outputs();
         let outputs = pset.outputs_mut();
-        let change_output = &mut outputs[n_outputs - 2]; // index check: we always have the lbtc change and the fee output at least
-        change_output.amount = Some(satoshi_change);
+        if decoy {
+            // Split the change at a random point so the two outputs can't be told apart
+            let decoy_amount = rng.gen_range(satoshi_change / 4..=satoshi_change * 3 / 4);
+            outputs[n_outputs - 3].amount = Some(satoshi_change - decoy_amount);
+            outputs[n_outputs - 2].amount = Some(decoy_amount);
+        } else {
+            let change_output = &mut outputs[n_outputs - 2]; // index check: we always have the lbtc change and the fee output at least
+            change_output.amount = Some(satoshi_change);
+        }
         let fee_output = &mut outputs[n_outputs - 1];
         fee_output.amount = Some(fee);
 
-        // TODO inputs/outputs(except fee) randomization, not trivial because of blinder_index on inputs
+        // Outputs are shuffled except the fee that stays last.
+        // TODO inputs randomization, not trivial because of blinder_index on inputs
+        if self.privacy {
+            outputs[..n_outputs - 1].shuffle(&mut rng);
+        }
 
         // Blind the transaction
-        let mut rng = thread_rng();
         pset.blind_last(&mut rng, &EC, &inp_txout_sec)?;
 
         // Add details to the pset from our descriptor, like bip32derivation and keyorigin
@@ -516,6 +640,46 @@ impl<'a> WolletTxBuilder<'a> {
         }
     }
 
+    /// Wrapper of [`TxBuilder::fee()`]
+    pub fn fee(self, fee: Option<u64>) -> Self {
+        Self {
+            wollet: self.wollet,
+            inner: self.inner.fee(fee),
+        }
+    }
+
+    /// Wrapper of [`TxBuilder::spend_path()`]
+    pub fn spend_path(self, spend_path: Option<SpendPath>) -> Self {
+        Self {
+            wollet: self.wollet,
+            inner: self.inner.spend_path(spend_path),
+        }
+    }
+
+    /// Wrapper of [`TxBuilder::exclude_utxos()`]
+    pub fn exclude_utxos(self, outpoints: &[OutPoint]) -> Self {
+        Self {
+            wollet: self.wollet,
+            inner: self.inner.exclude_utxos(outpoints),
+        }
+    }
+
+    /// Wrapper of [`TxBuilder::drain_lbtc_to()`]
+    pub fn drain_lbtc_to(self, address: Address) -> Self {
+        Self {
+            wollet: self.wollet,
+            inner: self.inner.drain_lbtc_to(address),
+        }
+    }
+
+    /// Wrapper of [`TxBuilder::privacy()`]
+    pub fn privacy(self, privacy: bool) -> Self {
+        Self {
+            wollet: self.wollet,
+            inner: self.inner.privacy(privacy),
+        }
+    }
+
     /// Wrapper of [`TxBuilder::issue_asset()`]
     pub fn issue_asset(
         self,
@@ -537,6 +701,14 @@ impl<'a> WolletTxBuilder<'a> {
         })
     }
 
+    /// Wrapper of [`TxBuilder::blinded_issuance()`]
+    pub fn blinded_issuance(self, blinded: bool) -> Self {
+        Self {
+            wollet: self.wollet,
+            inner: self.inner.blinded_issuance(blinded),
+        }
+    }
+
     /// Wrapper of [`TxBuilder::reissue_asset()`]
     pub fn reissue_asset(
         self,
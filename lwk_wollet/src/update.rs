@@ -2,7 +2,7 @@ use crate::descriptor::Chain;
 use crate::elements::{OutPoint, Script, Transaction, TxOutSecrets, Txid};
 use crate::error::Error;
 use crate::store::{Height, Timestamp};
-use crate::{Wollet, WolletDescriptor};
+use crate::{WalletTxOut, Wollet, WolletDescriptor};
 use aes_gcm_siv::aead::generic_array::GenericArray;
 use aes_gcm_siv::aead::AeadMutInPlace;
 use base64::prelude::*;
@@ -38,6 +38,32 @@ pub struct Update {
     pub tip: BlockHeader,
 }
 
+/// The changes to the wallet UTXOs made by the updates applied to a [`Wollet`], returned by
+/// [`Wollet::take_utxo_changes()`]
+#[derive(Clone, Debug)]
+pub struct UtxoChanges {
+    /// The outputs that became wallet UTXOs
+    pub added: Vec<WalletTxOut>,
+
+    /// The outpoints spent by the new transactions, some may not be wallet UTXOs
+    pub spent: Vec<OutPoint>,
+
+    /// The changes can't be applied incrementally, for example because a transaction has been
+    /// removed by a reorg, the UTXOs must be computed from scratch
+    pub reset: bool,
+}
+
+impl Default for UtxoChanges {
+    /// A freshly created wallet has no previous UTXOs the changes could be applied to
+    fn default() -> Self {
+        Self {
+            added: vec![],
+            spent: vec![],
+            reset: true,
+        }
+    }
+}
+
 impl Update {
     pub fn only_tip(&self) -> bool {
         self.new_txs.is_empty()
@@ -130,6 +156,23 @@ impl Wollet {
                 store_tip_height: store.cache.tip.0,
             });
         }
+        self.updates_applied += 1;
+
+        let spent: Vec<OutPoint> = new_txs
+            .txs
+            .iter()
+            .filter(|(txid, _)| !store.cache.all_txs.contains_key(txid))
+            .flat_map(|(_, tx)| tx.input.iter().map(|i| i.previous_output))
+            .collect();
+        // Unblinding an output of a transaction already in the wallet is not tracked
+        let unblinds_known_tx = new_txs
+            .unblinds
+            .iter()
+            .any(|(outpoint, _)| store.cache.heights.contains_key(&outpoint.txid));
+        let txs_removed = txid_height_delete
+            .iter()
+            .any(|txid| store.cache.heights.contains_key(txid));
+        let utxo_changes_reset = txs_removed || unblinds_known_tx;
 
         store.cache.tip = (tip.height, tip.block_hash());
         store.cache.all_txs.extend(new_txs.txs);
@@ -144,6 +187,11 @@ impl Wollet {
             .cache
             .heights
             .retain(|k, _| !txid_height_delete.contains(k));
+        let txids_added: HashSet<Txid> = txid_height
+            .iter()
+            .map(|(txid, _)| *txid)
+            .filter(|txid| !store.cache.heights.contains_key(txid))
+            .collect();
         store.cache.heights.extend(txid_height.clone());
         store.cache.timestamps.extend(timestamps);
         store
@@ -188,12 +236,51 @@ impl Wollet {
                 .store(last_used_internal + 1, atomic::Ordering::Relaxed);
         }
 
+        self.track_utxo_changes(utxo_changes_reset, &txids_added, spent)?;
+
         if do_persist {
             self.persister.push(update)?;
         }
 
         Ok(())
     }
+
+    fn track_utxo_changes(
+        &mut self,
+        reset: bool,
+        txids_added: &HashSet<Txid>,
+        spent: Vec<OutPoint>,
+    ) -> Result<(), Error> {
+        if reset || self.utxo_changes.reset {
+            // The UTXOs are going to be computed from scratch, no need to collect the changes
+            self.utxo_changes = UtxoChanges::default();
+            return Ok(());
+        }
+        let spent_set: HashSet<OutPoint> = spent.iter().cloned().collect();
+        for txid in txids_added {
+            if let Some(height) = self.store.cache.heights.get(txid) {
+                let added = self.tx_txos(txid, *height, &spent_set)?;
+                self.utxo_changes.added.extend(added);
+            }
+        }
+        self.utxo_changes.spent.extend(spent);
+        Ok(())
+    }
+
+    /// Take the changes to the wallet UTXOs made by the updates applied since the previous call
+    ///
+    /// Applying them to the UTXOs returned by [`Wollet::utxos()`] at the time of the previous call
+    /// gives the current ones, unless [`UtxoChanges::reset`] is set.
+    pub fn take_utxo_changes(&mut self) -> UtxoChanges {
+        std::mem::replace(
+            &mut self.utxo_changes,
+            UtxoChanges {
+                added: vec![],
+                spent: vec![],
+                reset: false,
+            },
+        )
+    }
 }
 
 impl Encodable for DownloadTxResult {
@@ -8,8 +8,9 @@ use crate::error::Error;
 use crate::hashes::Hash;
 use crate::model::{AddressResult, IssuanceDetails, WalletTx, WalletTxOut};
 use crate::persister::PersistError;
-use crate::store::Store;
+use crate::store::{Height, Store};
 use crate::tx_builder::{extract_issuances, WolletTxBuilder};
+use crate::update::UtxoChanges;
 use crate::util::EC;
 use crate::{FsPersister, NoPersist, Persister, Update, WolletDescriptor};
 use elements::bitcoin::bip32::ChildNumber;
@@ -32,6 +33,12 @@ pub struct Wollet {
     pub(crate) store: Store,
     pub(crate) persister: Arc<dyn Persister + Send + Sync>,
     descriptor: WolletDescriptor,
+
+    /// Not part of the wallet content, excluded from the hash
+    pub(crate) updates_applied: u64,
+
+    /// Not part of the wallet content, excluded from the hash
+    pub(crate) utxo_changes: UtxoChanges,
 }
 
 impl std::fmt::Debug for Wollet {
@@ -63,6 +70,8 @@ impl Wollet {
             config,
             descriptor,
             persister,
+            updates_applied: 0,
+            utxo_changes: UtxoChanges::default(),
         };
 
         for i in 0.. {
@@ -130,8 +139,11 @@ impl Wollet {
     ///
     /// If Some return the address at the given index,
     /// otherwise the last unused address.
+    ///
+    /// A descriptor without wildcard has a single address, at index 0, whatever the index is.
     pub fn address(&self, index: Option<u32>) -> Result<AddressResult, Error> {
         let index = match index {
+            _ if !self.descriptor.is_ranged() => 0,
             Some(i) => i,
             None => self
                 .store
@@ -153,8 +165,11 @@ impl Wollet {
     ///
     /// If Some return the address at the given index,
     /// otherwise the last unused address.
+    ///
+    /// A descriptor without wildcard has a single address, at index 0, whatever the index is.
     pub fn change(&self, index: Option<u32>) -> Result<AddressResult, Error> {
         let index = match index {
+            _ if !self.descriptor.is_ranged() => 0,
             Some(i) => i,
             None => self
                 .store
@@ -177,50 +192,58 @@ impl Wollet {
             HashSet::new()
         };
         for (tx_id, height) in self.store.cache.heights.iter() {
-            let tx = self
-                .store
-                .cache
-                .all_txs
-                .get(tx_id)
-                .ok_or_else(|| Error::Generic(format!("txos no tx {}", tx_id)))?;
-            let tx_txos: Vec<WalletTxOut> = {
-                tx.output
-                    .clone()
-                    .into_iter()
-                    .enumerate()
-                    .map(|(vout, output)| {
-                        (
-                            OutPoint {
-                                txid: tx.txid(),
-                                vout: vout as u32,
-                            },
-                            output,
-                        )
-                    })
-                    .filter(|(outpoint, _)| !spent.contains(outpoint))
-                    .filter_map(|(outpoint, output)| {
-                        if let Some(unblinded) = self.store.cache.unblinded.get(&outpoint) {
-                            let index = self.index(&output.script_pubkey).ok()?;
-                            return Some(WalletTxOut {
-                                outpoint,
-                                script_pubkey: output.script_pubkey,
-                                height: *height,
-                                unblinded: *unblinded,
-                                wildcard_index: index.1,
-                                ext_int: index.0,
-                            });
-                        }
-                        None
-                    })
-                    .collect()
-            };
-            txos.extend(tx_txos);
+            txos.extend(self.tx_txos(tx_id, *height, &spent)?);
         }
         txos.sort_by(|a, b| b.unblinded.value.cmp(&a.unblinded.value));
 
         Ok(txos)
     }
 
+    /// The wallet outputs of the given transaction, excluding the `spent` ones
+    pub(crate) fn tx_txos(
+        &self,
+        tx_id: &Txid,
+        height: Option<Height>,
+        spent: &HashSet<OutPoint>,
+    ) -> Result<Vec<WalletTxOut>, Error> {
+        let tx = self
+            .store
+            .cache
+            .all_txs
+            .get(tx_id)
+            .ok_or_else(|| Error::Generic(format!("txos no tx {}", tx_id)))?;
+        Ok(tx
+            .output
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(vout, output)| {
+                (
+                    OutPoint {
+                        txid: tx.txid(),
+                        vout: vout as u32,
+                    },
+                    output,
+                )
+            })
+            .filter(|(outpoint, _)| !spent.contains(outpoint))
+            .filter_map(|(outpoint, output)| {
+                if let Some(unblinded) = self.store.cache.unblinded.get(&outpoint) {
+                    let index = self.index(&output.script_pubkey).ok()?;
+                    return Some(WalletTxOut {
+                        outpoint,
+                        script_pubkey: output.script_pubkey,
+                        height,
+                        unblinded: *unblinded,
+                        wildcard_index: index.1,
+                        ext_int: index.0,
+                    });
+                }
+                None
+            })
+            .collect())
+    }
+
     /// Get the wallet UTXOs
     pub fn utxos(&self) -> Result<Vec<WalletTxOut>, Error> {
         self.txos_inner(true)
@@ -252,6 +275,15 @@ impl Wollet {
         self.balance_from_utxos(&utxos)
     }
 
+    /// Number of updates applied to the wallet, including the persisted ones applied when it's
+    /// created
+    ///
+    /// The wallet content changes only when an update is applied, so values computed from it,
+    /// like the balance, are still valid until this number changes.
+    pub fn updates_applied(&self) -> u64 {
+        self.updates_applied
+    }
+
     /// Get the wallet transactions with their heights (if confirmed)
     pub fn transactions(&self) -> Result<Vec<WalletTx>, Error> {
         let mut txs = vec![];
@@ -358,7 +390,8 @@ impl Wollet {
         })
     }
 
-    pub(crate) fn index(&self, script_pubkey: &Script) -> Result<(Chain, u32), Error> {
+    /// The chain and the derivation index of a script of the wallet
+    pub fn index(&self, script_pubkey: &Script) -> Result<(Chain, u32), Error> {
         let (ext_int, index) = self
             .store
             .cache
@@ -374,6 +407,43 @@ impl Wollet {
         Ok((*ext_int, *index))
     }
 
+    /// Estimate the weight of the transaction once every input is signed and finalized
+    ///
+    /// Inputs already finalized are counted with their witness, the others with the largest
+    /// witness satisfying the descriptor, e.g. for multisig with the signatures of the threshold.
+    /// Every input not finalized must be of this wallet.
+    pub fn estimate_finalized_weight(
+        &self,
+        pset: &PartiallySignedTransaction,
+    ) -> Result<usize, Error> {
+        let mut weight = pset.extract_tx()?.weight();
+        for input in pset.inputs() {
+            if input.final_script_witness.is_some() || input.final_script_sig.is_some() {
+                continue;
+            }
+            let txout = input
+                .witness_utxo
+                .as_ref()
+                .ok_or_else(|| Error::Generic("missing witness utxo".into()))?;
+            weight += self
+                .definite_descriptor(&txout.script_pubkey)?
+                .max_weight_to_satisfy()?;
+        }
+        Ok(weight)
+    }
+
+    /// Estimate the weight added to a transaction by spending an output of this wallet with
+    /// the given script, once signed
+    pub fn spend_weight(&self, script_pubkey: &Script) -> Result<usize, Error> {
+        // Previous outpoint, script sig length and sequence, the empty issuance and pegin
+        // witnesses
+        let base = 4 * (32 + 4 + 1 + 4) + 3;
+        let satisfaction = self
+            .definite_descriptor(script_pubkey)?
+            .max_weight_to_satisfy()?;
+        Ok(base + satisfaction)
+    }
+
     // TODO: move to WolletDescriptor::definite_descriptor(index)
     pub(crate) fn definite_descriptor(
         &self,
@@ -504,6 +574,25 @@ pub fn full_scan_with_electrum_client(
     Ok(())
 }
 
+/// Like [`full_scan_with_electrum_client()`] but the scan can be stopped by setting `cancel`,
+/// in which case the wallet is left untouched, and it reports how far it has gone in `progress`
+#[cfg(feature = "electrum")]
+pub fn full_scan_with_electrum_client_cancellable(
+    wollet: &mut Wollet,
+    electrum_client: &mut crate::ElectrumClient,
+    cancel: &std::sync::atomic::AtomicBool,
+    progress: &crate::ScanProgress,
+) -> Result<(), Error> {
+    use crate::BlockchainBackend;
+
+    let update = electrum_client.full_scan_cancellable(wollet, cancel, progress)?;
+    if let Some(update) = update {
+        wollet.apply_update(update)?
+    }
+
+    Ok(())
+}
+
 fn tx_fee(tx: &Transaction) -> u64 {
     tx.output
         .iter()
@@ -574,12 +663,13 @@ mod tests {
     use crate::elements::bitcoin::bip32::{Xpriv, Xpub};
     use crate::elements::bitcoin::network::Network;
     use crate::elements::AddressParams;
+    use crate::tx_builder::DECOY_MIN_CHANGE;
     use crate::NoPersist;
     use elements_miniscript::confidential::bare::tweak_private_key;
     use elements_miniscript::confidential::Key;
     use elements_miniscript::descriptor::checksum::desc_checksum;
     use elements_miniscript::descriptor::DescriptorSecretKey;
-    use lwk_common::{singlesig_desc, DescriptorBlindingKey, Singlesig};
+    use lwk_common::{singlesig_desc, DescriptorBlindingKey, Signer, Singlesig};
     use lwk_signer::SwSigner;
 
     #[test]
@@ -758,4 +848,574 @@ mod tests {
 
         assert_eq!(5372789003087276099, wollet.status());
     }
+
+    #[test]
+    fn test_updates_applied() {
+        let bytes = lwk_test_util::update_test_vector_bytes();
+
+        let update = crate::Update::deserialize(&bytes[..]).unwrap();
+        let exp = "ct(slip77(9c8e4f05c7711a98c838be228bcb84924d4570ca53f35fa1c793e58841d47023),elwpkh([73c5da0a/84'/1'/0']tpubDC8msFGeGuwnKG9Upg7DM2b4DaRqg3CUZa5g8v2SRQ6K4NSkxUgd7HsL2XVWbVm39yBA4LAxysQAm397zwQSQoQgewGiYZqrA9DsP4zbQ1M/<0;1>/*))";
+        let mut wollet = new_wollet(exp);
+        assert_eq!(wollet.updates_applied(), 0);
+        let status = wollet.status();
+
+        wollet.apply_update(update).unwrap();
+        assert_eq!(wollet.updates_applied(), 1);
+        assert_ne!(wollet.status(), status);
+    }
+
+    #[test]
+    fn test_utxo_changes() {
+        use crate::elements::{TxIn, TxOut};
+        use crate::update::DownloadTxResult;
+
+        let bytes = lwk_test_util::update_test_vector_bytes();
+        let tip = crate::Update::deserialize(&bytes[..]).unwrap().tip;
+        let update = |txs: Vec<Transaction>, unblinds, txid_height_delete, scripts| Update {
+            txid_height_new: txs.iter().map(|tx| (tx.txid(), Some(1))).collect(),
+            new_txs: DownloadTxResult {
+                txs: txs.into_iter().map(|tx| (tx.txid(), tx)).collect(),
+                unblinds,
+            },
+            txid_height_delete,
+            timestamps: vec![],
+            scripts,
+            tip: tip.clone(),
+        };
+
+        let exp = "ct(slip77(9c8e4f05c7711a98c838be228bcb84924d4570ca53f35fa1c793e58841d47023),elwpkh([73c5da0a/84'/1'/0']tpubDC8msFGeGuwnKG9Upg7DM2b4DaRqg3CUZa5g8v2SRQ6K4NSkxUgd7HsL2XVWbVm39yBA4LAxysQAm397zwQSQoQgewGiYZqrA9DsP4zbQ1M/<0;1>/*))";
+        let mut wollet = new_wollet(exp);
+        assert!(wollet.take_utxo_changes().reset);
+        assert!(!wollet.take_utxo_changes().reset);
+
+        // A transaction funding the wallet
+        let script_pubkey = wollet.address(Some(0)).unwrap().address().script_pubkey();
+        let funding = Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                script_pubkey: script_pubkey.clone(),
+                ..Default::default()
+            }],
+        };
+        let outpoint = OutPoint::new(funding.txid(), 0);
+        let secrets = lwk_test_util::tx_out_secrets_test_vector();
+        let scripts = [(script_pubkey, (Chain::External, ChildNumber::from(0)))].into();
+        let funded = update(vec![funding], vec![(outpoint, secrets)], vec![], scripts);
+        wollet.apply_update(funded).unwrap();
+        let changes = wollet.take_utxo_changes();
+        assert!(!changes.reset);
+        assert_eq!(changes.added.len(), 1);
+        assert_eq!(changes.added[0].outpoint, outpoint);
+        assert_eq!(changes.added[0].unblinded, secrets);
+        assert!(changes.spent.is_empty());
+        assert_eq!(wollet.utxos().unwrap().len(), 1);
+
+        // A transaction spending it
+        let spending = Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: outpoint,
+                ..Default::default()
+            }],
+            output: vec![],
+        };
+        let spending_txid = spending.txid();
+        wollet
+            .apply_update(update(vec![spending], vec![], vec![], HashMap::new()))
+            .unwrap();
+        let changes = wollet.take_utxo_changes();
+        assert!(!changes.reset);
+        assert!(changes.added.is_empty());
+        assert_eq!(changes.spent, vec![outpoint]);
+        assert!(wollet.utxos().unwrap().is_empty());
+
+        // Removing a wallet transaction requires to compute the UTXOs from scratch
+        let reorg = update(
+            vec![],
+            vec![],
+            vec![outpoint.txid, spending_txid],
+            HashMap::new(),
+        );
+        wollet.apply_update(reorg).unwrap();
+        assert!(wollet.take_utxo_changes().reset);
+    }
+
+    #[test]
+    fn test_descriptor_without_wildcard() {
+        let desc = "ct(slip77(9c8e4f05c7711a98c838be228bcb84924d4570ca53f35fa1c793e58841d47023),elwpkh([73c5da0a/84'/1'/0']tpubDC8msFGeGuwnKG9Upg7DM2b4DaRqg3CUZa5g8v2SRQ6K4NSkxUgd7HsL2XVWbVm39yBA4LAxysQAm397zwQSQoQgewGiYZqrA9DsP4zbQ1M/0/5))";
+        let wollet = new_wollet(desc);
+        assert!(!wollet.wollet_descriptor().is_ranged());
+
+        let addr = wollet.address(None).unwrap();
+        assert_eq!(addr.index(), 0);
+        let other = wollet.address(Some(3)).unwrap();
+        assert_eq!(other.index(), 0);
+        assert_eq!(addr.address(), other.address());
+        assert_eq!(wollet.change(Some(7)).unwrap().address(), addr.address());
+    }
+
+    #[test]
+    fn test_combine_dedupes_signatures() {
+        let desc = "ct(slip77(ab5824f4477b4ebb00a132adfd8eb0b7935cf24f6ac151add5d1913db374ce92),elwpkh([759db348/84'/1'/0']tpubDCRMaF33e44pcJj534LXVhFbHibPbJ5vuLhSSPFAw57kYURv4tzXFL6LSnd78bkjqdmE3USedkbpXJUPA1tdzKfuYSL7PianceqAhwL2UkA/<0;1>/*))";
+        let wollet = new_wollet(desc);
+
+        let secret_key = crate::secp256k1::SecretKey::from_slice(&[1u8; 32]).unwrap();
+        let public_key = crate::bitcoin::PublicKey::new(secret_key.public_key(&EC));
+        let mut pset = PartiallySignedTransaction::new_v2();
+        let mut input = elements::pset::Input::from_prevout(elements::OutPoint::default());
+        input.partial_sigs.insert(public_key, vec![1, 2, 3]);
+        pset.add_input(input);
+
+        let combined = wollet.combine(&[pset.clone(), pset.clone()]).unwrap();
+        assert_eq!(combined.inputs()[0].partial_sigs.len(), 1);
+        assert_eq!(
+            combined.inputs()[0].partial_sigs,
+            pset.inputs()[0].partial_sigs
+        );
+    }
+
+    /// A wallet with a single explicit output of `asset`, the policy asset if `None`
+    fn funded_wollet(signer: &SwSigner, satoshi: u64, asset: Option<AssetId>) -> Wollet {
+        use crate::elements::confidential::{AssetBlindingFactor, ValueBlindingFactor};
+        use crate::elements::{confidential, LockTime, TxOut, TxOutSecrets, TxOutWitness};
+
+        let desc: WolletDescriptor = singlesig_desc(
+            signer,
+            Singlesig::Wpkh,
+            DescriptorBlindingKey::Slip77,
+            false,
+        )
+        .unwrap()
+        .parse()
+        .unwrap();
+        let mut wollet =
+            Wollet::new(ElementsNetwork::LiquidTestnet, NoPersist::new(), desc).unwrap();
+        let script_pubkey = wollet.address(Some(0)).unwrap().address().script_pubkey();
+        let asset = asset.unwrap_or(wollet.policy_asset());
+        let tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                asset: confidential::Asset::Explicit(asset),
+                value: confidential::Value::Explicit(satoshi),
+                nonce: confidential::Nonce::Null,
+                script_pubkey: script_pubkey.clone(),
+                witness: TxOutWitness::default(),
+            }],
+        };
+        let txid = tx.txid();
+        let secrets = TxOutSecrets::new(
+            asset,
+            AssetBlindingFactor::zero(),
+            satoshi,
+            ValueBlindingFactor::zero(),
+        );
+        let bytes = lwk_test_util::update_test_vector_bytes();
+        let mut update = crate::Update::deserialize(&bytes[..]).unwrap();
+        update.new_txs.txs = vec![(txid, tx)];
+        update.new_txs.unblinds = vec![(OutPoint::new(txid, 0), secrets)];
+        update.txid_height_new = vec![(txid, Some(1))];
+        update.txid_height_delete = vec![];
+        update.scripts = [(script_pubkey, (Chain::External, ChildNumber::from(0)))].into();
+        wollet.apply_update(update).unwrap();
+        wollet
+    }
+
+    #[test]
+    fn test_blinded_issuance() {
+        use crate::elements::confidential::Value;
+
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let issue = |blinded| {
+            wollet
+                .tx_builder()
+                .issue_asset(1_000, None, 1, None, None)
+                .unwrap()
+                .blinded_issuance(blinded)
+                .finish()
+                .unwrap()
+        };
+
+        let pset = issue(false);
+        let explicit = pset.inputs()[0].asset_issuance();
+        assert_eq!(explicit.amount, Value::Explicit(1_000));
+        assert_eq!(explicit.inflation_keys, Value::Explicit(1));
+
+        let blinded_pset = issue(true);
+        let input = &blinded_pset.inputs()[0];
+        let blinded = input.asset_issuance();
+        assert!(blinded.amount.is_confidential());
+        assert!(blinded.inflation_keys.is_confidential());
+        assert!(input.issuance_value_amount.is_none());
+        assert!(input.issuance_inflation_keys.is_none());
+        // Same asset, but a different reissuance token
+        let (asset, token) = pset.inputs()[0].issuance_ids();
+        let (blinded_asset, blinded_token) = input.issuance_ids();
+        assert_eq!(asset, blinded_asset);
+        assert_ne!(token, blinded_token);
+
+        // The blinding factors of the issuance are balanced and the rangeproofs are valid
+        let tx = blinded_pset.extract_tx().unwrap();
+        let spent: Vec<_> = blinded_pset
+            .inputs()
+            .iter()
+            .map(|i| i.witness_utxo.clone().unwrap())
+            .collect();
+        tx.verify_tx_amt_proofs(&EC, &spent).unwrap();
+    }
+
+    #[test]
+    fn test_exclude_utxos() {
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let address = wollet.address(Some(1)).unwrap();
+        let excluded = [wollet.utxos().unwrap()[0].outpoint];
+        let send = |excluded: &[OutPoint]| {
+            wollet
+                .tx_builder()
+                .add_lbtc_recipient(address.address(), 1_000)
+                .unwrap()
+                .exclude_utxos(excluded)
+                .finish()
+        };
+        assert!(send(&[]).is_ok());
+        let err = send(&excluded).unwrap_err();
+        assert_eq!(err.to_string(), Error::InsufficientFunds.to_string());
+    }
+
+    #[test]
+    fn test_drain_lbtc_to() {
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let address = wollet.address(Some(5)).unwrap();
+        let pset = wollet
+            .tx_builder()
+            .drain_lbtc_to(address.address().clone())
+            .finish()
+            .unwrap();
+        // A single output receiving everything but the fee, and the fee output
+        assert_eq!(pset.n_outputs(), 2);
+        let output = &pset.outputs()[0];
+        assert_eq!(output.script_pubkey, address.address().script_pubkey());
+        let fee = pset.outputs()[1].amount.unwrap();
+        assert!(fee > 0);
+        assert_eq!(output.amount, Some(100_000 - fee));
+    }
+
+    #[test]
+    fn test_privacy() {
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let address = wollet.address(Some(5)).unwrap();
+        let send = |privacy, satoshi| {
+            wollet
+                .tx_builder()
+                .add_lbtc_recipient(address.address(), satoshi)
+                .unwrap()
+                .privacy(privacy)
+                .finish()
+                .unwrap()
+        };
+        let fee = |pset: &PartiallySignedTransaction| {
+            let fee_output = pset.outputs().last().unwrap();
+            assert!(fee_output.script_pubkey.is_empty());
+            fee_output.amount.unwrap()
+        };
+
+        // Recipient, change and fee
+        let pset = send(false, 10_000);
+        assert_eq!(pset.n_outputs(), 3);
+        let fee_without_decoy = fee(&pset);
+
+        // Recipient, change, decoy change and fee: the fee is still last and it's higher
+        let pset = send(true, 10_000);
+        assert_eq!(pset.n_outputs(), 4);
+        assert!(fee(&pset) > fee_without_decoy);
+        let change: Vec<_> = (0..2)
+            .map(|i| wollet.change(Some(i)).unwrap().address().script_pubkey())
+            .collect();
+        let n_change = pset.outputs()[..3]
+            .iter()
+            .filter(|o| change.contains(&o.script_pubkey))
+            .count();
+        assert_eq!(n_change, 2);
+        let spent: Vec<_> = pset
+            .inputs()
+            .iter()
+            .map(|i| i.witness_utxo.clone().unwrap())
+            .collect();
+        let tx = pset.extract_tx().unwrap();
+        tx.verify_tx_amt_proofs(&EC, &spent).unwrap();
+
+        // The change is too small for a decoy
+        let pset = send(true, 95_000);
+        assert_eq!(pset.n_outputs(), 3);
+        fee(&pset);
+
+        // The change is large enough with the temporary fee, but not with the actual one
+        let pset = wollet
+            .tx_builder()
+            .add_lbtc_recipient(address.address(), 10_000)
+            .unwrap()
+            .privacy(true)
+            .fee(Some(100_000 - 10_000 - DECOY_MIN_CHANGE + 1))
+            .finish()
+            .unwrap();
+        assert_eq!(pset.n_outputs(), 3);
+        let change = pset
+            .outputs()
+            .iter()
+            .find(|o| o.script_pubkey == wollet.change(Some(0)).unwrap().address().script_pubkey())
+            .unwrap();
+        assert_eq!(change.amount, Some(DECOY_MIN_CHANGE - 1));
+        assert_eq!(fee(&pset), 100_000 - 10_000 - DECOY_MIN_CHANGE + 1);
+    }
+
+    #[test]
+    fn test_spend_weight() {
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let utxo = &wollet.utxos().unwrap()[0];
+        let weight = wollet.spend_weight(&utxo.script_pubkey).unwrap();
+
+        // Spending the output adds the weight to a transaction without inputs
+        let mut pset = wollet
+            .tx_builder()
+            .drain_lbtc_to(wollet.address(Some(5)).unwrap().address().clone())
+            .finish()
+            .unwrap();
+        signer.sign(&mut pset).unwrap();
+        let tx = wollet.finalize(&mut pset).unwrap();
+        let mut without_input = tx.clone();
+        without_input.input.clear();
+        let actual = tx.weight() - without_input.weight();
+        assert!(weight >= actual && weight < actual + 8, "{weight} {actual}");
+
+        let err = wollet.spend_weight(&Script::new()).unwrap_err();
+        assert!(matches!(err, Error::ScriptNotMine));
+    }
+
+    #[test]
+    fn test_absolute_fee() {
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let address = wollet.address(Some(5)).unwrap();
+        let send = |fee| {
+            wollet
+                .tx_builder()
+                .add_lbtc_recipient(address.address(), 10_000)
+                .unwrap()
+                .fee(Some(fee))
+                .finish()
+        };
+        let pset = send(5_000).unwrap();
+        let n_outputs = pset.n_outputs();
+        assert_eq!(pset.outputs()[n_outputs - 1].amount, Some(5_000));
+
+        let err = send(1).unwrap_err();
+        assert!(matches!(err, Error::FeeTooLow { fee: 1, .. }), "{err}");
+        let err = send(95_000).unwrap_err();
+        assert!(matches!(err, Error::InsufficientFunds), "{err}");
+    }
+
+    #[test]
+    fn test_estimate_finalized_weight() {
+        let (signer, _) = SwSigner::random(false).unwrap();
+        let wollet = funded_wollet(&signer, 100_000, None);
+        let address = wollet.address(Some(5)).unwrap();
+        let mut pset = wollet
+            .tx_builder()
+            .drain_lbtc_to(address.address().clone())
+            .finish()
+            .unwrap();
+        let estimate = wollet.estimate_finalized_weight(&pset).unwrap();
+        assert!(estimate > pset.extract_tx().unwrap().weight());
+
+        signer.sign(&mut pset).unwrap();
+        assert_eq!(wollet.estimate_finalized_weight(&pset).unwrap(), estimate);
+        let tx = wollet.finalize(&mut pset).unwrap();
+        // signatures may be a couple of bytes shorter than the largest ones
+        assert!(estimate >= tx.weight() && estimate < tx.weight() + 8);
+        assert_eq!(
+            wollet.estimate_finalized_weight(&pset).unwrap(),
+            tx.weight()
+        );
+
+        let mut foreign = pset.clone();
+        foreign.inputs_mut()[0].final_script_witness = None;
+        foreign.inputs_mut()[0]
+            .witness_utxo
+            .as_mut()
+            .unwrap()
+            .script_pubkey = Script::new();
+        let err = wollet.estimate_finalized_weight(&foreign).unwrap_err();
+        assert!(matches!(err, Error::ScriptNotMine));
+    }
+
+    #[test]
+    fn test_multipath_chains() {
+        let view_key = "slip77(ab5824f4477b4ebb00a132adfd8eb0b7935cf24f6ac151add5d1913db374ce92)";
+        let xpub = "[759db348/84'/1'/0']tpubDCRMaF33e44pcJj534LXVhFbHibPbJ5vuLhSSPFAw57kYURv4tzXFL6LSnd78bkjqdmE3USedkbpXJUPA1tdzKfuYSL7PianceqAhwL2UkA";
+        let multipath = new_wollet(&format!("ct({view_key},elwpkh({xpub}/<0;1>/*))"));
+        let external = new_wollet(&format!("ct({view_key},elwpkh({xpub}/0/*))"));
+        let internal = new_wollet(&format!("ct({view_key},elwpkh({xpub}/1/*))"));
+        assert!(multipath.descriptor.descriptor().is_multipath());
+
+        for i in 0..3 {
+            let receive = multipath.address(Some(i)).unwrap();
+            let change = multipath.change(Some(i)).unwrap();
+            assert_eq!(
+                receive.address(),
+                external.address(Some(i)).unwrap().address()
+            );
+            assert_eq!(
+                change.address(),
+                internal.address(Some(i)).unwrap().address()
+            );
+            assert_ne!(receive.address(), change.address());
+        }
+    }
+
+    #[test]
+    fn test_add_to_pset() {
+        let (signer_a, _) = SwSigner::random(false).unwrap();
+        let (signer_b, _) = SwSigner::random(false).unwrap();
+        let wollet_a = funded_wollet(&signer_a, 100_000, None);
+        let wollet_b = funded_wollet(&signer_b, 50_000, None);
+        let asset = wollet_a.policy_asset();
+        let utxos_a: Vec<_> = wollet_a
+            .utxos()
+            .unwrap()
+            .iter()
+            .map(|u| u.outpoint)
+            .collect();
+        let utxos_b: Vec<_> = wollet_b
+            .utxos()
+            .unwrap()
+            .iter()
+            .map(|u| u.outpoint)
+            .collect();
+
+        let mut pset = PartiallySignedTransaction::new_v2();
+        let addr = wollet_b.address(Some(1)).unwrap().address().clone();
+        let recipient = crate::Recipient::from_address(10_000, &addr, asset);
+        let err = wollet_b
+            .add_to_pset(&mut pset, &utxos_a, &[], 0)
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::UtxoNotMine(utxos_a[0]).to_string());
+        wollet_a
+            .add_to_pset(&mut pset, &utxos_a, &[recipient.clone()], 500)
+            .unwrap();
+        // recipient, change and fee
+        assert_eq!(pset.n_outputs(), 3);
+        wollet_b
+            .add_to_pset(&mut pset, &utxos_b, &[recipient.clone()], 100)
+            .unwrap();
+        // recipient and change, the fee output is reused
+        assert_eq!(pset.n_outputs(), 5);
+        assert_eq!(pset.outputs()[2].amount, Some(600));
+        let err = wollet_b
+            .add_to_pset(&mut pset, &utxos_b, &[], 0)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::UtxoAlreadyInPset(utxos_b[0]).to_string()
+        );
+
+        assert_eq!(
+            wollet_a
+                .blind_pset(&mut PartiallySignedTransaction::new_v2())
+                .unwrap_err()
+                .to_string(),
+            Error::NothingToBlind.to_string()
+        );
+        wollet_a.blind_pset(&mut pset).unwrap();
+        assert!(!pset.global.scalars.is_empty());
+        let err = wollet_b
+            .add_to_pset(&mut pset, &utxos_b, &[recipient], 0)
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::PsetAlreadyBlinded.to_string());
+        wollet_b.blind_pset(&mut pset).unwrap();
+        assert!(pset.global.scalars.is_empty());
+
+        signer_a.sign(&mut pset).unwrap();
+        signer_b.sign(&mut pset).unwrap();
+        let tx = wollet_a.finalize(&mut pset).unwrap();
+        let spent: Vec<_> = pset
+            .inputs()
+            .iter()
+            .map(|i| i.witness_utxo.clone().unwrap())
+            .collect();
+        tx.verify_tx_amt_proofs(&EC, &spent).unwrap();
+    }
+
+    #[test]
+    fn test_swap() {
+        let (signer_maker, _) = SwSigner::random(false).unwrap();
+        let (signer_taker, _) = SwSigner::random(false).unwrap();
+        let asset = AssetId::from_slice(&[1; 32]).unwrap();
+        let maker = funded_wollet(&signer_maker, 1_000, Some(asset));
+        let taker = funded_wollet(&signer_taker, 100_000, None);
+        let lbtc = maker.policy_asset();
+
+        let err = maker
+            .swap_propose(asset, 2_000, lbtc, 50_000, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::SwapInsufficientFunds {
+                asset,
+                needed: 2_000,
+                available: 1_000
+            }
+            .to_string()
+        );
+        assert!(maker.swap_propose(asset, 600, asset, 600, &[]).is_err());
+        let excluded = [maker.utxos().unwrap()[0].outpoint];
+        let err = maker
+            .swap_propose(asset, 600, lbtc, 50_000, &excluded)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::SwapInsufficientFunds { available: 0, .. }
+        ));
+        let mut pset = maker.swap_propose(asset, 600, lbtc, 50_000, &[]).unwrap();
+        // the output receiving L-BTC and the change
+        assert_eq!(pset.n_outputs(), 2);
+
+        let err = taker
+            .swap_accept(&mut PartiallySignedTransaction::new_v2(), None, &[])
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::NotASwapProposal.to_string());
+        let err = funded_wollet(&signer_taker, 10_000, None)
+            .swap_accept(&mut pset.clone(), None, &[])
+            .unwrap_err();
+        assert!(matches!(err, Error::SwapInsufficientFunds { .. }));
+
+        taker.swap_accept(&mut pset, None, &[]).unwrap();
+        // the output receiving the asset, the L-BTC change and the fee
+        assert_eq!(pset.n_outputs(), 5);
+        let fee = pset.outputs()[4].amount.unwrap();
+        assert!(fee > 0);
+        maker.blind_pset(&mut pset).unwrap();
+        assert!(pset.global.scalars.is_empty());
+
+        signer_maker.sign(&mut pset).unwrap();
+        signer_taker.sign(&mut pset).unwrap();
+        let tx = maker.finalize(&mut pset).unwrap();
+        let spent: Vec<_> = pset
+            .inputs()
+            .iter()
+            .map(|i| i.witness_utxo.clone().unwrap())
+            .collect();
+        tx.verify_tx_amt_proofs(&EC, &spent).unwrap();
+        // the estimate covers the actual transaction at 0.1 sat/vB
+        assert!(fee * 10 >= tx.vsize() as u64);
+        assert!(fee * 10 < tx.vsize() as u64 * 11 / 10);
+    }
 }
@@ -235,7 +235,6 @@ fn unsupported_descriptor() {
     let desc_p2pkh = format!("ct({view_key},elpkh({xpub1}/*))");
     let desc_p2sh = format!("ct({view_key},elsh(multi(2,{xpub1}/*,{xpub2}/*)))",);
     let desc_p2tr = format!("ct({view_key},eltr({xpub1}/*))");
-    let desc_no_wildcard = format!("ct({view_key},elwpkh({xpub1}))");
 
     let desc_multi_path_1 = format!("ct({view_key},elwpkh({xpub1}/<0;1;2>/*))");
     let desc_multi_path_2 = format!("ct({view_key},elwpkh({xpub1}/<0;1>/0/*))");
@@ -247,10 +246,6 @@ fn unsupported_descriptor() {
         (desc_p2pkh, Error::UnsupportedDescriptorNonV0),
         (desc_p2sh, Error::UnsupportedDescriptorNonV0),
         (desc_p2tr, Error::UnsupportedDescriptorNonV0),
-        (
-            desc_no_wildcard,
-            Error::UnsupportedDescriptorWithoutWildcard,
-        ),
         (desc_multi_path_1, Error::UnsupportedMultipathDescriptor),
         (desc_multi_path_2, Error::UnsupportedMultipathDescriptor),
         (desc_multi_path_3, Error::UnsupportedMultipathDescriptor),
@@ -406,6 +401,52 @@ fn contract() {
     }
 }
 
+#[test]
+fn blinded_issuance() {
+    let server = setup(false);
+    for blinded in [false, true] {
+        let signer = generate_signer();
+        let view_key = generate_view_key();
+        let desc = format!("ct({},elwpkh({}/*))", view_key, signer.xpub());
+        let signers = [&AnySigner::Software(signer.clone())];
+        let mut wallet = TestWollet::new(&server.electrs.electrum_url, &desc);
+        wallet.fund_btc(&server);
+
+        let satoshi_a = 100_000;
+        let satoshi_t = 1;
+        let mut pset = wallet
+            .tx_builder()
+            .issue_asset(satoshi_a, None, satoshi_t, None, None)
+            .unwrap()
+            .blinded_issuance(blinded)
+            .finish()
+            .unwrap();
+        let (asset, token) = pset.inputs()[0].issuance_ids();
+
+        // The wallet can inspect its own blinded issuances
+        let details = wallet.wollet.get_details(&pset).unwrap();
+        let issuance = &details.issuances[0];
+        assert!(issuance.is_issuance());
+        assert_eq!(issuance.asset_satoshi().is_none(), blinded);
+        assert_eq!(issuance.token_satoshi().is_none(), blinded);
+        assert_eq!(details.balance.balances[&asset], satoshi_a as i64);
+        assert_eq!(details.balance.balances[&token], satoshi_t as i64);
+
+        wallet.sign(&signer, &mut pset);
+        let txid = wallet.send(&mut pset);
+        assert_eq!(wallet.balance(&asset), satoshi_a);
+        assert_eq!(wallet.balance(&token), satoshi_t);
+
+        let tx = wallet.wollet.transaction(&txid).unwrap().unwrap().tx;
+        let issuance = &tx.input[0].asset_issuance;
+        assert_eq!(issuance.amount.is_confidential(), blinded);
+        assert_eq!(issuance.inflation_keys.is_confidential(), blinded);
+
+        // Reissuing is possible in both cases
+        wallet.reissueasset(&signers, 10_000, &asset, None);
+    }
+}
+
 #[test]
 fn multiple_descriptors() {
     // Use a different descriptors for the asset and the reissuance token
@@ -772,6 +813,133 @@ fn multisig_flow() {
 
     // * Multisig Sign: Complete
 }
+#[test]
+fn spend_path() {
+    let server = setup(false);
+    let signer_a = generate_signer();
+    let signer_b = generate_signer();
+    let view_key = generate_view_key();
+    // B can spend alone only after 2 blocks
+    let desc_str = format!(
+        "ct({},elwsh(or_d(pk({}/*),and_v(v:pk({}/*),older(2)))))",
+        view_key,
+        signer_a.xpub(),
+        signer_b.xpub()
+    );
+    let mut wallet = TestWollet::new(&server.electrs.electrum_url, &desc_str);
+    wallet.fund_btc(&server);
+    let node_addr = server.node_getnewaddress();
+
+    let err = wallet
+        .tx_builder()
+        .add_lbtc_recipient(&node_addr, 1_000)
+        .unwrap()
+        .spend_path(Some(SpendPath::Older(10)))
+        .finish()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        Error::SpendPathNotInDescriptor("older(10)".to_string()).to_string()
+    );
+
+    // The funding transaction is unconfirmed
+    let err = wallet
+        .tx_builder()
+        .add_lbtc_recipient(&node_addr, 1_000)
+        .unwrap()
+        .spend_path(Some(SpendPath::Older(2)))
+        .finish()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        Error::SpendPathNotSatisfiable("older(2)".to_string()).to_string()
+    );
+
+    server.generate(2);
+    let mut pset = None;
+    for _ in 0..120 {
+        wallet.sync();
+        if let Ok(p) = wallet
+            .tx_builder()
+            .add_lbtc_recipient(&node_addr, 1_000)
+            .unwrap()
+            .spend_path(Some(SpendPath::Older(2)))
+            .finish()
+        {
+            pset = Some(p);
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+    let mut pset = pset.expect("timelock expired");
+    wallet.sign(&signer_b, &mut pset);
+    wallet.send(&mut pset);
+}
+
+#[test]
+fn add_to_pset() {
+    let server = setup(false);
+    let signer_a = generate_signer();
+    let desc_a = format!("ct({},elwpkh({}/*))", generate_view_key(), signer_a.xpub());
+    let mut wallet_a = TestWollet::new(&server.electrs.electrum_url, &desc_a);
+    wallet_a.fund_btc(&server);
+    let signer_b = generate_signer();
+    let desc_b = format!("ct({},elwpkh({}/*))", generate_view_key(), signer_b.xpub());
+    let mut wallet_b = TestWollet::new(&server.electrs.electrum_url, &desc_b);
+    wallet_b.fund_btc(&server);
+    let policy_asset = wallet_a.policy_asset();
+    let outpoints = |w: &TestWollet| -> Vec<_> {
+        w.wollet
+            .utxos()
+            .unwrap()
+            .iter()
+            .map(|u| u.outpoint)
+            .collect()
+    };
+
+    // A pays the fee, both parties send to the node
+    let node_addr = server.node_getnewaddress();
+    let mut pset = elements::pset::PartiallySignedTransaction::new_v2();
+    let recipient_a = Recipient::from_address(10_000, &node_addr, policy_asset);
+    wallet_a
+        .wollet
+        .add_to_pset(&mut pset, &outpoints(&wallet_a), &[recipient_a], 5_000)
+        .unwrap();
+    let recipient_b = Recipient::from_address(20_000, &node_addr, policy_asset);
+    let utxos_b = outpoints(&wallet_b);
+    wallet_b
+        .wollet
+        .add_to_pset(&mut pset, &utxos_b, &[recipient_b.clone()], 0)
+        .unwrap();
+    // 2 recipients, 2 changes and the fee
+    assert_eq!(pset.n_outputs(), 5);
+
+    let err = wallet_b
+        .wollet
+        .add_to_pset(&mut pset, &utxos_b, &[recipient_b.clone()], 0)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        Error::UtxoAlreadyInPset(utxos_b[0]).to_string()
+    );
+
+    wallet_a.wollet.blind_pset(&mut pset).unwrap();
+    // Inputs cannot be added once outputs are blinded
+    let err = wallet_b
+        .wollet
+        .add_to_pset(&mut pset, &utxos_b, &[recipient_b], 0)
+        .unwrap_err();
+    assert_eq!(err.to_string(), Error::PsetAlreadyBlinded.to_string());
+    wallet_b.wollet.blind_pset(&mut pset).unwrap();
+
+    let balance_b = wallet_b.balance(&policy_asset);
+    wallet_a.sign(&signer_a, &mut pset);
+    wallet_b.sign(&signer_b, &mut pset);
+    wallet_a.send(&mut pset);
+    wallet_b.sync();
+    assert_eq!(wallet_b.balance(&policy_asset), balance_b - 20_000);
+}
+
 #[test]
 fn jade_sign_wollet_pset() {
     let server = setup(false);